@@ -11,6 +11,13 @@ ffi.cdef[[
     char* message;
     int created;
     int line_number;
+    int column_number;
+    char* framework;
+    int found_existing;
+    char* preview_content;
+    char* warnings;
+    int has_only_stub;
+    char* resolver_name;
   } TestsmithResult;
 
   // Find or create test file (language auto-detected from source_path)
@@ -19,11 +26,53 @@ ffi.cdef[[
     const char* structure,
     const char* framework,
     int create,
-    int dry_run
+    int dry_run,
+    int force
   );
 
+  // Same as testsmith_find_or_create, plus a decision log written to *decision_log
+  // (free it with testsmith_string_free)
+  TestsmithResult* testsmith_find_or_create_verbose(
+    const char* source_path,
+    const char* structure,
+    const char* framework,
+    int create,
+    int dry_run,
+    int force,
+    char** decision_log
+  );
+
+  // Free a string returned through an out-parameter
+  void testsmith_string_free(char* s);
+
+  typedef struct {
+    TestsmithResult* results;
+    size_t count;
+  } TestsmithBatchResult;
+
+  // Find or create test files for several sources, sharing one cache load/save
+  TestsmithBatchResult* testsmith_find_or_create_batch(
+    const char** source_paths,
+    size_t count,
+    const char* structure,
+    const char* framework,
+    int create,
+    int dry_run,
+    int force
+  );
+
+  // Plan tests for every source under a directory without writing
+  // (message holds "source\ttest_path\taction" lines)
+  TestsmithResult* testsmith_plan_directory(const char* dir_path);
+
+  // Clear the cache (project_root = NULL clears everything)
+  TestsmithResult* testsmith_clear_cache(const char* project_root);
+
   // Free result
   void testsmith_result_free(TestsmithResult* result);
+
+  // Free batch result
+  void testsmith_batch_free(TestsmithBatchResult* batch);
 ]]
 
 -- Try to load the shared library
@@ -113,10 +162,27 @@ local function load_library()
   return false
 end
 
+-- Convert a TestsmithResult into a Lua table (does not free it)
+local function to_lua_result(result)
+  return {
+    success = result.success ~= 0,
+    message = ffi.string(result.message),
+    created = result.created ~= 0,
+    line_number = result.line_number,
+    column_number = result.column_number,
+    framework = result.framework ~= nil and ffi.string(result.framework) or nil,
+    found_existing = result.found_existing ~= 0,
+    preview_content = result.preview_content ~= nil and ffi.string(result.preview_content) or nil,
+    warnings = result.warnings ~= nil and vim.split(ffi.string(result.warnings), "\n") or {},
+    has_only_stub = result.has_only_stub ~= 0,
+    resolver_name = result.resolver_name ~= nil and ffi.string(result.resolver_name) or nil,
+  }
+end
+
 --- Find or create test file via FFI
 ---@param source_path string Path to source file (language auto-detected from extension)
----@param opts table Options: structure, framework ("auto" for auto-detection or explicit framework), create, dry_run
----@return table Result with fields: success, message, created, line_number
+---@param opts table Options: structure, framework ("auto" for auto-detection or explicit framework), create, dry_run, force (allow a test path outside the project root)
+---@return table Result with fields: success, message, created, line_number, column_number, framework, found_existing, preview_content (dry runs), warnings, has_only_stub, resolver_name
 function M.find_or_create(source_path, opts)
   opts = opts or {}
 
@@ -132,6 +198,7 @@ function M.find_or_create(source_path, opts)
   local framework = opts.framework or "auto"
   local create = opts.create ~= false and 1 or 0
   local dry_run = opts.dry_run and 1 or 0
+  local force = opts.force and 1 or 0
 
   -- Call the FFI function (language auto-detected from source_path, framework auto-detected if "auto" is specified)
   local result = lib.testsmith_find_or_create(
@@ -139,7 +206,8 @@ function M.find_or_create(source_path, opts)
     structure,
     framework,
     create,
-    dry_run
+    dry_run,
+    force
   )
 
   if result == nil then
@@ -150,20 +218,165 @@ function M.find_or_create(source_path, opts)
   end
 
   -- Convert result to Lua table
-  local message = ffi.string(result.message)
-  local success = result.success ~= 0
-  local created = result.created ~= 0
-  local line_number = result.line_number
+  local converted = to_lua_result(result)
 
   -- Free the result
   lib.testsmith_result_free(result)
 
-  return {
-    success = success,
-    message = message,
-    created = created,
-    line_number = line_number,
-  }
+  return converted
+end
+
+--- Find or create test file via FFI, also returning why each choice was made
+---@param source_path string Path to source file (language auto-detected from extension)
+---@param opts table Options: same as find_or_create
+---@return table Result as for find_or_create, plus `decisions` (list of log lines)
+function M.find_or_create_verbose(source_path, opts)
+  opts = opts or {}
+
+  if not load_library() then
+    return {
+      success = false,
+      message = "Testsmith library not found. Make sure libtestsmith_nvim is built and accessible.",
+      decisions = {},
+    }
+  end
+
+  local structure = opts.structure or "maven"
+  local framework = opts.framework or "auto"
+  local create = opts.create ~= false and 1 or 0
+  local dry_run = opts.dry_run and 1 or 0
+  local force = opts.force and 1 or 0
+
+  local log = ffi.new("char*[1]")
+  local result = lib.testsmith_find_or_create_verbose(source_path, structure, framework, create, dry_run, force, log)
+
+  if result == nil then
+    return {
+      success = false,
+      message = "FFI call failed",
+      decisions = {},
+    }
+  end
+
+  local converted = to_lua_result(result)
+  lib.testsmith_result_free(result)
+
+  converted.decisions = {}
+  if log[0] ~= nil then
+    converted.decisions = vim.split(ffi.string(log[0]), "\n", { plain = true })
+    lib.testsmith_string_free(log[0])
+  end
+
+  return converted
+end
+
+--- Find or create test files for several sources in one FFI call
+---@param source_paths string[] Paths to source files (language auto-detected per file)
+---@param opts table Options: same as find_or_create, applied to every path
+---@return table[] Results in the same order as source_paths
+function M.find_or_create_batch(source_paths, opts)
+  opts = opts or {}
+
+  if not load_library() then
+    local results = {}
+    for i = 1, #source_paths do
+      results[i] = {
+        success = false,
+        message = "Testsmith library not found. Make sure libtestsmith_nvim is built and accessible.",
+      }
+    end
+    return results
+  end
+
+  -- "auto" detects each file's structure from its own project root (mixed monorepos)
+  local structure = opts.structure or "auto"
+  local framework = opts.framework or "auto"
+  local create = opts.create ~= false and 1 or 0
+  local dry_run = opts.dry_run and 1 or 0
+  local force = opts.force and 1 or 0
+
+  local count = #source_paths
+  local c_paths = ffi.new("const char*[?]", count)
+  for i, path in ipairs(source_paths) do
+    c_paths[i - 1] = path
+  end
+
+  local batch = lib.testsmith_find_or_create_batch(c_paths, count, structure, framework, create, dry_run, force)
+
+  if batch == nil then
+    return {}
+  end
+
+  local results = {}
+  for i = 0, tonumber(batch.count) - 1 do
+    results[i + 1] = to_lua_result(batch.results[i])
+  end
+
+  lib.testsmith_batch_free(batch)
+
+  return results
+end
+
+--- Plan the tests for every source under a directory, without writing anything
+---@param dir_path string Directory to plan
+---@return table Result with fields: success, message (error only), plan (list of { source, test_path, action })
+function M.plan_directory(dir_path)
+  if not load_library() then
+    return {
+      success = false,
+      message = "Testsmith library not found. Make sure libtestsmith_nvim is built and accessible.",
+      plan = {},
+    }
+  end
+
+  local result = lib.testsmith_plan_directory(dir_path)
+  if result == nil then
+    return {
+      success = false,
+      message = "FFI call failed",
+      plan = {},
+    }
+  end
+
+  local converted = to_lua_result(result)
+  lib.testsmith_result_free(result)
+
+  converted.plan = {}
+  if converted.success then
+    for _, line in ipairs(vim.split(converted.message, "\n", { plain = true, trimempty = true })) do
+      local source, test_path, action = line:match("^([^\t]*)\t([^\t]*)\t(.*)$")
+      if source then
+        table.insert(converted.plan, { source = source, test_path = test_path, action = action })
+      end
+    end
+  end
+
+  return converted
+end
+
+--- Clear cached framework/structure detection via FFI
+---@param project_root string|nil Project root to forget (nil clears the whole cache)
+---@return table Result with fields: success, message, created (true if anything was removed)
+function M.clear_cache(project_root)
+  if not load_library() then
+    return {
+      success = false,
+      message = "Testsmith library not found. Make sure libtestsmith_nvim is built and accessible.",
+    }
+  end
+
+  local result = lib.testsmith_clear_cache(project_root)
+  if result == nil then
+    return {
+      success = false,
+      message = "FFI call failed",
+    }
+  end
+
+  local converted = to_lua_result(result)
+  lib.testsmith_result_free(result)
+
+  return converted
 end
 
 --- Check if FFI is available
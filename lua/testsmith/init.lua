@@ -51,7 +51,7 @@ end
 
 --- Execute testsmith via FFI (if available) or CLI
 ---@param source_file string Path to source file
----@param opts table|nil Additional options (structure, framework="auto", dry_run, create)
+---@param opts table|nil Additional options (structure, framework="auto", dry_run, create, force)
 ---@return string, integer output, exit_code, boolean used_ffi
 local function run_testsmith(source_file, opts)
   opts = opts or {}
@@ -67,6 +67,7 @@ local function run_testsmith(source_file, opts)
       framework = opts.framework or "auto",
       create = opts.create ~= false,
       dry_run = opts.dry_run or false,
+      force = opts.force or false,
     }
 
     local result = ffi_module.find_or_create(source_file, ffi_opts)
@@ -108,6 +109,10 @@ local function run_testsmith(source_file, opts)
     table.insert(cmd, "--dry-run")
   end
 
+  if opts.force then
+    table.insert(cmd, "--force")
+  end
+
   local output = vim.fn.system(table.concat(cmd, " "))
   local exit_code = vim.v.shell_error
 
@@ -126,6 +131,11 @@ local function auto_detect_structure(file_path)
     return "same-file"
   end
 
+  -- Vue/Svelte component and Go tests sit next to the source
+  if ext == "vue" or ext == "svelte" or ext == "go" then
+    return "adjacent"
+  end
+
   -- Default to configured structure (usually maven for Java)
   return M.config.structure
 end
@@ -146,6 +156,56 @@ local function is_test_file(file_path)
     return true, "rust_suffix"
   end
 
+  -- Go test pattern: foo_test.go
+  if file_name:match("_test%.go$") then
+    return true, "go_suffix"
+  end
+
+  -- Adjacent C++ test pattern: foo.test.cpp
+  if file_name:match("%.test%.cpp$") then
+    return true, "cpp_adjacent"
+  end
+
+  -- Elixir test pattern: foo_test.exs
+  if file_name:match("_test%.exs$") then
+    return true, "elixir_suffix"
+  end
+
+  -- Scala test pattern: FooSpec.scala
+  if file_name:match("Spec%.scala$") then
+    return true, "scala_spec"
+  end
+
+  -- PHP test pattern: FooTest.php
+  if file_name:match("Test%.php$") then
+    return true, "php_suffix"
+  end
+
+  -- Dart test pattern: foo_test.dart
+  if file_name:match("_test%.dart$") then
+    return true, "dart_suffix"
+  end
+
+  -- Ruby test pattern: foo_spec.rb
+  if file_name:match("_spec%.rb$") then
+    return true, "ruby_spec"
+  end
+
+  -- Haskell test pattern: FooSpec.hs
+  if file_name:match("Spec%.hs$") then
+    return true, "haskell_spec"
+  end
+
+  -- C# test pattern: FooTests.cs
+  if file_name:match("Tests%.cs$") then
+    return true, "csharp_suffix"
+  end
+
+  -- Swift test pattern: FooTests.swift
+  if file_name:match("Tests%.swift$") then
+    return true, "swift_suffix"
+  end
+
   return false, nil
 end
 
@@ -161,8 +221,8 @@ local function find_source_for_test(test_file, test_type)
     -- FooTest.java → Foo.java (in src/main structure)
     local source_name = file_name:gsub("Test%.java$", ".java")
 
-    -- Look in Maven structure (src/main/java)
-    local src_main = test_file:gsub("src/test/java", "src/main/java")
+    -- Look in Maven structure (src/main/java), also from Android's src/androidTest/java
+    local src_main = test_file:gsub("src/test/java", "src/main/java"):gsub("src/androidTest/java", "src/main/java")
     src_main = src_main:gsub("Test%.java$", ".java")
 
     if vim.fn.filereadable(src_main) == 1 then
@@ -182,6 +242,68 @@ local function find_source_for_test(test_file, test_type)
     if vim.fn.filereadable(same_dir) == 1 then
       return same_dir
     end
+  elseif test_type == "go_suffix" then
+    -- foo_test.go → foo.go
+    local same_dir = dir .. file_name:gsub("_test%.go$", ".go")
+    if vim.fn.filereadable(same_dir) == 1 then
+      return same_dir
+    end
+  elseif test_type == "cpp_adjacent" then
+    -- foo.test.cpp → foo.cpp (or foo.hpp) next to it
+    for _, ext in ipairs({ "cpp", "hpp" }) do
+      local same_dir = dir .. file_name:gsub("%.test%.cpp$", "." .. ext)
+      if vim.fn.filereadable(same_dir) == 1 then
+        return same_dir
+      end
+    end
+  elseif test_type == "elixir_suffix" then
+    -- test/my_app/foo_test.exs → lib/my_app/foo.ex
+    local lib_path = test_file:gsub("/test/", "/lib/"):gsub("_test%.exs$", ".ex")
+    if vim.fn.filereadable(lib_path) == 1 then
+      return lib_path
+    end
+  elseif test_type == "scala_spec" then
+    -- src/test/scala/.../FooSpec.scala → src/main/scala/.../Foo.scala
+    local src_main = test_file:gsub("src/test/scala", "src/main/scala"):gsub("Spec%.scala$", ".scala")
+    if vim.fn.filereadable(src_main) == 1 then
+      return src_main
+    end
+  elseif test_type == "php_suffix" then
+    -- tests/Http/ClientTest.php → src/Http/Client.php
+    local src_path = test_file:gsub("/tests?/", "/src/"):gsub("Test%.php$", ".php")
+    if vim.fn.filereadable(src_path) == 1 then
+      return src_path
+    end
+  elseif test_type == "dart_suffix" then
+    -- test/src/foo_test.dart → lib/src/foo.dart
+    local lib_path = test_file:gsub("/test/", "/lib/"):gsub("_test%.dart$", ".dart")
+    if vim.fn.filereadable(lib_path) == 1 then
+      return lib_path
+    end
+  elseif test_type == "ruby_spec" then
+    -- spec/my_gem/foo_spec.rb → lib/my_gem/foo.rb
+    local lib_path = test_file:gsub("/spec/", "/lib/"):gsub("_spec%.rb$", ".rb")
+    if vim.fn.filereadable(lib_path) == 1 then
+      return lib_path
+    end
+  elseif test_type == "haskell_spec" then
+    -- test/Data/ParserSpec.hs → src/Data/Parser.hs
+    local src_path = test_file:gsub("/test/", "/src/"):gsub("Spec%.hs$", ".hs")
+    if vim.fn.filereadable(src_path) == 1 then
+      return src_path
+    end
+  elseif test_type == "csharp_suffix" then
+    -- tests/Services/ParserTests.cs → src/Services/Parser.cs
+    local src_path = test_file:gsub("/tests?/", "/src/"):gsub("Tests%.cs$", ".cs")
+    if vim.fn.filereadable(src_path) == 1 then
+      return src_path
+    end
+  elseif test_type == "swift_suffix" then
+    -- Tests/ParserTests/LexerTests.swift → Sources/Parser/Lexer.swift
+    local src_path = test_file:gsub("/Tests/([^/]-)Tests/", "/Sources/%1/"):gsub("Tests%.swift$", ".swift")
+    if vim.fn.filereadable(src_path) == 1 then
+      return src_path
+    end
   end
 
   return nil
@@ -249,6 +371,10 @@ function M.find_or_create_test(opts)
       local result = ffi_module.find_or_create(current_file, ffi_opts)
 
       if result.success then
+        for _, warning in ipairs(result.warnings or {}) do
+          vim.notify(warning, vim.log.levels.WARN, { title = "Testsmith" })
+        end
+
         local test_file = result.message
         local open_cmd = "edit"
 
@@ -262,10 +388,10 @@ function M.find_or_create_test(opts)
 
         if M.config.open_on_create or not result.created then
           vim.cmd(open_cmd .. " " .. vim.fn.fnameescape(test_file))
-          -- Set cursor to the TODO line if available
+          -- Set cursor on the TODO comment if available
           if result.line_number and result.line_number > 0 then
             local win = vim.api.nvim_get_current_win()
-            vim.api.nvim_win_set_cursor(win, { result.line_number, 0 })
+            vim.api.nvim_win_set_cursor(win, { result.line_number, result.column_number or 0 })
           end
         end
       else
@@ -304,7 +430,23 @@ function M.find_or_create_test(opts)
     end,
 
     on_stderr = function(_, data)
-      local error_msg = table.concat(data, "\n")
+      -- The CLI reports non-fatal problems as "warning: ..." lines
+      local errors = {}
+      for _, line in ipairs(data) do
+        local warning = line:match("^warning: (.*)")
+        if warning then
+          vim.schedule(function()
+            vim.notify(warning, vim.log.levels.WARN, { title = "Testsmith" })
+          end)
+        elseif line ~= "" then
+          table.insert(errors, line)
+        end
+      end
+      if #errors == 0 then
+        return
+      end
+
+      local error_msg = table.concat(errors, "\n")
       vim.schedule(function()
         vim.notify("Error: " .. error_msg, vim.log.levels.ERROR, { title = "Testsmith" })
       end)
@@ -390,4 +532,29 @@ function M.preview_test()
   end
 end
 
+--- Clear cached framework/structure detection
+---@param project_root string|nil Project root to forget (nil clears the whole cache)
+function M.clear_cache(project_root)
+  local message, exit_code
+
+  if M.config.prefer_ffi and ffi_module.is_available() then
+    local result = ffi_module.clear_cache(project_root)
+    message, exit_code = result.message, result.success and 0 or 1
+  else
+    local cmd = { M.config.binary, "clear" }
+    if project_root then
+      table.insert(cmd, "--project")
+      table.insert(cmd, vim.fn.shellescape(project_root))
+    end
+    message = vim.fn.system(table.concat(cmd, " "))
+    exit_code = vim.v.shell_error
+  end
+
+  if exit_code == 0 then
+    vim.notify(vim.trim(message), vim.log.levels.INFO, { title = "Testsmith" })
+  else
+    vim.notify("Error: " .. message, vim.log.levels.ERROR, { title = "Testsmith" })
+  end
+end
+
 return M
@@ -33,6 +33,14 @@ vim.api.nvim_create_user_command("TestsmithFindTab", function()
   testsmith.find_or_create_test({ split = "tab" })
 end, { desc = "Find or create test file in new tab" })
 
+vim.api.nvim_create_user_command("TestsmithClearCache", function(args)
+  local project_root = nil
+  if args.args ~= "" then
+    project_root = (vim.fn.fnamemodify(args.args, ":p"):gsub("/$", ""))
+  end
+  testsmith.clear_cache(project_root)
+end, { nargs = "?", complete = "dir", desc = "Clear cached framework detection (optionally for one project root)" })
+
 -- Auto-setup with defaults (can be overridden by user)
 if not vim.g.testsmith_setup_done then
   testsmith.setup()
@@ -1,4 +1,5 @@
 use crate::cli::{Framework, StructureType};
+use crate::config::project_root::config_file_paths;
 use crate::error::TestsmithError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -7,75 +8,182 @@ use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 /// Cached data for a specific language in a project
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LanguageCache {
     pub framework: String,
     pub structure: String,
     pub last_used: u64,
+    /// Set when the user pinned their explicit choices; pinned entries are never re-detected
+    #[serde(default)]
+    pub pinned: bool,
+    /// testsmith version that wrote the entry (empty for entries from older versions)
+    #[serde(default)]
+    pub written_by: String,
 }
 
-/// The complete cache structure: project_root -> language -> cache data
+/// testsmith version stamped on the entries it writes
+pub const CACHE_WRITER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+impl LanguageCache {
+    /// Decision-log note for an entry another testsmith version wrote (None if this one did)
+    pub fn version_note(&self) -> Option<String> {
+        match self.written_by.as_str() {
+            CACHE_WRITER_VERSION => None,
+            "" => Some(format!("Cache entry written by a testsmith older than {}", CACHE_WRITER_VERSION)),
+            version => Some(format!(
+                "Cache entry written by testsmith {} (this is {})",
+                version, CACHE_WRITER_VERSION
+            )),
+        }
+    }
+}
+
+/// The complete cache structure: project_root -> entry key (see `entry_key`) -> cache data
 pub type ProjectCache = HashMap<String, HashMap<String, LanguageCache>>;
 
-/// Get the cache file path: ~/.local/share/nvim/testsmith/testsmith.projects.json
-fn get_cache_file_path() -> Result<PathBuf, TestsmithError> {
-    let data_dir = if cfg!(target_os = "windows") {
-        std::env::var("APPDATA")
-            .ok()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| PathBuf::from("."))
-    } else {
-        // Use XDG_DATA_HOME or default to ~/.local/share
-        if let Ok(xdg_data) = std::env::var("XDG_DATA_HOME") {
-            PathBuf::from(xdg_data)
+/// Entries unused for longer than this (90 days) are pruned
+pub const CACHE_MAX_AGE_SECS: u64 = 90 * 24 * 60 * 60;
+
+/// Name of the cache file inside the cache directory
+const CACHE_FILE_NAME: &str = "testsmith.projects.json";
+
+/// On-disk project cache rooted at a data directory
+/// (`<base_dir>/nvim/testsmith/testsmith.projects.json`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheStore {
+    base_dir: PathBuf,
+}
+
+impl CacheStore {
+    /// Store rooted at an explicit data directory (e.g. a temp dir in tests)
+    pub fn new(base_dir: PathBuf) -> Self {
+        CacheStore { base_dir }
+    }
+
+    /// Store rooted at the user's data directory: $XDG_DATA_HOME, ~/.local/share or %APPDATA%
+    pub fn from_env() -> Result<Self, TestsmithError> {
+        let data_dir = if cfg!(target_os = "windows") {
+            std::env::var("APPDATA")
+                .ok()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("."))
         } else {
-            let home = std::env::var("HOME").map_err(|_| TestsmithError::CacheError {
-                reason: "Could not determine home directory".to_string(),
-            })?;
-            PathBuf::from(home).join(".local/share")
+            // Use XDG_DATA_HOME or default to ~/.local/share
+            if let Ok(xdg_data) = std::env::var("XDG_DATA_HOME") {
+                PathBuf::from(xdg_data)
+            } else {
+                let home = std::env::var("HOME").map_err(|_| TestsmithError::CacheError {
+                    reason: "Could not determine home directory".to_string(),
+                })?;
+                PathBuf::from(home).join(".local/share")
+            }
+        };
+
+        Ok(CacheStore::new(data_dir))
+    }
+
+    /// Path of the cache file (the directory is created on save, not here)
+    pub fn cache_file_path(&self) -> PathBuf {
+        self.base_dir.join("nvim/testsmith").join(CACHE_FILE_NAME)
+    }
+
+    /// Load the cache, or an empty one if the file doesn't exist yet
+    pub fn load(&self) -> Result<ProjectCache, TestsmithError> {
+        let cache_file = self.cache_file_path();
+        if !cache_file.exists() {
+            return Ok(HashMap::new());
         }
-    };
 
-    let cache_dir = data_dir.join("nvim/testsmith");
+        let content = fs::read_to_string(&cache_file).map_err(|e| TestsmithError::CacheError {
+            reason: format!("Failed to read cache file: {}", e),
+        })?;
 
-    // Create directory if it doesn't exist
-    fs::create_dir_all(&cache_dir).map_err(|e| TestsmithError::CacheError {
-        reason: format!("Failed to create cache directory: {}", e),
-    })?;
+        serde_json::from_str(&content).map_err(|e| TestsmithError::CacheError {
+            reason: format!("Failed to parse cache JSON: {}", e),
+        })
+    }
 
-    Ok(cache_dir.join("testsmith.projects.json"))
-}
+    /// Write the cache, creating the cache directory if needed
+    pub fn save(&self, cache: &ProjectCache) -> Result<(), TestsmithError> {
+        let cache_file = self.cache_file_path();
+        if let Some(cache_dir) = cache_file.parent() {
+            fs::create_dir_all(cache_dir).map_err(|e| TestsmithError::CacheError {
+                reason: format!("Failed to create cache directory: {}", e),
+            })?;
+        }
 
-/// Load the cache from disk
-pub fn load_cache() -> Result<ProjectCache, TestsmithError> {
-    let cache_file = get_cache_file_path()?;
+        let json = serde_json::to_string_pretty(cache).map_err(|e| TestsmithError::CacheError {
+            reason: format!("Failed to serialize cache: {}", e),
+        })?;
+
+        fs::write(&cache_file, json).map_err(|e| TestsmithError::CacheError {
+            reason: format!("Failed to write cache file: {}", e),
+        })?;
 
-    if !cache_file.exists() {
-        return Ok(HashMap::new());
+        Ok(())
     }
 
-    let content = fs::read_to_string(&cache_file).map_err(|e| TestsmithError::CacheError {
-        reason: format!("Failed to read cache file: {}", e),
-    })?;
+    /// Record the framework/structure for a project and language, persisting immediately
+    pub fn update_entry(
+        &self,
+        project_root: &Path,
+        language: &str,
+        framework: &Framework,
+        structure: &StructureType,
+    ) -> Result<(), TestsmithError> {
+        let mut cache = self.load()?;
+        update_cache_entry(&mut cache, project_root, language, framework, structure)?;
+        self.save(&cache)
+    }
 
-    serde_json::from_str(&content).map_err(|e| TestsmithError::CacheError {
-        reason: format!("Failed to parse cache JSON: {}", e),
-    })
-}
+    /// Delete the cache entirely, or forget a single project root
+    /// Returns whether anything was removed
+    pub fn clear(&self, project_root: Option<&Path>) -> Result<bool, TestsmithError> {
+        let cache_file = self.cache_file_path();
+        let Some(project_root) = project_root else {
+            if !cache_file.exists() {
+                return Ok(false);
+            }
+            fs::remove_file(&cache_file).map_err(|e| TestsmithError::CacheError {
+                reason: format!("Failed to delete cache file: {}", e),
+            })?;
+            return Ok(true);
+        };
 
-/// Save the cache to disk
-pub fn save_cache(cache: &ProjectCache) -> Result<(), TestsmithError> {
-    let cache_file = get_cache_file_path()?;
+        let mut cache = self.load()?;
 
-    let json = serde_json::to_string_pretty(cache).map_err(|e| TestsmithError::CacheError {
-        reason: format!("Failed to serialize cache: {}", e),
-    })?;
+        // Roots are stored as detected (usually absolute), so also try the canonical form
+        let mut keys = vec![project_root.to_path_buf()];
+        if let Ok(canonical) = project_root.canonicalize() {
+            keys.push(canonical);
+        }
+        let mut removed = false;
+        for key in keys.iter().filter_map(|key| key.to_str()) {
+            removed |= cache.remove(key).is_some();
+        }
 
-    fs::write(&cache_file, json).map_err(|e| TestsmithError::CacheError {
-        reason: format!("Failed to write cache file: {}", e),
-    })?;
+        if removed {
+            self.save(&cache)?;
+        }
 
-    Ok(())
+        Ok(removed)
+    }
+}
+
+/// Load the cache from the default location
+pub fn load_cache() -> Result<ProjectCache, TestsmithError> {
+    CacheStore::from_env()?.load()
+}
+
+/// Save the cache to the default location
+pub fn save_cache(cache: &ProjectCache) -> Result<(), TestsmithError> {
+    CacheStore::from_env()?.save(cache)
+}
+
+/// Clear the cache at the default location: delete it entirely, or forget a single project root
+/// Returns whether anything was removed
+pub fn clear_cache(project_root: Option<&Path>) -> Result<bool, TestsmithError> {
+    CacheStore::from_env()?.clear(project_root)
 }
 
 /// Get current Unix timestamp
@@ -86,6 +194,16 @@ pub fn current_timestamp() -> u64 {
         .as_secs()
 }
 
+/// Key of a language's entry within a project: the language itself for the project root,
+/// or `Java@services/billing` for a build module nested below it, since nested modules
+/// (e.g. Gradle subprojects under one `.testsmithrc`) can each use a different framework
+pub fn entry_key(language: &str, module: Option<&Path>) -> String {
+    match module.and_then(|module| module.to_str()).filter(|module| !module.is_empty()) {
+        Some(module) => format!("{}@{}", language, module.replace('\\', "/")),
+        None => language.to_string(),
+    }
+}
+
 /// Update or insert a cache entry for a project
 pub fn update_cache_entry(
     cache: &mut ProjectCache,
@@ -101,20 +219,66 @@ pub fn update_cache_entry(
         })?
         .to_string();
 
+    let languages = cache.entry(root_str).or_default();
+    let pinned = languages.get(language).is_some_and(|entry| entry.pinned);
+
     let lang_cache = LanguageCache {
         framework: format!("{:?}", framework),
         structure: format!("{:?}", structure),
         last_used: current_timestamp(),
+        pinned,
+        written_by: CACHE_WRITER_VERSION.to_string(),
     };
 
-    cache
-        .entry(root_str)
-        .or_insert_with(HashMap::new)
-        .insert(language.to_string(), lang_cache);
+    languages.insert(language.to_string(), lang_cache);
 
     Ok(())
 }
 
+/// Pin an existing entry so staleness checks and age pruning leave it alone
+/// Returns false if there is no entry to pin
+pub fn pin_cache_entry(cache: &mut ProjectCache, project_root: &Path, language: &str) -> bool {
+    let entry = project_root
+        .to_str()
+        .and_then(|root| cache.get_mut(root))
+        .and_then(|languages| languages.get_mut(language));
+
+    match entry {
+        Some(entry) => {
+            entry.pinned = true;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Forget a project's entry for one language, pinned or not, so the next run detects afresh
+/// Returns whether there was an entry to remove
+pub fn remove_cache_entry(cache: &mut ProjectCache, project_root: &Path, language: &str) -> bool {
+    let Some(root_str) = project_root.to_str() else {
+        return false;
+    };
+    let Some(languages) = cache.get_mut(root_str) else {
+        return false;
+    };
+
+    let removed = languages.remove(language).is_some();
+    if languages.is_empty() {
+        cache.remove(root_str);
+    }
+    removed
+}
+
+/// Drop unpinned entries not used within `max_age_secs`, and projects whose root no longer exists
+pub fn prune_cache(cache: &mut ProjectCache, max_age_secs: u64) {
+    let cutoff = current_timestamp().saturating_sub(max_age_secs);
+
+    cache.retain(|project_root, languages| {
+        languages.retain(|_, entry| entry.pinned || entry.last_used >= cutoff);
+        !languages.is_empty() && Path::new(project_root).is_dir()
+    });
+}
+
 /// Get a cache entry for a project and language
 pub fn get_cache_entry(
     cache: &ProjectCache,
@@ -132,18 +296,17 @@ pub fn is_cache_stale(
     last_used: u64,
     config_files: &[&str],
 ) -> bool {
-    for config_file in config_files {
-        let path = project_root.join(config_file);
-        if path.exists() {
-            if let Ok(metadata) = fs::metadata(&path) {
-                if let Ok(modified) = metadata.modified() {
-                    if let Ok(duration) = modified.duration_since(SystemTime::UNIX_EPOCH) {
-                        let mod_time = duration.as_secs();
-                        if mod_time > last_used {
-                            return true;
-                        }
-                    }
-                }
+    let paths = config_files
+        .iter()
+        .flat_map(|config_file| config_file_paths(project_root, config_file));
+    for path in paths {
+        if let Ok(metadata) = fs::metadata(&path)
+            && let Ok(modified) = metadata.modified()
+            && let Ok(duration) = modified.duration_since(SystemTime::UNIX_EPOCH)
+        {
+            let mod_time = duration.as_secs();
+            if mod_time > last_used {
+                return true;
             }
         }
     }
@@ -189,6 +352,24 @@ mod tests {
         assert!(get_cache_entry(&cache, root, "rust").is_some());
     }
 
+    #[test]
+    fn test_entry_key_includes_nested_module() {
+        assert_eq!(entry_key("Java", None), "Java");
+        assert_eq!(entry_key("Java", Some(Path::new(""))), "Java");
+        assert_eq!(entry_key("Java", Some(Path::new("services/billing"))), "Java@services/billing");
+
+        let mut cache = ProjectCache::new();
+        let root = Path::new("/project/root");
+        let legacy = entry_key("Java", Some(Path::new("legacy")));
+        let modern = entry_key("Java", Some(Path::new("modern")));
+        update_cache_entry(&mut cache, root, &legacy, &Framework::JUnit4, &StructureType::Gradle).unwrap();
+        update_cache_entry(&mut cache, root, &modern, &Framework::JUnit, &StructureType::Gradle).unwrap();
+
+        assert_eq!(get_cache_entry(&cache, root, "Java@legacy").unwrap().framework, "JUnit4");
+        assert_eq!(get_cache_entry(&cache, root, "Java@modern").unwrap().framework, "JUnit");
+        assert!(get_cache_entry(&cache, root, "Java").is_none());
+    }
+
     #[test]
     fn test_is_cache_stale() {
         let temp_dir = TempDir::new().unwrap();
@@ -222,6 +403,144 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_prune_cache_removes_old_and_missing_projects() {
+        let temp_dir = TempDir::new().unwrap();
+        let live_root = temp_dir.path().join("live");
+        let old_root = temp_dir.path().join("old");
+        let missing_root = temp_dir.path().join("deleted");
+        fs::create_dir_all(&live_root).unwrap();
+        fs::create_dir_all(&old_root).unwrap();
+
+        let mut cache = ProjectCache::new();
+        for root in [&live_root, &old_root, &missing_root] {
+            update_cache_entry(&mut cache, root, "java", &Framework::JUnit, &StructureType::Maven).unwrap();
+        }
+        update_cache_entry(&mut cache, &live_root, "rust", &Framework::Native, &StructureType::SameFile)
+            .unwrap();
+        cache.get_mut(old_root.to_str().unwrap()).unwrap().get_mut("java").unwrap().last_used = 0;
+        cache.get_mut(live_root.to_str().unwrap()).unwrap().get_mut("rust").unwrap().last_used = 0;
+
+        prune_cache(&mut cache, CACHE_MAX_AGE_SECS);
+
+        assert_eq!(cache.len(), 1);
+        assert!(get_cache_entry(&cache, &live_root, "java").is_some());
+        assert!(get_cache_entry(&cache, &live_root, "rust").is_none());
+        assert!(get_cache_entry(&cache, &old_root, "java").is_none());
+        assert!(get_cache_entry(&cache, &missing_root, "java").is_none());
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = CacheStore::new(temp_dir.path().to_path_buf());
+        assert!(store.load().unwrap().is_empty());
+
+        let root = Path::new("/project/root");
+        store.update_entry(root, "java", &Framework::JUnit4, &StructureType::Gradle).unwrap();
+        assert!(store.cache_file_path().starts_with(temp_dir.path()));
+
+        let reloaded = CacheStore::new(temp_dir.path().to_path_buf()).load().unwrap();
+        let entry = get_cache_entry(&reloaded, root, "java").unwrap();
+        assert_eq!(entry.framework, "JUnit4");
+        assert_eq!(entry.structure, "Gradle");
+    }
+
+    #[test]
+    fn test_clear_whole_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = CacheStore::new(temp_dir.path().to_path_buf());
+        store.update_entry(Path::new("/a"), "java", &Framework::JUnit, &StructureType::Maven).unwrap();
+
+        assert!(store.clear(None).unwrap());
+        assert!(!store.cache_file_path().exists());
+        assert!(!store.clear(None).unwrap());
+    }
+
+    #[test]
+    fn test_clear_single_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = CacheStore::new(temp_dir.path().to_path_buf());
+        store.update_entry(Path::new("/a"), "java", &Framework::JUnit4, &StructureType::Maven).unwrap();
+        store.update_entry(Path::new("/b"), "java", &Framework::JUnit, &StructureType::Gradle).unwrap();
+
+        assert!(store.clear(Some(Path::new("/a"))).unwrap());
+        let remaining = store.load().unwrap();
+        assert!(get_cache_entry(&remaining, Path::new("/a"), "java").is_none());
+        assert!(get_cache_entry(&remaining, Path::new("/b"), "java").is_some());
+
+        assert!(!store.clear(Some(Path::new("/a"))).unwrap());
+    }
+
+    #[test]
+    fn test_update_keeps_pin() {
+        let mut cache = ProjectCache::new();
+        let root = Path::new("/project/root");
+
+        update_cache_entry(&mut cache, root, "java", &Framework::JUnit4, &StructureType::Gradle).unwrap();
+        assert!(pin_cache_entry(&mut cache, root, "java"));
+        update_cache_entry(&mut cache, root, "java", &Framework::JUnit4, &StructureType::Gradle).unwrap();
+
+        assert!(get_cache_entry(&cache, root, "java").unwrap().pinned);
+        assert!(!pin_cache_entry(&mut cache, root, "rust"));
+    }
+
+    #[test]
+    fn test_remove_cache_entry_keeps_other_languages() {
+        let mut cache = ProjectCache::new();
+        let root = Path::new("/project/root");
+
+        update_cache_entry(&mut cache, root, "java", &Framework::JUnit4, &StructureType::Gradle).unwrap();
+        update_cache_entry(&mut cache, root, "rust", &Framework::Native, &StructureType::SameFile).unwrap();
+
+        assert!(remove_cache_entry(&mut cache, root, "java"));
+        assert!(get_cache_entry(&cache, root, "java").is_none());
+        assert!(get_cache_entry(&cache, root, "rust").is_some());
+
+        assert!(remove_cache_entry(&mut cache, root, "rust"));
+        assert!(cache.is_empty());
+        assert!(!remove_cache_entry(&mut cache, root, "rust"));
+    }
+
+    #[test]
+    fn test_entry_records_writer_version() {
+        let mut cache = ProjectCache::new();
+        let root = Path::new("/project/root");
+        update_cache_entry(&mut cache, root, "java", &Framework::JUnit, &StructureType::Maven).unwrap();
+
+        let entry = get_cache_entry(&cache, root, "java").unwrap();
+        assert_eq!(entry.written_by, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_entry_without_version_deserializes() {
+        let json = r#"{"/project/root": {"java": {"framework": "JUnit4", "structure": "Gradle", "last_used": 1}}}"#;
+        let cache: ProjectCache = serde_json::from_str(json).unwrap();
+
+        let entry = get_cache_entry(&cache, Path::new("/project/root"), "java").unwrap();
+        assert_eq!(entry.framework, "JUnit4");
+        assert_eq!(entry.written_by, "");
+        assert!(!entry.pinned);
+    }
+
+    #[test]
+    fn test_version_note_only_for_other_writers() {
+        let mut cache = ProjectCache::new();
+        let root = Path::new("/project/root");
+        update_cache_entry(&mut cache, root, "java", &Framework::JUnit, &StructureType::Maven).unwrap();
+        let mut entry = get_cache_entry(&cache, root, "java").unwrap().clone();
+        assert_eq!(entry.version_note(), None);
+
+        entry.written_by = "0.0.1".to_string();
+        assert_eq!(
+            entry.version_note(),
+            Some(format!("Cache entry written by testsmith 0.0.1 (this is {})", CACHE_WRITER_VERSION))
+        );
+
+        entry.written_by = String::new();
+        assert!(entry.version_note().unwrap().contains("older than"));
+    }
+
     #[test]
     fn test_cache_serialization() {
         let mut cache = ProjectCache::new();
@@ -1,4 +1,7 @@
-use clap::{Parser, ValueEnum};
+use crate::config::language::default_structure_for_language;
+use crate::config::structure::get_structure_info;
+use crate::template::registry::TemplateRegistry;
+use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -8,12 +11,21 @@ use std::path::PathBuf;
     about = "Find or create test files for source code",
     long_about = "Testsmith finds or creates test files for Java and other languages. \
                   It supports multiple project structures (Maven, flat, etc.) and \
-                  test frameworks (JUnit, native Rust tests, etc.)"
+                  test frameworks (JUnit, native Rust tests, etc.)",
+    subcommand_negates_reqs = true,
+    args_conflicts_with_subcommands = true
 )]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Source file path to find/create test for
-    #[arg(value_name = "FILE")]
-    pub source_file: PathBuf,
+    #[arg(value_name = "FILE", required_unless_present = "list")]
+    pub source_file: Option<PathBuf>,
+
+    /// List supported language/framework combinations and exit
+    #[arg(long)]
+    pub list: bool,
 
     /// Project structure type (auto-detected if not provided)
     #[arg(short, long, value_enum)]
@@ -34,6 +46,181 @@ pub struct Cli {
     /// Show what would be done without creating files
     #[arg(long)]
     pub dry_run: bool,
+
+    /// Print every location the structure accepts for the test, most preferred first, without writing
+    #[arg(long)]
+    pub list_candidates: bool,
+
+    /// Pin the framework/structure for this project so detection never overrides them
+    #[arg(long)]
+    pub pin: bool,
+
+    /// Ignore the cached framework/structure for this project, detect them again and update the cache
+    #[arg(long)]
+    pub refresh: bool,
+
+    /// Generate one test stub per non-private source method (Java, Kotlin and Rust)
+    #[arg(long)]
+    pub method_stubs: bool,
+
+    /// Prefix for generated test function names, e.g. "should" or "should_" (default: "test" for JUnit, "test_" for Rust)
+    #[arg(long)]
+    pub method_prefix: Option<String>,
+
+    /// Test file name suffix, e.g. "Tests" for FooTests.java (default: "Test")
+    #[arg(long)]
+    pub test_suffix: Option<String>,
+
+    /// Test file name prefix, e.g. "Test" for TestFoo.java
+    #[arg(long)]
+    pub test_prefix: Option<String>,
+
+    /// Naming scheme for generated test methods when using --method-stubs
+    #[arg(long, value_enum, default_value = "camel")]
+    pub method_naming: MethodNaming,
+
+    /// Kind of test stub to generate (property needs proptest, quickcheck or jqwik in the build file)
+    #[arg(long, value_enum, default_value = "unit")]
+    pub kind: TestKind,
+
+    /// Put the test in this directory (keeping the computed file name and Java package path)
+    #[arg(long, value_name = "DIR")]
+    pub test_dir: Option<PathBuf>,
+
+    /// With --dry-run, still create the test file's parent directories
+    #[arg(long)]
+    pub prepare_dirs: bool,
+
+    /// Write the test even if its path falls outside the detected project root
+    #[arg(long)]
+    pub force: bool,
+
+    /// Same-file structure only: insert the test module at this 1-based line instead of appending it
+    #[arg(long, value_name = "LINE")]
+    pub insert_at_line: Option<usize>,
+
+    /// What to do when the test class or test module already exists
+    #[arg(long, value_enum, default_value = "skip")]
+    pub on_exists: OnExists,
+
+    /// Shorthand for --on-exists append
+    #[arg(long, conflicts_with = "on_exists")]
+    pub append: bool,
+
+    /// Where --append adds the test inside an existing Rust test module
+    #[arg(long, value_enum, default_value = "bottom")]
+    pub append_position: AppendPosition,
+
+    /// List the source's public methods as TODO comments at the top of the new test
+    #[arg(long)]
+    pub document_api: bool,
+
+    /// Text of the generated TODO comment, e.g. "FIXME: add assertions" (default: "TODO: Implement test")
+    #[arg(long, value_name = "TEXT")]
+    pub todo_text: Option<String>,
+
+    /// Make the new test fail until it is implemented (TDD red phase) instead of passing empty
+    #[arg(long)]
+    pub failing_stub: bool,
+
+    /// JUnit 5 only: generate a @ParameterizedTest fed by @ValueSource instead of a plain @Test
+    #[arg(long)]
+    pub parameterized: bool,
+
+    /// Android projects only: target the instrumented tests in src/androidTest instead of src/test
+    #[arg(long)]
+    pub android_instrumented: bool,
+
+    /// Wrap the --method-stubs tests in "// region" / "// endregion" markers for editor folding
+    #[arg(long)]
+    pub section_markers: bool,
+
+    /// Add what the framework needs to run the tests: the `__main__` guard for Python unittest,
+    /// an index.test.js loading every test for JavaScript/TypeScript in __tests__/
+    #[arg(long)]
+    pub with_runner: bool,
+
+    /// Print the structure, resolver and decisions behind the chosen test path to stderr
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Output format for results and errors
+    #[arg(long, value_enum, default_value = "text")]
+    pub format: OutputFormat,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Clear cached framework/structure detection
+    #[command(alias = "clear-cache")]
+    Clear {
+        /// Only forget this project root instead of clearing everything
+        #[arg(long, value_name = "PATH")]
+        project: Option<PathBuf>,
+    },
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+pub enum OutputFormat {
+    /// Human-readable messages
+    #[value(name = "text")]
+    Text,
+
+    /// JSON objects for editor integrations
+    #[value(name = "json")]
+    Json,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Default, ValueEnum, Debug)]
+pub enum MethodNaming {
+    /// testFoo, with a numeric suffix for repeated names (testFoo2)
+    #[default]
+    #[value(name = "camel")]
+    Camel,
+
+    /// test_foo_1, test_foo_2, numbered per source method name
+    #[value(name = "numbered")]
+    Numbered,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Default, ValueEnum, Debug)]
+pub enum TestKind {
+    /// Example-based test
+    #[default]
+    #[value(name = "unit")]
+    Unit,
+
+    /// Property-based test (proptest or quickcheck for Rust, jqwik for Java)
+    #[value(name = "property")]
+    Property,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Default, ValueEnum, Debug)]
+pub enum OnExists {
+    /// Leave it as is and just position the cursor in it
+    #[default]
+    #[value(name = "skip")]
+    Skip,
+
+    /// Add another test to the existing Java test class or Rust test module
+    #[value(name = "append")]
+    Append,
+
+    /// Fail instead of opening it
+    #[value(name = "error")]
+    Error,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Default, ValueEnum, Debug)]
+pub enum AppendPosition {
+    /// First in the module, after its `use` lines
+    #[value(name = "top")]
+    Top,
+
+    /// Last in the module, before its closing brace
+    #[default]
+    #[value(name = "bottom")]
+    Bottom,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
@@ -42,7 +229,7 @@ pub enum StructureType {
     #[value(name = "maven")]
     Maven,
 
-    /// Same file structure (#[cfg(test)] mod tests for Rust)
+    /// Same file structure (#[cfg(test)] mod tests for Rust, test blocks for Zig)
     #[value(name = "same-file")]
     SameFile,
 
@@ -53,6 +240,18 @@ pub enum StructureType {
     /// Flat structure (src/ and tests/ at root)
     #[value(name = "flat")]
     Flat,
+
+    /// Rust workspace test crate (integration tests in a dedicated member's tests/)
+    #[value(name = "test-crate")]
+    TestCrate,
+
+    /// Adjacent test file next to the source (Foo.vue <-> Foo.test.ts)
+    #[value(name = "adjacent")]
+    Adjacent,
+
+    /// Test file in a `__tests__/` directory beside the source (cart.js <-> __tests__/cart.test.js)
+    #[value(name = "tests-dir")]
+    TestsDir,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Hash)]
@@ -71,6 +270,48 @@ pub enum Language {
 
     #[value(name = "typescript")]
     TypeScript,
+
+    #[value(name = "vue")]
+    Vue,
+
+    #[value(name = "svelte")]
+    Svelte,
+
+    #[value(name = "cpp")]
+    Cpp,
+
+    #[value(name = "kotlin")]
+    Kotlin,
+
+    #[value(name = "go")]
+    Go,
+
+    #[value(name = "elixir")]
+    Elixir,
+
+    #[value(name = "php")]
+    Php,
+
+    #[value(name = "scala")]
+    Scala,
+
+    #[value(name = "csharp")]
+    CSharp,
+
+    #[value(name = "zig")]
+    Zig,
+
+    #[value(name = "dart")]
+    Dart,
+
+    #[value(name = "ruby")]
+    Ruby,
+
+    #[value(name = "haskell")]
+    Haskell,
+
+    #[value(name = "swift")]
+    Swift,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Hash)]
@@ -87,9 +328,150 @@ pub enum Framework {
     #[value(name = "native")]
     Native,
 
+    #[value(name = "rstest")]
+    Rstest,
+
+    #[value(name = "proptest")]
+    Proptest,
+
     #[value(name = "jest")]
     Jest,
 
     #[value(name = "pytest")]
     Pytest,
+
+    #[value(name = "unittest")]
+    Unittest,
+
+    #[value(name = "vitest")]
+    Vitest,
+
+    #[value(name = "gtest", alias = "googletest")]
+    GoogleTest,
+
+    #[value(name = "gotest")]
+    GoTest,
+
+    #[value(name = "exunit")]
+    ExUnit,
+
+    #[value(name = "phpunit")]
+    PHPUnit,
+
+    #[value(name = "scalatest")]
+    ScalaTest,
+
+    #[value(name = "munit")]
+    MUnit,
+
+    #[value(name = "xunit")]
+    XUnit,
+
+    #[value(name = "jasmine")]
+    Jasmine,
+
+    #[value(name = "darttest")]
+    DartTest,
+
+    #[value(name = "mocha")]
+    Mocha,
+
+    #[value(name = "rspec")]
+    RSpec,
+
+    #[value(name = "hspec")]
+    HSpec,
+
+    #[value(name = "xctest")]
+    XCTest,
+}
+
+/// Name of a clap value as typed on the command line (e.g. "junit4")
+pub fn value_name<T: ValueEnum>(value: &T) -> String {
+    value
+        .to_possible_value()
+        .map(|v| v.get_name().to_string())
+        .unwrap_or_default()
+}
+
+/// Render a table of supported language/framework combinations with each
+/// language's default structure, sorted by language then framework
+pub fn list_combinations() -> String {
+    let registry = TemplateRegistry::new();
+    let mut rows = Vec::new();
+
+    let mut languages = Language::value_variants().to_vec();
+    languages.sort();
+    let mut frameworks = Framework::value_variants().to_vec();
+    frameworks.sort();
+
+    for language in &languages {
+        for framework in &frameworks {
+            if registry.is_supported(*language, *framework) {
+                let structure = default_structure_for_language(*language);
+                let info = get_structure_info(structure);
+                rows.push(format!(
+                    "{:<12} {:<10} {:<10} {}",
+                    value_name(language),
+                    value_name(framework),
+                    value_name(&structure),
+                    info.description
+                ));
+            }
+        }
+    }
+
+    format!(
+        "{:<12} {:<10} {:<10} {}\n{}\n",
+        "LANGUAGE",
+        "FRAMEWORK",
+        "STRUCTURE",
+        "DESCRIPTION",
+        rows.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_combinations_contains_supported_rows() {
+        let table = list_combinations();
+        let rows: Vec<Vec<&str>> = table
+            .lines()
+            .map(|line| line.split_whitespace().take(3).collect())
+            .collect();
+
+        assert!(rows.contains(&vec!["java", "junit", "maven"]));
+        assert!(rows.contains(&vec!["rust", "native", "same-file"]));
+        assert!(!rows.contains(&vec!["java", "native", "maven"]));
+    }
+
+    #[test]
+    fn test_source_file_not_required_with_list() {
+        let cli = Cli::try_parse_from(["testsmith-nvim", "--list"]).unwrap();
+        assert!(cli.list);
+        assert!(cli.source_file.is_none());
+        assert!(Cli::try_parse_from(["testsmith-nvim"]).is_err());
+    }
+
+    #[test]
+    fn test_clear_subcommand() {
+        let cli = Cli::try_parse_from(["testsmith-nvim", "clear", "--project", "/work/app"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Clear { project: Some(ref p) }) if p == &PathBuf::from("/work/app")));
+
+        let cli = Cli::try_parse_from(["testsmith-nvim", "clear-cache"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Clear { project: None })));
+    }
+
+    #[test]
+    fn test_append_conflicts_with_explicit_on_exists() {
+        let cli = Cli::try_parse_from(["testsmith-nvim", "Foo.java", "--append"]).unwrap();
+        assert!(cli.append);
+        assert_eq!(cli.on_exists, OnExists::Skip);
+
+        let error = Cli::try_parse_from(["testsmith-nvim", "Foo.java", "--append", "--on-exists", "error"]).unwrap_err();
+        assert_eq!(error.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
 }
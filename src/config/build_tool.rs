@@ -0,0 +1,141 @@
+use crate::config::project_root::config_file_paths;
+use std::path::Path;
+
+/// Build tool that owns a project, identified by the config file it reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildTool {
+    /// pom.xml
+    Maven,
+    /// build.gradle (Groovy DSL)
+    Gradle,
+    /// build.gradle.kts (Kotlin DSL)
+    GradleKts,
+    /// build.sbt
+    Sbt,
+    /// Cargo.toml
+    Cargo,
+    /// package.json
+    Npm,
+    /// pyproject.toml, setup.py or requirements.txt
+    Pip,
+    /// go.mod
+    Go,
+    /// mix.exs
+    Mix,
+    /// composer.json
+    Composer,
+    /// CMakeLists.txt
+    CMake,
+    /// build.zig
+    Zig,
+    /// pubspec.yaml
+    Pub,
+    /// Gemfile
+    Bundler,
+    /// stack.yaml
+    Stack,
+    /// *.cabal
+    Cabal,
+    /// *.csproj or *.sln
+    DotNet,
+    /// Package.swift
+    SwiftPm,
+}
+
+/// Config files and the tool each identifies, in priority order: a JVM project that has both
+/// pom.xml and build.gradle (mid-migration) is still built by Maven
+const BUILD_TOOL_FILES: &[(&str, BuildTool)] = &[
+    ("pom.xml", BuildTool::Maven),
+    ("build.gradle", BuildTool::Gradle),
+    ("build.gradle.kts", BuildTool::GradleKts),
+    ("build.sbt", BuildTool::Sbt),
+    ("Cargo.toml", BuildTool::Cargo),
+    ("package.json", BuildTool::Npm),
+    ("pyproject.toml", BuildTool::Pip),
+    ("setup.py", BuildTool::Pip),
+    ("requirements.txt", BuildTool::Pip),
+    ("go.mod", BuildTool::Go),
+    ("mix.exs", BuildTool::Mix),
+    ("composer.json", BuildTool::Composer),
+    ("CMakeLists.txt", BuildTool::CMake),
+    ("build.zig", BuildTool::Zig),
+    ("pubspec.yaml", BuildTool::Pub),
+    ("Gemfile", BuildTool::Bundler),
+    ("stack.yaml", BuildTool::Stack),
+    ("*.cabal", BuildTool::Cabal),
+    ("*.csproj", BuildTool::DotNet),
+    ("*.sln", BuildTool::DotNet),
+    ("Package.swift", BuildTool::SwiftPm),
+];
+
+impl BuildTool {
+    /// Whether this is one of the Gradle DSLs, whose projects use the Gradle structure
+    pub fn is_gradle(self) -> bool {
+        matches!(self, BuildTool::Gradle | BuildTool::GradleKts)
+    }
+}
+
+/// Detect the build tool from the config files directly inside `project_root`
+/// Returns the tool and the file that identified it
+pub fn detect_build_tool_file(project_root: &Path) -> Option<(BuildTool, &'static str)> {
+    BUILD_TOOL_FILES
+        .iter()
+        .find(|(file, _)| !config_file_paths(project_root, file).is_empty())
+        .map(|&(file, tool)| (tool, file))
+}
+
+/// Detect the build tool from the config files directly inside `project_root`
+pub fn detect_build_tool(project_root: &Path) -> Option<BuildTool> {
+    detect_build_tool_file(project_root).map(|(tool, _)| tool)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Build tool detected for a directory holding exactly these files
+    fn tool_for(files: &[&str]) -> Option<BuildTool> {
+        let temp_dir = TempDir::new().unwrap();
+        for file in files {
+            fs::write(temp_dir.path().join(file), "").unwrap();
+        }
+        detect_build_tool(temp_dir.path())
+    }
+
+    #[test]
+    fn test_jvm_build_tools() {
+        assert_eq!(tool_for(&["pom.xml"]), Some(BuildTool::Maven));
+        assert_eq!(tool_for(&["build.gradle"]), Some(BuildTool::Gradle));
+        assert_eq!(tool_for(&["build.gradle.kts", "settings.gradle.kts"]), Some(BuildTool::GradleKts));
+        assert_eq!(tool_for(&["build.sbt"]), Some(BuildTool::Sbt));
+
+        // Maven wins while a project migrates to Gradle
+        assert_eq!(tool_for(&["build.gradle", "pom.xml"]), Some(BuildTool::Maven));
+    }
+
+    #[test]
+    fn test_other_build_tools() {
+        assert_eq!(tool_for(&["Cargo.toml"]), Some(BuildTool::Cargo));
+        assert_eq!(tool_for(&["package.json", "tsconfig.json"]), Some(BuildTool::Npm));
+        assert_eq!(tool_for(&["pyproject.toml"]), Some(BuildTool::Pip));
+        assert_eq!(tool_for(&["requirements.txt"]), Some(BuildTool::Pip));
+        assert_eq!(tool_for(&["go.mod"]), Some(BuildTool::Go));
+        assert_eq!(tool_for(&["mix.exs"]), Some(BuildTool::Mix));
+        assert_eq!(tool_for(&["composer.json"]), Some(BuildTool::Composer));
+        assert_eq!(tool_for(&["CMakeLists.txt"]), Some(BuildTool::CMake));
+        assert_eq!(tool_for(&["build.zig"]), Some(BuildTool::Zig));
+        assert_eq!(tool_for(&["pubspec.yaml"]), Some(BuildTool::Pub));
+        assert_eq!(tool_for(&["Gemfile"]), Some(BuildTool::Bundler));
+        assert_eq!(tool_for(&["stack.yaml"]), Some(BuildTool::Stack));
+        assert_eq!(tool_for(&["parser.cabal"]), Some(BuildTool::Cabal));
+        assert_eq!(tool_for(&["App.csproj"]), Some(BuildTool::DotNet));
+        assert_eq!(tool_for(&["Package.swift"]), Some(BuildTool::SwiftPm));
+    }
+
+    #[test]
+    fn test_no_build_tool() {
+        assert_eq!(tool_for(&["README.md"]), None);
+    }
+}
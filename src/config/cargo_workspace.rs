@@ -0,0 +1,178 @@
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A member crate of a Cargo workspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    /// Directory containing the member's Cargo.toml
+    pub path: PathBuf,
+    /// Package name from `[package] name = "..."`
+    pub package_name: String,
+}
+
+impl WorkspaceMember {
+    /// Name used to refer to the crate in Rust code (my-crate -> my_crate)
+    pub fn crate_ident(&self) -> String {
+        self.package_name.replace('-', "_")
+    }
+}
+
+/// Return the body of a `[section]` table (up to the next table header)
+pub(crate) fn toml_section<'a>(content: &'a str, section: &str) -> Option<&'a str> {
+    let header = Regex::new(&format!(r"(?m)^\s*\[{}\]\s*$", regex::escape(section))).unwrap();
+    let start = header.find(content)?.end();
+    let rest = &content[start..];
+
+    let next_header = Regex::new(r"(?m)^\s*\[").unwrap();
+    let end = next_header.find(rest).map(|m| m.start()).unwrap_or(rest.len());
+
+    Some(&rest[..end])
+}
+
+/// Read the package name from a crate's Cargo.toml
+pub fn package_name(crate_dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(crate_dir.join("Cargo.toml")).ok()?;
+    let package = toml_section(&content, "package")?;
+
+    let name_regex = Regex::new(r#"(?m)^\s*name\s*=\s*"([^"]+)""#).unwrap();
+    Some(name_regex.captures(package)?.get(1)?.as_str().to_string())
+}
+
+/// Name Rust code uses for the crate whose Cargo.toml is in `project_root` (my-crate -> my_crate)
+pub fn crate_name_from_cargo_toml(project_root: &Path) -> Option<String> {
+    package_name(project_root).map(|name| name.replace('-', "_"))
+}
+
+/// Find the closest ancestor directory whose Cargo.toml declares a `[workspace]`
+pub fn find_workspace_root(start_path: &Path) -> Option<PathBuf> {
+    let canonical_path = start_path
+        .canonicalize()
+        .unwrap_or_else(|_| start_path.to_path_buf());
+
+    canonical_path.ancestors().find_map(|dir| {
+        let content = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+        toml_section(&content, "workspace").map(|_| dir.to_path_buf())
+    })
+}
+
+/// List the members of a workspace, expanding trailing `/*` globs
+pub fn workspace_members(workspace_root: &Path) -> Vec<WorkspaceMember> {
+    let content = match fs::read_to_string(workspace_root.join("Cargo.toml")) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+    let workspace = match toml_section(&content, "workspace") {
+        Some(workspace) => workspace,
+        None => return Vec::new(),
+    };
+
+    let members_regex = Regex::new(r"(?s)members\s*=\s*\[(.*?)\]").unwrap();
+    let entry_regex = Regex::new(r#""([^"]+)""#).unwrap();
+
+    let entries = match members_regex.captures(workspace) {
+        Some(caps) => caps[1].to_string(),
+        None => return Vec::new(),
+    };
+
+    let mut member_dirs = Vec::new();
+    for caps in entry_regex.captures_iter(&entries) {
+        let entry = &caps[1];
+        if let Some(parent) = entry.strip_suffix("/*") {
+            if let Ok(read_dir) = fs::read_dir(workspace_root.join(parent)) {
+                let mut dirs: Vec<PathBuf> = read_dir
+                    .filter_map(|e| e.ok().map(|e| e.path()))
+                    .filter(|p| p.join("Cargo.toml").is_file())
+                    .collect();
+                dirs.sort();
+                member_dirs.extend(dirs);
+            }
+        } else {
+            member_dirs.push(workspace_root.join(entry));
+        }
+    }
+
+    member_dirs
+        .into_iter()
+        .filter_map(|path| {
+            let package_name = package_name(&path)?;
+            Some(WorkspaceMember { path, package_name })
+        })
+        .collect()
+}
+
+/// Find the workspace member that contains the given source file
+pub fn member_for_source(members: &[WorkspaceMember], source_path: &Path) -> Option<WorkspaceMember> {
+    let canonical_path = source_path
+        .canonicalize()
+        .unwrap_or_else(|_| source_path.to_path_buf());
+
+    members
+        .iter()
+        .filter(|member| canonical_path.starts_with(&member.path))
+        .max_by_key(|member| member.path.components().count())
+        .cloned()
+}
+
+/// Find a workspace member by package name or directory name
+pub fn find_member(members: &[WorkspaceMember], name: &str) -> Option<WorkspaceMember> {
+    members
+        .iter()
+        .find(|member| {
+            member.package_name == name
+                || member.path.file_name().and_then(|n| n.to_str()) == Some(name)
+        })
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_crate(dir: &Path, name: &str) {
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n\n[dependencies]\n", name),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_workspace_members_with_glob() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\n    \"crates/*\",\n    \"tests\",\n]\n",
+        )
+        .unwrap();
+        write_crate(&root.join("crates/crate-a"), "crate-a");
+        write_crate(&root.join("crates/crate-b"), "crate-b");
+        write_crate(&root.join("tests"), "integration-tests");
+
+        let members = workspace_members(&root);
+        let names: Vec<&str> = members.iter().map(|m| m.package_name.as_str()).collect();
+        assert_eq!(names, vec!["crate-a", "crate-b", "integration-tests"]);
+
+        let source = root.join("crates/crate-b/src/lib.rs");
+        fs::write(&source, "").unwrap();
+        assert_eq!(find_workspace_root(&source).unwrap(), root);
+        assert_eq!(member_for_source(&members, &source).unwrap().crate_ident(), "crate_b");
+        assert_eq!(find_member(&members, "tests").unwrap().package_name, "integration-tests");
+    }
+
+    #[test]
+    fn test_package_name_ignores_other_sections() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[dependencies]\nname = \"not-this\"\n\n[package]\nname = \"real-name\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(package_name(temp_dir.path()), Some("real-name".to_string()));
+        assert_eq!(crate_name_from_cargo_toml(temp_dir.path()), Some("real_name".to_string()));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::cli::{Language, StructureType};
+use crate::config::cargo_workspace::toml_section;
+use crate::error::TestsmithError;
+use crate::file_ops::FileSystem;
+use clap::ValueEnum;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Name of the optional user-wide defaults file inside the config directory
+pub const DEFAULTS_FILE_NAME: &str = "defaults.toml";
+
+/// User-wide defaults from `defaults.toml` (TOML), applied to every project:
+///
+/// ```toml
+/// [structure]
+/// rust = "test-crate"
+/// java = "gradle"
+/// ```
+///
+/// Keys and values use the same names as `--language` and `--structure`.
+/// There is no TOML crate, so only this subset is read: a `[structure]` table of
+/// `key = "value"` (or `'value'`) pairs without escapes, and `#` comments on their
+/// own line or after a value. Other tables are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Defaults {
+    structures: HashMap<Language, StructureType>,
+}
+
+impl Defaults {
+    /// Parse the contents of a defaults file; `origin` names it in errors
+    pub fn parse(content: &str, origin: &Path) -> Result<Self, TestsmithError> {
+        // The value is matched before any trailing comment, so a `#` inside quotes is kept
+        let entry_regex = Regex::new(r#"^\s*([\w-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')\s*(?:#.*)?$"#).unwrap();
+        let mut structures = HashMap::new();
+
+        for line in toml_section(content, "structure").unwrap_or("").lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let caps = entry_regex.captures(line).ok_or_else(|| TestsmithError::ConfigError {
+                reason: format!("Expected `language = \"structure\"` in {}: {}", origin.display(), line.trim()),
+            })?;
+            let language = Language::from_str(&caps[1], true).map_err(|_| TestsmithError::ConfigError {
+                reason: format!("Unknown language '{}' in {}", &caps[1], origin.display()),
+            })?;
+            let value = caps.get(2).or_else(|| caps.get(3)).map_or("", |m| m.as_str());
+            let structure = StructureType::from_str(value, true).map_err(|_| TestsmithError::ConfigError {
+                reason: format!("Unknown structure '{}' in {}", value, origin.display()),
+            })?;
+            structures.insert(language, structure);
+        }
+
+        Ok(Defaults { structures })
+    }
+
+    /// Load a defaults file, or empty defaults if it doesn't exist
+    pub fn load(fs: &FileSystem, path: &Path) -> Result<Self, TestsmithError> {
+        if !fs.file_exists(path) {
+            return Ok(Defaults::default());
+        }
+
+        Self::parse(&fs.read_file(path)?, path)
+    }
+
+    /// Structure to use for a language when none is given or configured for the project
+    pub fn structure_for(&self, language: Language) -> Option<StructureType> {
+        self.structures.get(&language).copied()
+    }
+}
+
+/// Location of the defaults file: $XDG_CONFIG_HOME, ~/.config or %APPDATA%, under `testsmith/`
+pub fn defaults_file_path() -> Option<PathBuf> {
+    let config_dir = if cfg!(target_os = "windows") {
+        PathBuf::from(std::env::var("APPDATA").ok()?)
+    } else if let Ok(xdg_config) = std::env::var("XDG_CONFIG_HOME") {
+        PathBuf::from(xdg_config)
+    } else {
+        PathBuf::from(std::env::var("HOME").ok()?).join(".config")
+    };
+
+    Some(config_dir.join("testsmith").join(DEFAULTS_FILE_NAME))
+}
+
+/// Load the user's defaults file, or empty defaults when there is none
+pub fn load_defaults(fs: &FileSystem) -> Result<Defaults, TestsmithError> {
+    match defaults_file_path() {
+        Some(path) => Defaults::load(fs, &path),
+        None => Ok(Defaults::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_structure_per_language() {
+        let content = "# testsmith defaults\n[structure]\nrust = \"test-crate\"  # integration tests\njava = \"gradle\"\n\n[other]\npython = \"flat\"\n";
+        let defaults = Defaults::parse(content, Path::new("defaults.toml")).unwrap();
+
+        assert_eq!(defaults.structure_for(Language::Rust), Some(StructureType::TestCrate));
+        assert_eq!(defaults.structure_for(Language::Java), Some(StructureType::Gradle));
+        assert_eq!(defaults.structure_for(Language::Python), None);
+    }
+
+    #[test]
+    fn test_unknown_structure_is_config_error() {
+        let result = Defaults::parse("[structure]\nrust = \"sideways\"\n", Path::new("defaults.toml"));
+        assert!(matches!(result, Err(TestsmithError::ConfigError { .. })));
+
+        let result = Defaults::parse("[structure]\ncobol = \"flat\"\n", Path::new("defaults.toml"));
+        assert!(matches!(result, Err(TestsmithError::ConfigError { .. })));
+    }
+
+    #[test]
+    fn test_hash_inside_quotes_is_not_a_comment() {
+        let content = "[structure]\nrust = \"same#file\" # not a structure\njava = 'gradle' # single quotes\n";
+        let result = Defaults::parse(content, Path::new("defaults.toml"));
+        assert!(matches!(result, Err(TestsmithError::ConfigError { reason }) if reason.contains("'same#file'")));
+
+        let defaults = Defaults::parse("[structure]\njava = 'gradle' # single quotes\n", Path::new("defaults.toml")).unwrap();
+        assert_eq!(defaults.structure_for(Language::Java), Some(StructureType::Gradle));
+    }
+
+    #[test]
+    fn test_missing_defaults_file_is_empty() {
+        let fs = FileSystem::new_memory();
+        let defaults = Defaults::load(&fs, &Path::new("/config/testsmith").join(DEFAULTS_FILE_NAME)).unwrap();
+        assert_eq!(defaults, Defaults::default());
+    }
+
+    #[test]
+    fn test_load_reads_through_file_system() {
+        let fs = FileSystem::new_memory();
+        let path = Path::new("/config/testsmith").join(DEFAULTS_FILE_NAME);
+        fs.write_file_new(&path, "[structure]\npython = \"flat\"\n").unwrap();
+
+        let defaults = Defaults::load(&fs, &path).unwrap();
+        assert_eq!(defaults.structure_for(Language::Python), Some(StructureType::Flat));
+    }
+}
@@ -0,0 +1,282 @@
+use crate::file_ops::FileSystem;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// Indentation style from `indent_style`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    Space,
+    Tab,
+}
+
+/// Line ending from `end_of_line`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndOfLine {
+    Lf,
+    Crlf,
+    Cr,
+}
+
+impl EndOfLine {
+    fn as_str(&self) -> &'static str {
+        match self {
+            EndOfLine::Lf => "\n",
+            EndOfLine::Crlf => "\r\n",
+            EndOfLine::Cr => "\r",
+        }
+    }
+}
+
+/// Formatting settings that apply to a single file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditorConfig {
+    pub indent_style: Option<IndentStyle>,
+    pub indent_size: Option<usize>,
+    pub end_of_line: Option<EndOfLine>,
+}
+
+/// Indentation width the built-in templates are written with
+const TEMPLATE_INDENT_SIZE: usize = 4;
+
+impl EditorConfig {
+    fn set(&mut self, key: &str, value: &str) {
+        match key {
+            "indent_style" => {
+                self.indent_style = match value {
+                    "tab" => Some(IndentStyle::Tab),
+                    "space" => Some(IndentStyle::Space),
+                    _ => self.indent_style,
+                }
+            }
+            "indent_size" => {
+                if let Ok(size) = value.parse() {
+                    self.indent_size = Some(size);
+                }
+            }
+            "end_of_line" => {
+                self.end_of_line = match value {
+                    "lf" => Some(EndOfLine::Lf),
+                    "crlf" => Some(EndOfLine::Crlf),
+                    "cr" => Some(EndOfLine::Cr),
+                    _ => self.end_of_line,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether any setting would change template output
+    pub fn is_empty(&self) -> bool {
+        self.indent_style.is_none() && self.indent_size.is_none() && self.end_of_line.is_none()
+    }
+
+    /// Re-indent and re-terminate generated content (written with 4-space indents and `\n`)
+    pub fn apply(&self, content: &str) -> String {
+        let indent_unit = match self.indent_style {
+            Some(IndentStyle::Tab) => "\t".to_string(),
+            _ => " ".repeat(self.indent_size.unwrap_or(TEMPLATE_INDENT_SIZE)),
+        };
+        let newline = self.end_of_line.unwrap_or(EndOfLine::Lf).as_str();
+
+        let lines: Vec<String> = content
+            .split('\n')
+            .map(|line| {
+                let trimmed = line.trim_start_matches(' ');
+                let leading = line.len() - trimmed.len();
+                let levels = leading / TEMPLATE_INDENT_SIZE;
+                let remainder = leading % TEMPLATE_INDENT_SIZE;
+                format!("{}{}{}", indent_unit.repeat(levels), " ".repeat(remainder), trimmed)
+            })
+            .collect();
+
+        lines.join(newline)
+    }
+}
+
+/// Convert an editorconfig section glob into a regex
+/// Supports `*`, `**`, `?`, `{a,b}`, `[...]` and negated `[!...]`
+fn glob_to_regex(glob: &str) -> Option<Regex> {
+    let mut pattern = String::from("^");
+    let mut chars = glob.chars().peekable();
+    let mut brace_depth = 0;
+    let mut in_class = false;
+
+    while let Some(c) = chars.next() {
+        if in_class {
+            match c {
+                ']' => {
+                    in_class = false;
+                    pattern.push(']');
+                }
+                '-' => pattern.push('-'),
+                _ => pattern.push_str(&regex::escape(&c.to_string())),
+            }
+            continue;
+        }
+
+        match c {
+            '*' => {
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                    pattern.push_str(".*");
+                } else {
+                    pattern.push_str("[^/]*");
+                }
+            }
+            '?' => pattern.push_str("[^/]"),
+            '{' => {
+                brace_depth += 1;
+                pattern.push_str("(?:");
+            }
+            '}' if brace_depth > 0 => {
+                brace_depth -= 1;
+                pattern.push(')');
+            }
+            ',' if brace_depth > 0 => pattern.push('|'),
+            '[' => {
+                in_class = true;
+                pattern.push('[');
+                if chars.peek() == Some(&'!') {
+                    chars.next();
+                    // A negated class never matches the path separator
+                    pattern.push_str("^/");
+                }
+            }
+            _ => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    pattern.push('$');
+    Regex::new(&pattern).ok()
+}
+
+/// Check whether a section glob applies to a file, relative to the .editorconfig directory
+fn section_matches(glob: &str, relative_path: &str) -> bool {
+    // Globs without a slash match the file name in any directory
+    let (glob, target) = if glob.contains('/') {
+        (glob.trim_start_matches('/').to_string(), relative_path.to_string())
+    } else {
+        let file_name = relative_path.rsplit('/').next().unwrap_or(relative_path);
+        (glob.to_string(), file_name.to_string())
+    };
+
+    glob_to_regex(&glob).is_some_and(|regex| regex.is_match(&target))
+}
+
+/// Check whether an .editorconfig file declares `root = true` before its first section
+fn declares_root(content: &str) -> bool {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .take_while(|line| !line.starts_with('['))
+        .filter_map(|line| line.split_once('='))
+        .any(|(key, value)| key.trim().eq_ignore_ascii_case("root") && value.trim().eq_ignore_ascii_case("true"))
+}
+
+/// Apply one .editorconfig file's matching sections to the settings
+fn apply_file(content: &str, relative_path: &str, config: &mut EditorConfig) {
+    let mut section_applies = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            section_applies = section_matches(&line[1..line.len() - 1], relative_path);
+            continue;
+        }
+
+        if section_applies && let Some((key, value)) = line.split_once('=') {
+            config.set(&key.trim().to_lowercase(), &value.trim().to_lowercase());
+        }
+    }
+}
+
+/// Resolve the editorconfig settings for a file, walking up from its directory
+/// Nearer files override farther ones; the search stops at `root = true`
+pub fn find_editorconfig(fs: &FileSystem, file_path: &Path) -> EditorConfig {
+    let mut config_files: Vec<(PathBuf, String)> = Vec::new();
+
+    for dir in file_path.ancestors().skip(1) {
+        let candidate = dir.join(".editorconfig");
+        if let Ok(content) = fs.read_file(&candidate) {
+            let is_root = declares_root(&content);
+            config_files.push((dir.to_path_buf(), content));
+            if is_root {
+                break;
+            }
+        }
+    }
+
+    let mut config = EditorConfig::default();
+    for (dir, content) in config_files.iter().rev() {
+        let relative_path = file_path
+            .strip_prefix(dir)
+            .unwrap_or(file_path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        apply_file(content, &relative_path, &mut config);
+    }
+
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tabs_and_crlf() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(
+            Path::new("/project/.editorconfig"),
+            "root = true\n\n[*]\nindent_style = space\n\n[*.java]\nindent_style = tab\nend_of_line = crlf\n",
+        )
+        .unwrap();
+
+        let config = find_editorconfig(&fs, Path::new("/project/src/test/java/FooTest.java"));
+        assert_eq!(config.indent_style, Some(IndentStyle::Tab));
+        assert_eq!(config.end_of_line, Some(EndOfLine::Crlf));
+
+        let output = config.apply("class FooTest {\n    void test() {\n        // TODO\n    }\n}\n");
+        assert_eq!(output, "class FooTest {\r\n\tvoid test() {\r\n\t\t// TODO\r\n\t}\r\n}\r\n");
+    }
+
+    #[test]
+    fn test_nearer_file_overrides_and_root_stops_search() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(
+            Path::new("/project/.editorconfig"),
+            "root = true\n[*]\nindent_size = 8\nend_of_line = crlf\n",
+        )
+        .unwrap();
+        fs.write_file_new(Path::new("/project/pkg/.editorconfig"), "[*.{js,ts}]\nindent_size = 2\n")
+            .unwrap();
+
+        let config = find_editorconfig(&fs, Path::new("/project/pkg/foo.test.ts"));
+        assert_eq!(config.indent_size, Some(2));
+        assert_eq!(config.end_of_line, Some(EndOfLine::Crlf));
+
+        let config = find_editorconfig(&fs, Path::new("/project/pkg/lib.rs"));
+        assert_eq!(config.indent_size, Some(8));
+    }
+
+    #[test]
+    fn test_no_editorconfig_leaves_content_unchanged() {
+        let config = EditorConfig::default();
+        assert!(config.is_empty());
+        assert_eq!(config.apply("a\n    b\n"), "a\n    b\n");
+    }
+
+    #[test]
+    fn test_negated_character_class() {
+        assert!(section_matches("[!_]*.py", "parser.py"));
+        assert!(!section_matches("[!_]*.py", "__init__.py"));
+        assert!(!section_matches("[!a-c].rs", "b.rs"));
+        assert!(section_matches("[a-c].rs", "b.rs"));
+        assert!(section_matches("src/[!x]/*.rs", "src/y/lib.rs"));
+        assert!(!section_matches("src/[!x]/*.rs", "src/x/lib.rs"));
+    }
+}
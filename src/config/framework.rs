@@ -5,10 +5,23 @@ use crate::error::TestsmithError;
 pub fn is_valid_combination(language: Language, framework: Framework) -> bool {
     match language {
         Language::Java => matches!(framework, Framework::JUnit | Framework::JUnit4 | Framework::TestNG),
-        Language::Rust => matches!(framework, Framework::Native),
-        Language::Python => matches!(framework, Framework::Pytest),
-        Language::JavaScript => matches!(framework, Framework::Jest),
-        Language::TypeScript => matches!(framework, Framework::Jest),
+        Language::Rust => matches!(framework, Framework::Native | Framework::Rstest | Framework::Proptest),
+        Language::Zig => matches!(framework, Framework::Native),
+        Language::Python => matches!(framework, Framework::Pytest | Framework::Unittest),
+        Language::JavaScript => matches!(framework, Framework::Jest | Framework::Jasmine | Framework::Mocha),
+        Language::TypeScript => matches!(framework, Framework::Jest | Framework::Jasmine),
+        Language::Vue | Language::Svelte => matches!(framework, Framework::Vitest),
+        Language::Cpp => matches!(framework, Framework::GoogleTest),
+        Language::Kotlin => matches!(framework, Framework::JUnit),
+        Language::Go => matches!(framework, Framework::GoTest),
+        Language::Elixir => matches!(framework, Framework::ExUnit),
+        Language::Php => matches!(framework, Framework::PHPUnit),
+        Language::Scala => matches!(framework, Framework::ScalaTest | Framework::MUnit),
+        Language::CSharp => matches!(framework, Framework::XUnit),
+        Language::Dart => matches!(framework, Framework::DartTest),
+        Language::Ruby => matches!(framework, Framework::RSpec),
+        Language::Haskell => matches!(framework, Framework::HSpec),
+        Language::Swift => matches!(framework, Framework::XCTest),
     }
 }
 
@@ -31,10 +44,23 @@ pub fn validate_combination(
 pub fn supported_frameworks_for_language(language: Language) -> Vec<Framework> {
     match language {
         Language::Java => vec![Framework::JUnit, Framework::JUnit4, Framework::TestNG],
-        Language::Rust => vec![Framework::Native],
-        Language::Python => vec![Framework::Pytest],
-        Language::JavaScript => vec![Framework::Jest],
-        Language::TypeScript => vec![Framework::Jest],
+        Language::Rust => vec![Framework::Native, Framework::Rstest, Framework::Proptest],
+        Language::Zig => vec![Framework::Native],
+        Language::Python => vec![Framework::Pytest, Framework::Unittest],
+        Language::JavaScript => vec![Framework::Jest, Framework::Jasmine, Framework::Mocha],
+        Language::TypeScript => vec![Framework::Jest, Framework::Jasmine],
+        Language::Vue | Language::Svelte => vec![Framework::Vitest],
+        Language::Cpp => vec![Framework::GoogleTest],
+        Language::Kotlin => vec![Framework::JUnit],
+        Language::Go => vec![Framework::GoTest],
+        Language::Elixir => vec![Framework::ExUnit],
+        Language::Php => vec![Framework::PHPUnit],
+        Language::Scala => vec![Framework::ScalaTest, Framework::MUnit],
+        Language::CSharp => vec![Framework::XUnit],
+        Language::Dart => vec![Framework::DartTest],
+        Language::Ruby => vec![Framework::RSpec],
+        Language::Haskell => vec![Framework::HSpec],
+        Language::Swift => vec![Framework::XCTest],
     }
 }
 
@@ -57,6 +83,25 @@ mod tests {
         assert!(!is_valid_combination(Language::Java, Framework::Pytest));
     }
 
+    #[test]
+    fn test_python_unittest_valid() {
+        assert!(is_valid_combination(Language::Python, Framework::Unittest));
+        assert!(!is_valid_combination(Language::Java, Framework::Unittest));
+    }
+
+    #[test]
+    fn test_js_and_ts_jasmine_valid() {
+        assert!(is_valid_combination(Language::JavaScript, Framework::Jasmine));
+        assert!(is_valid_combination(Language::TypeScript, Framework::Jasmine));
+        assert!(!is_valid_combination(Language::Java, Framework::Jasmine));
+    }
+
+    #[test]
+    fn test_mocha_valid_for_javascript_only() {
+        assert!(is_valid_combination(Language::JavaScript, Framework::Mocha));
+        assert!(!is_valid_combination(Language::TypeScript, Framework::Mocha));
+    }
+
     #[test]
     fn test_rust_native_valid() {
         assert!(is_valid_combination(Language::Rust, Framework::Native));
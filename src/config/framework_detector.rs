@@ -1,23 +1,44 @@
 use crate::cli::{Framework, Language};
+use crate::config::build_tool::{detect_build_tool_file, BuildTool};
+use crate::config::cargo_workspace::toml_section;
+use crate::config::project_root::{config_file_paths, MAX_ROOT_SEARCH_DEPTH};
 use crate::error::TestsmithError;
+use regex::Regex;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-/// Find the project root by searching for config files
-fn find_project_root(start_path: &Path) -> Option<PathBuf> {
+/// Find the nearest directory with a build or project config file of any language
+/// (the build module a source belongs to, e.g. one Gradle subproject)
+pub fn find_module_root(start_path: &Path) -> Option<PathBuf> {
     let mut current = if start_path.is_dir() {
         start_path.to_path_buf()
     } else {
         start_path.parent()?.to_path_buf()
     };
 
-    loop {
+    for _ in 0..MAX_ROOT_SEARCH_DEPTH {
         // Check for common config files
         if current.join("Cargo.toml").exists()
             || current.join("pom.xml").exists()
             || current.join("build.gradle").exists()
             || current.join("build.gradle.kts").exists()
             || current.join("package.json").exists()
+            || current.join("CMakeLists.txt").exists()
+            || current.join("go.mod").exists()
+            || current.join("mix.exs").exists()
+            || current.join("composer.json").exists()
+            || current.join("build.sbt").exists()
+            || current.join("pyproject.toml").exists()
+            || current.join("setup.py").exists()
+            || current.join("build.zig").exists()
+            || current.join("pubspec.yaml").exists()
+            || current.join("Gemfile").exists()
+            || current.join(".rspec").exists()
+            || current.join("stack.yaml").exists()
+            || current.join("Package.swift").exists()
+            || !config_file_paths(&current, "*.cabal").is_empty()
+            || !config_file_paths(&current, "*.csproj").is_empty()
+            || !config_file_paths(&current, "*.sln").is_empty()
         {
             return Some(current);
         }
@@ -38,88 +59,610 @@ fn find_project_root(start_path: &Path) -> Option<PathBuf> {
     None
 }
 
+/// Whether a Cargo.toml declares `name` under `[dev-dependencies]`: `name = "0.18"` or
+/// `name.workspace = true` in the section, or a `[dev-dependencies.name]` table (comments ignored)
+fn declares_rust_dev_dependency(content: &str, name: &str) -> bool {
+    let dev_dependencies: String = toml_section(content, "dev-dependencies")
+        .unwrap_or("")
+        .lines()
+        .map(|line| format!("{}\n", line.split('#').next().unwrap_or("")))
+        .collect();
+    let entry = Regex::new(&format!(r"(?m)^\s*{}\s*[=.]", regex::escape(name))).unwrap();
+    let table = Regex::new(&format!(r"(?m)^\s*\[dev-dependencies\.{}\]", regex::escape(name))).unwrap();
+
+    entry.is_match(&dev_dependencies) || table.is_match(content)
+}
+
 /// Detect test framework from Cargo.toml for Rust projects
+/// Rust's built-in test harness, or rstest/proptest when `[dev-dependencies]` declares them
+/// (rstest wins when both are, since proptest suits only property tests)
 fn detect_rust_framework(cargo_toml: &Path) -> Option<Framework> {
-    if fs::read_to_string(cargo_toml).is_ok() {
-        // Check if any test framework dependencies are listed
-        // Rust's native test framework is built-in, so just return Native
-        // unless we find alternative test frameworks (rarely used)
-        Some(Framework::Native)
+    let content = fs::read_to_string(cargo_toml).ok()?;
+
+    if declares_rust_dev_dependency(&content, "rstest") {
+        Some(Framework::Rstest)
+    } else if declares_rust_dev_dependency(&content, "proptest") {
+        Some(Framework::Proptest)
     } else {
-        None
+        Some(Framework::Native)
     }
 }
 
-/// Detect test framework from pom.xml for Java Maven projects
-fn detect_java_maven_framework(pom_xml: &Path) -> Option<Framework> {
-    if let Ok(content) = fs::read_to_string(pom_xml) {
-        // Look for JUnit 5 (jupiter)
-        if content.contains("junit-jupiter") || content.contains("org.junit.jupiter") {
-            return Some(Framework::JUnit);
-        }
+/// A `groupId`/`artifactId` pair declared as a build dependency
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct JavaDependency {
+    group_id: String,
+    artifact_id: String,
+}
 
-        // Look for JUnit 4
-        if content.contains("junit:junit") || content.contains("junit</artifactId>") {
-            return Some(Framework::JUnit4);
+impl JavaDependency {
+    fn new(group_id: &str, artifact_id: &str) -> Self {
+        JavaDependency {
+            group_id: group_id.to_string(),
+            artifact_id: artifact_id.to_string(),
         }
+    }
+}
 
-        // Look for TestNG
-        if content.contains("testng") || content.contains("org.testng") {
-            return Some(Framework::TestNG);
-        }
+/// Map declared dependencies to a test framework
+/// Priority: JUnit 5 > JUnit 4 > TestNG
+fn framework_from_java_dependencies(dependencies: &[JavaDependency]) -> Option<Framework> {
+    if dependencies
+        .iter()
+        .any(|dep| dep.group_id == "org.junit.jupiter" || dep.artifact_id.starts_with("junit-jupiter"))
+    {
+        return Some(Framework::JUnit);
+    }
+
+    if dependencies
+        .iter()
+        .any(|dep| dep.group_id == "junit" && dep.artifact_id == "junit")
+    {
+        return Some(Framework::JUnit4);
+    }
+
+    if dependencies
+        .iter()
+        .any(|dep| dep.group_id == "org.testng" && dep.artifact_id == "testng")
+    {
+        return Some(Framework::TestNG);
     }
 
     None
 }
 
-/// Detect test framework from build.gradle for Java Gradle projects
-fn detect_java_gradle_framework(build_gradle: &Path) -> Option<Framework> {
-    if let Ok(content) = fs::read_to_string(build_gradle) {
-        // Look for JUnit 5
-        if content.contains("org.junit.jupiter") || content.contains("junit-jupiter") {
-            return Some(Framework::JUnit);
+/// Whether JUnit 4 is also declared, either directly or through the vintage engine
+fn declares_junit4(dependencies: &[JavaDependency]) -> bool {
+    dependencies.iter().any(|dep| {
+        (dep.group_id == "junit" && dep.artifact_id == "junit") || dep.artifact_id == "junit-vintage-engine"
+    })
+}
+
+/// Count Java test files importing JUnit 4 vs JUnit 5 `@Test` under a directory,
+/// descending at most `max_depth` levels
+fn count_junit_imports(dir: &Path, junit4: &mut usize, junit5: &mut usize, max_depth: usize) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if max_depth > 0 {
+                count_junit_imports(&path, junit4, junit5, max_depth - 1);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "java")
+            && let Ok(content) = fs::read_to_string(&path)
+        {
+            if content.contains("import org.junit.Test;") {
+                *junit4 += 1;
+            } else if content.contains("import org.junit.jupiter.api.Test;") {
+                *junit5 += 1;
+            }
         }
+    }
+}
+
+/// Pick JUnit 4 or 5 by which style dominates existing tests, defaulting to JUnit 5
+fn dominant_junit_style(project_root: &Path, max_scan_depth: usize) -> Framework {
+    let (mut junit4, mut junit5) = (0, 0);
+    count_junit_imports(&project_root.join("src/test/java"), &mut junit4, &mut junit5, max_scan_depth);
+
+    if junit4 > junit5 {
+        Framework::JUnit4
+    } else {
+        Framework::JUnit
+    }
+}
 
-        // Look for JUnit 4 (new test suite API: useJUnit('4.x'))
-        if content.contains("useJUnit('4") || content.contains("useJUnit(\"4") {
-            return Some(Framework::JUnit4);
+/// Map dependencies to a framework, breaking JUnit 5 + JUnit 4 ties using existing tests
+fn framework_for_java_project(
+    dependencies: &[JavaDependency],
+    project_root: &Path,
+    max_scan_depth: usize,
+) -> Option<Framework> {
+    match framework_from_java_dependencies(dependencies) {
+        Some(Framework::JUnit) if declares_junit4(dependencies) => {
+            Some(dominant_junit_style(project_root, max_scan_depth))
         }
+        framework => framework,
+    }
+}
+
+/// Collect `<dependency>` coordinates from pom.xml, ignoring XML comments
+fn parse_maven_dependencies(content: &str) -> Vec<JavaDependency> {
+    let comment_regex = Regex::new(r"(?s)<!--.*?-->").unwrap();
+    let dependency_regex = Regex::new(r"(?s)<dependency>(.*?)</dependency>").unwrap();
+    let group_regex = Regex::new(r"<groupId>\s*([^<\s]+)\s*</groupId>").unwrap();
+    let artifact_regex = Regex::new(r"<artifactId>\s*([^<\s]+)\s*</artifactId>").unwrap();
+
+    let content = comment_regex.replace_all(content, "");
+
+    dependency_regex
+        .captures_iter(&content)
+        .filter_map(|caps| {
+            let body = caps.get(1)?.as_str();
+            let group_id = group_regex.captures(body)?.get(1)?.as_str();
+            let artifact_id = artifact_regex.captures(body)?.get(1)?.as_str();
+            Some(JavaDependency::new(group_id, artifact_id))
+        })
+        .collect()
+}
 
-        // Look for JUnit 4 (old dependency style: junit:junit)
-        if content.contains("junit:junit") && !content.contains("org.junit.jupiter") {
-            return Some(Framework::JUnit4);
+/// Strip `//` and `/* */` comments from C-style source (Gradle, Java), line by line
+pub(crate) fn uncommented_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut in_block_comment = false;
+
+    for line in content.lines() {
+        let mut code = String::new();
+        let mut rest = line;
+
+        loop {
+            if in_block_comment {
+                match rest.find("*/") {
+                    Some(end) => {
+                        in_block_comment = false;
+                        rest = &rest[end + 2..];
+                    }
+                    None => break,
+                }
+            } else {
+                let block_start = rest.find("/*");
+                let line_start = rest.find("//");
+                match (block_start, line_start) {
+                    (Some(block), Some(line)) if line < block => {
+                        code.push_str(&rest[..line]);
+                        break;
+                    }
+                    (Some(block), _) => {
+                        code.push_str(&rest[..block]);
+                        in_block_comment = true;
+                        rest = &rest[block + 2..];
+                    }
+                    (None, Some(line)) => {
+                        code.push_str(&rest[..line]);
+                        break;
+                    }
+                    (None, None) => {
+                        code.push_str(rest);
+                        break;
+                    }
+                }
+            }
         }
 
-        // Look for TestNG
-        if content.contains("org.testng") || content.contains("testng") {
-            return Some(Framework::TestNG);
+        if !code.trim().is_empty() {
+            lines.push(code);
         }
     }
 
-    None
+    lines
+}
+
+/// Coordinates written as `"group:artifact[:version]"` strings
+fn coordinate_regex() -> Regex {
+    Regex::new(r#"["']([\w.\-]+):([\w.\-]+)(?::[^"']*)?["']"#).unwrap()
+}
+
+/// Coordinates in map notation: `group: 'org.junit.jupiter', name: 'junit-jupiter'` (Groovy)
+/// or `group = "org.junit.jupiter", name = "junit-jupiter"` (Kotlin DSL and version catalogs)
+fn map_notation_regex() -> Regex {
+    Regex::new(r#"\bgroup\s*[:=]\s*["']([\w.\-]+)["']\s*,\s*name\s*[:=]\s*["']([\w.\-]+)["']"#).unwrap()
+}
+
+/// Find the version catalog (`gradle/libs.versions.toml`) of the build a Gradle module belongs to
+fn find_version_catalog(module_dir: &Path) -> Option<PathBuf> {
+    module_dir
+        .ancestors()
+        .take(MAX_ROOT_SEARCH_DEPTH)
+        .map(|dir| dir.join("gradle/libs.versions.toml"))
+        .find(|catalog| catalog.is_file())
+}
+
+/// Map each `[libraries]` alias of a version catalog to its coordinates, keyed by the
+/// accessor build files use (`junit-jupiter` -> `junit.jupiter`, referenced as `libs.junit.jupiter`)
+fn parse_version_catalog(content: &str) -> Vec<(String, JavaDependency)> {
+    let entry_regex = Regex::new(r"^\s*([\w.\-]+)\s*=\s*(.+)$").unwrap();
+    let coordinate_regex = coordinate_regex();
+    let map_notation_regex = map_notation_regex();
+
+    toml_section(content, "libraries")
+        .unwrap_or("")
+        .lines()
+        .filter_map(|line| {
+            let caps = entry_regex.captures(line.split('#').next().unwrap_or(""))?;
+            let accessor = caps[1].replace(['-', '_'], ".");
+            let value = &caps[2];
+            let coordinates = coordinate_regex
+                .captures(value)
+                .or_else(|| map_notation_regex.captures(value))?;
+            Some((accessor, JavaDependency::new(&coordinates[1], &coordinates[2])))
+        })
+        .collect()
+}
+
+/// Coordinates for a catalog accessor the catalog doesn't resolve, guessed from its name
+/// (`libs.junit.jupiter.api`, `libs.junit`, `libs.testng`)
+fn guess_catalog_dependency(accessor: &str) -> Option<JavaDependency> {
+    let segments: Vec<&str> = accessor.split('.').collect();
+    match segments.as_slice() {
+        ["junit", "jupiter", ..] => Some(JavaDependency::new("org.junit.jupiter", "junit-jupiter")),
+        ["junit", "vintage", ..] => Some(JavaDependency::new("org.junit.vintage", "junit-vintage-engine")),
+        ["junit"] | ["junit4"] => Some(JavaDependency::new("junit", "junit")),
+        ["testng"] => Some(JavaDependency::new("org.testng", "testng")),
+        _ => None,
+    }
+}
+
+/// Collect dependency coordinates from uncommented Gradle lines: `"group:artifact[:version]"` strings,
+/// map notation, and version-catalog accessors (`libs.junit.jupiter`) resolved through `catalog`
+fn parse_gradle_dependencies(lines: &[String], catalog: &[(String, JavaDependency)]) -> Vec<JavaDependency> {
+    let coordinate_regex = coordinate_regex();
+    let map_notation_regex = map_notation_regex();
+    let accessor_regex = Regex::new(r"\blibs\.([\w.]+)").unwrap();
+
+    lines
+        .iter()
+        .flat_map(|line| {
+            let coordinates = coordinate_regex
+                .captures_iter(line)
+                .chain(map_notation_regex.captures_iter(line))
+                .map(|caps| JavaDependency::new(&caps[1], &caps[2]));
+            let accessors = accessor_regex
+                .captures_iter(line)
+                .map(|caps| caps[1].to_string())
+                .filter(|accessor| !accessor.starts_with("versions.") && !accessor.starts_with("plugins."))
+                .filter_map(|accessor| {
+                    catalog
+                        .iter()
+                        .find(|(alias, _)| *alias == accessor)
+                        .map(|(_, dependency)| dependency.clone())
+                        .or_else(|| guess_catalog_dependency(&accessor))
+                });
+            coordinates.chain(accessors).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Detect test framework from pom.xml for Java Maven projects
+fn detect_java_maven_framework(pom_xml: &Path, project_root: &Path, max_scan_depth: usize) -> Option<Framework> {
+    let content = fs::read_to_string(pom_xml).ok()?;
+    framework_for_java_project(&parse_maven_dependencies(&content), project_root, max_scan_depth)
+}
+
+/// Detect test framework from build.gradle for Java Gradle projects
+fn detect_java_gradle_framework(build_gradle: &Path, project_root: &Path, max_scan_depth: usize) -> Option<Framework> {
+    let content = fs::read_to_string(build_gradle).ok()?;
+    let lines = uncommented_lines(&content);
+    let catalog = find_version_catalog(project_root)
+        .and_then(|catalog| fs::read_to_string(catalog).ok())
+        .map(|catalog| parse_version_catalog(&catalog))
+        .unwrap_or_default();
+    let dependencies = parse_gradle_dependencies(&lines, &catalog);
+
+    // Look for JUnit 5 (or JUnit 4 if existing tests favour it)
+    if let Some(Framework::JUnit) = framework_from_java_dependencies(&dependencies) {
+        return framework_for_java_project(&dependencies, project_root, max_scan_depth);
+    }
+
+    // Look for JUnit 4 (new test suite API: useJUnit('4.x'))
+    if lines
+        .iter()
+        .any(|line| line.contains("useJUnit('4") || line.contains("useJUnit(\"4"))
+    {
+        return Some(Framework::JUnit4);
+    }
+
+    // Look for TestNG (test suite API: useTestNG())
+    if lines.iter().any(|line| line.contains("useTestNG")) {
+        return Some(Framework::TestNG);
+    }
+
+    // Fall back to declared dependencies (junit:junit, org.testng:testng)
+    if let Some(framework) = framework_from_java_dependencies(&dependencies) {
+        return Some(framework);
+    }
+
+    // Running on the JUnit Platform with no JUnit 4 or TestNG dependency means JUnit 5,
+    // even when its dependencies come through a plugin or an unresolved catalog bundle
+    lines
+        .iter()
+        .any(|line| line.contains("useJUnitPlatform") || line.contains("useJUnitJupiter"))
+        .then_some(Framework::JUnit)
+}
+
+/// Check whether package.json declares a dependency with exactly this name
+fn has_js_dependency(manifest: &serde_json::Value, name: &str) -> bool {
+    ["dependencies", "devDependencies"].iter().any(|section| {
+        manifest
+            .get(section)
+            .and_then(|deps| deps.as_object())
+            .is_some_and(|deps| deps.contains_key(name))
+    })
+}
+
+/// Extensions a JS tool's config file may use (jest.config.ts, karma.conf.cjs, ...)
+const JS_CONFIG_EXTENSIONS: &[&str] = &["js", "cjs", "mjs", "ts", "cts", "mts", "json"];
+
+/// Whether `dir` holds a `<stem>.<ext>` config file for a JS tool
+fn has_js_config(dir: &Path, stem: &str) -> bool {
+    JS_CONFIG_EXTENSIONS
+        .iter()
+        .any(|ext| dir.join(format!("{}.{}", stem, ext)).is_file())
 }
 
 /// Detect test framework from package.json for JavaScript/TypeScript projects
+/// Runner configs beside it count too: Angular CLI projects run Jasmine specs through Karma
+/// (`karma.conf.js`), unless a Jest or Vitest config says the project has moved on
 fn detect_js_framework(package_json: &Path) -> Option<Framework> {
-    if let Ok(content) = fs::read_to_string(package_json) {
-        // Look for Jest
-        if content.contains("jest") {
-            return Some(Framework::Jest);
-        }
+    let content = fs::read_to_string(package_json).ok()?;
+    let manifest: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let project_dir = package_json.parent().unwrap_or(Path::new(""));
+
+    // Look for Jest
+    if has_js_dependency(&manifest, "jest") || has_js_config(project_dir, "jest.config") {
+        return Some(Framework::Jest);
+    }
+
+    if has_js_config(project_dir, "karma.conf") && !has_js_config(project_dir, "vitest.config") {
+        return Some(Framework::Jasmine);
+    }
+
+    if has_js_dependency(&manifest, "jasmine") || has_js_dependency(&manifest, "jasmine-core") {
+        return Some(Framework::Jasmine);
+    }
+
+    if has_js_dependency(&manifest, "mocha") {
+        return Some(Framework::Mocha);
+    }
+
+    None
+}
+
+/// Assertion library for Mocha tests: "chai" when package.json declares it
+/// (None = Node's built-in `assert`)
+pub fn detect_js_assertion_library(project_root: &Path) -> Option<String> {
+    let content = fs::read_to_string(project_root.join("package.json")).ok()?;
+    let manifest: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+    has_js_dependency(&manifest, "chai").then(|| "chai".to_string())
+}
+
+/// Assertion library for Rust tests: "pretty_assertions" when Cargo.toml declares it under `[dev-dependencies]`
+/// (None = the standard `assert_eq!`)
+pub fn detect_rust_assertion_library(project_root: &Path) -> Option<String> {
+    let content = fs::read_to_string(project_root.join("Cargo.toml")).ok()?;
+
+    declares_rust_dev_dependency(&content, "pretty_assertions").then(|| "pretty_assertions".to_string())
+}
+
+/// Detect test framework from package.json for Vue/Svelte component projects
+fn detect_component_framework(package_json: &Path) -> Option<Framework> {
+    let content = fs::read_to_string(package_json).ok()?;
+    let manifest: serde_json::Value = serde_json::from_str(&content).ok()?;
 
-        // Could add Mocha, Vitest, etc. here if needed
+    if has_js_dependency(&manifest, "vitest") {
+        return Some(Framework::Vitest);
+    }
+
+    None
+}
+
+/// Detect package:test from pubspec.yaml (a `test:` entry under dependencies or dev_dependencies)
+fn detect_dart_framework(pubspec_yaml: &Path) -> Option<Framework> {
+    let content = fs::read_to_string(pubspec_yaml).ok()?;
+    let section_regex = Regex::new(r"^(\w+):").unwrap();
+    let test_regex = Regex::new(r"^\s+test\s*:").unwrap();
+
+    let mut in_dependencies = false;
+    for line in content.lines() {
+        if let Some(caps) = section_regex.captures(line) {
+            in_dependencies = matches!(&caps[1], "dependencies" | "dev_dependencies");
+        } else if in_dependencies && test_regex.is_match(line) {
+            return Some(Framework::DartTest);
+        }
     }
 
     None
 }
 
-/// Detect test framework from project configuration files
+/// Detect PHPUnit from composer.json (require or require-dev)
+fn detect_php_framework(composer_json: &Path) -> Option<Framework> {
+    let content = fs::read_to_string(composer_json).ok()?;
+    let manifest: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+    let has_phpunit = ["require", "require-dev"].iter().any(|section| {
+        manifest
+            .get(section)
+            .and_then(|deps| deps.as_object())
+            .is_some_and(|deps| deps.contains_key("phpunit/phpunit"))
+    });
+
+    has_phpunit.then_some(Framework::PHPUnit)
+}
+
+/// Detect the test framework from build.sbt `libraryDependencies` ("org.scalatest" %% "scalatest" % ...)
+/// Preference: ScalaTest, MUnit, JUnit 5 (jupiter-interface), then JUnit 4
+fn detect_sbt_framework(build_sbt: &Path) -> Option<Framework> {
+    let content = fs::read_to_string(build_sbt).ok()?;
+    let module_regex = Regex::new(r#""([\w.\-]+)"\s*%%?%?\s*"([\w.\-]+)""#).unwrap();
+
+    let modules: Vec<(String, String)> = uncommented_lines(&content)
+        .iter()
+        .flat_map(|line| {
+            module_regex
+                .captures_iter(line)
+                .map(|caps| (caps[1].to_string(), caps[2].to_string()))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let declares = |matches: fn(&str, &str) -> bool| modules.iter().any(|(group, artifact)| matches(group, artifact));
+
+    if declares(|group, artifact| group == "org.scalatest" || artifact.starts_with("scalatest")) {
+        Some(Framework::ScalaTest)
+    } else if declares(|_, artifact| artifact == "munit") {
+        Some(Framework::MUnit)
+    } else if declares(|group, artifact| artifact == "jupiter-interface" || group == "org.junit.jupiter") {
+        Some(Framework::JUnit)
+    } else if declares(|_, artifact| artifact == "junit" || artifact == "junit-interface") {
+        Some(Framework::JUnit4)
+    } else {
+        None
+    }
+}
+
+/// Python config files that declare pytest as a dependency or configure it
+const PYTHON_CONFIG_FILES: &[&str] = &["pyproject.toml", "setup.py", "setup.cfg", "requirements.txt", "requirements-dev.txt", "tox.ini"];
+
+/// Directories never searched for Python tests (virtualenvs, caches)
+const PYTHON_SKIP_DIRS: &[&str] = &["venv", "__pycache__", "site-packages", "node_modules"];
+
+/// Whether any Python file under a directory, at most `max_depth` levels down, imports `unittest`
+fn imports_unittest(dir: &Path, max_depth: usize) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    let import_regex = Regex::new(r"(?m)^\s*(?:import\s+unittest\b|from\s+unittest\b)").unwrap();
+
+    entries.flatten().any(|entry| {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if path.is_dir() {
+            max_depth > 0
+                && !name.starts_with('.')
+                && !PYTHON_SKIP_DIRS.contains(&name.as_ref())
+                && imports_unittest(&path, max_depth - 1)
+        } else {
+            path.extension().is_some_and(|ext| ext == "py")
+                && fs::read_to_string(&path).is_ok_and(|content| import_regex.is_match(&content))
+        }
+    })
+}
+
+/// Detect pytest from project config, else unittest when the project's code imports it
+fn detect_python_framework(project_root: &Path, max_scan_depth: usize) -> Option<Framework> {
+    let configures_pytest = project_root.join("pytest.ini").exists()
+        || project_root.join("conftest.py").exists()
+        || PYTHON_CONFIG_FILES
+            .iter()
+            .filter_map(|file| fs::read_to_string(project_root.join(file)).ok())
+            .any(|content| {
+                content
+                    .lines()
+                    .map(|line| line.split('#').next().unwrap_or(""))
+                    .any(|line| line.contains("pytest"))
+            });
+
+    if configures_pytest {
+        Some(Framework::Pytest)
+    } else {
+        imports_unittest(project_root, max_scan_depth).then_some(Framework::Unittest)
+    }
+}
+
+/// Detect GoogleTest from CMakeLists.txt at the project root or in its test directory
+fn detect_cpp_framework(project_root: &Path) -> Option<Framework> {
+    let gtest_regex = Regex::new(r"(?i)gtest|googletest").unwrap();
+
+    let references_gtest = ["CMakeLists.txt", "test/CMakeLists.txt", "tests/CMakeLists.txt"]
+        .iter()
+        .filter_map(|file| fs::read_to_string(project_root.join(file)).ok())
+        .any(|content| {
+            content
+                .lines()
+                .map(|line| line.split('#').next().unwrap_or(""))
+                .any(|line| gtest_regex.is_match(line))
+        });
+
+    references_gtest.then_some(Framework::GoogleTest)
+}
+
+/// Property-based testing library a `--kind property` stub can be written for
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PropertyLibrary {
+    /// proptest, declared in Cargo.toml
+    Proptest,
+    /// quickcheck, declared in Cargo.toml
+    Quickcheck,
+    /// jqwik, declared in pom.xml or build.gradle(.kts)
+    Jqwik,
+}
+
+/// Detect proptest or quickcheck in Cargo.toml's `[dev-dependencies]`, preferring proptest when both are declared
+fn detect_rust_property_lib(cargo_toml: &Path) -> Option<PropertyLibrary> {
+    let content = fs::read_to_string(cargo_toml).ok()?;
+
+    if declares_rust_dev_dependency(&content, "proptest") {
+        Some(PropertyLibrary::Proptest)
+    } else if declares_rust_dev_dependency(&content, "quickcheck") {
+        Some(PropertyLibrary::Quickcheck)
+    } else {
+        None
+    }
+}
+
+/// Detect jqwik in a Maven or Gradle build file
+fn detect_java_property_lib(build_file: &Path) -> Option<PropertyLibrary> {
+    let content = fs::read_to_string(build_file).ok()?;
+
+    uncommented_lines(&content)
+        .iter()
+        .any(|line| line.contains("net.jqwik"))
+        .then_some(PropertyLibrary::Jqwik)
+}
+
+/// Whether a Gradle module applies an Android plugin (`com.android.application`,
+/// `com.android.library`, ...), so it has an `androidTest` source set for instrumented tests
+pub fn is_android_module(module_root: &Path) -> bool {
+    ["build.gradle", "build.gradle.kts"].iter().any(|file| {
+        fs::read_to_string(module_root.join(file))
+            .is_ok_and(|content| uncommented_lines(&content).iter().any(|line| line.contains("com.android")))
+    })
+}
+
+/// Detect a property-based testing library declared in the project's build file
+/// (these sit alongside the unit test framework, so detection runs after it)
+pub fn detect_property_library(project_root: &Path, language: Language) -> Option<PropertyLibrary> {
+    match language {
+        Language::Rust => detect_rust_property_lib(&project_root.join("Cargo.toml")),
+        Language::Java => ["pom.xml", "build.gradle", "build.gradle.kts"]
+            .iter()
+            .find_map(|file| detect_java_property_lib(&project_root.join(file))),
+        _ => None,
+    }
+}
+
+/// Detect test framework from project configuration files, and existing tests
+/// at most `max_scan_depth` levels deep where the configuration is ambiguous
 pub fn detect_framework(
     source_path: &Path,
     language: Language,
+    max_scan_depth: usize,
 ) -> Result<Option<Framework>, TestsmithError> {
     // Find project root
-    let project_root = match find_project_root(source_path) {
+    let project_root = match find_module_root(source_path) {
         Some(root) => root,
         None => return Ok(None),
     };
@@ -129,46 +672,71 @@ pub fn detect_framework(
             let cargo_toml = project_root.join("Cargo.toml");
             Ok(detect_rust_framework(&cargo_toml))
         }
-        Language::Java => {
-            // Try Maven first
-            let pom_xml = project_root.join("pom.xml");
-            if pom_xml.exists() {
-                if let Some(framework) = detect_java_maven_framework(&pom_xml) {
-                    return Ok(Some(framework));
-                }
+        // Only the build file of the tool that builds the module is read, as for its structure
+        Language::Java => match detect_build_tool_file(&project_root) {
+            Some((BuildTool::Maven, file)) => {
+                Ok(detect_java_maven_framework(&project_root.join(file), &project_root, max_scan_depth))
             }
-
-            // Try Gradle
-            let build_gradle = project_root.join("build.gradle");
-            if build_gradle.exists() {
-                if let Some(framework) = detect_java_gradle_framework(&build_gradle) {
-                    return Ok(Some(framework));
-                }
-            }
-
-            let build_gradle_kts = project_root.join("build.gradle.kts");
-            if build_gradle_kts.exists() {
-                if let Some(framework) = detect_java_gradle_framework(&build_gradle_kts) {
-                    return Ok(Some(framework));
-                }
+            Some((BuildTool::Gradle | BuildTool::GradleKts, file)) => {
+                Ok(detect_java_gradle_framework(&project_root.join(file), &project_root, max_scan_depth))
             }
-
-            Ok(None)
-        }
-        Language::JavaScript | Language::TypeScript => {
+            // sbt builds may also compile Java sources
+            Some((BuildTool::Sbt, file)) => Ok(detect_sbt_framework(&project_root.join(file))
+                .filter(|framework| matches!(framework, Framework::JUnit | Framework::JUnit4))),
+            _ => Ok(None),
+        },
+        Language::JavaScript => {
             let package_json = project_root.join("package.json");
             Ok(detect_js_framework(&package_json))
         }
-        Language::Python => {
-            // Could implement Python framework detection here
+        Language::TypeScript => {
+            // Only the JavaScript Mocha template exists
+            let package_json = project_root.join("package.json");
+            Ok(detect_js_framework(&package_json).filter(|framework| *framework != Framework::Mocha))
+        }
+        Language::Vue | Language::Svelte => {
+            let package_json = project_root.join("package.json");
+            Ok(detect_component_framework(&package_json))
+        }
+        Language::Python => Ok(detect_python_framework(&project_root, max_scan_depth)),
+        Language::Cpp => Ok(detect_cpp_framework(&project_root)),
+        Language::Kotlin => {
+            // Only the JUnit 5 template exists for Kotlin, so use the default
             Ok(None)
         }
+        // Go only has the standard library `testing` package
+        Language::Go => Ok(None),
+        // ExUnit ships with Elixir
+        Language::Elixir => Ok(None),
+        Language::Php => {
+            let composer_json = project_root.join("composer.json");
+            Ok(detect_php_framework(&composer_json))
+        }
+        Language::Scala => {
+            let build_sbt = project_root.join("build.sbt");
+            Ok(detect_sbt_framework(&build_sbt).filter(|framework| matches!(framework, Framework::ScalaTest | Framework::MUnit)))
+        }
+        // Only the xUnit template exists for C#, so use the default
+        Language::CSharp => Ok(None),
+        // Zig's test blocks are built into the language
+        Language::Zig => Ok(None),
+        Language::Dart => {
+            let pubspec_yaml = project_root.join("pubspec.yaml");
+            Ok(detect_dart_framework(&pubspec_yaml))
+        }
+        // Only the RSpec template exists for Ruby, so use the default
+        Language::Ruby => Ok(None),
+        // Only the HSpec template exists for Haskell, so use the default
+        Language::Haskell => Ok(None),
+        // XCTest ships with the Swift toolchain
+        Language::Swift => Ok(None),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::testsmithrc::DEFAULT_MAX_SCAN_DEPTH;
     use std::io::Write;
     use tempfile::TempDir;
 
@@ -184,6 +752,63 @@ mod tests {
         assert_eq!(framework, Framework::Native);
     }
 
+    #[test]
+    fn test_detect_rust_rstest_and_proptest_dev_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        let cargo_toml = temp_dir.path().join("Cargo.toml");
+
+        fs::write(
+            &cargo_toml,
+            "[package]\nname = \"test\"\n\n[dev-dependencies]\nrstest = \"0.18\"\nproptest = \"1.4\"\n",
+        )
+        .unwrap();
+        assert_eq!(detect_rust_framework(&cargo_toml), Some(Framework::Rstest));
+
+        fs::write(&cargo_toml, "[package]\nname = \"test\"\n\n[dev-dependencies.proptest]\nversion = \"1.4\"\n").unwrap();
+        assert_eq!(detect_rust_framework(&cargo_toml), Some(Framework::Proptest));
+
+        // Only dev-dependencies count, and commented-out entries don't
+        fs::write(
+            &cargo_toml,
+            "[package]\nname = \"test\"\n\n[dependencies]\nrstest = \"0.18\"\n\n[dev-dependencies]\n# proptest = \"1.4\"\ntempfile = \"3\"\n",
+        )
+        .unwrap();
+        assert_eq!(detect_rust_framework(&cargo_toml), Some(Framework::Native));
+    }
+
+    #[test]
+    fn test_is_android_module_from_plugin() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("build.gradle.kts"),
+            "plugins {\n    id(\"com.android.application\")\n}\n",
+        )
+        .unwrap();
+        assert!(is_android_module(temp_dir.path()));
+
+        fs::write(temp_dir.path().join("build.gradle.kts"), "plugins {\n    // id(\"com.android.library\")\n    java\n}\n").unwrap();
+        assert!(!is_android_module(temp_dir.path()));
+    }
+
+    #[test]
+    fn test_detect_rust_pretty_assertions() {
+        let temp_dir = TempDir::new().unwrap();
+        let cargo_toml = temp_dir.path().join("Cargo.toml");
+
+        fs::write(&cargo_toml, "[package]\nname = \"test\"\n\n[dev-dependencies]\npretty_assertions = \"1.4\"\n").unwrap();
+        assert_eq!(detect_rust_assertion_library(temp_dir.path()), Some("pretty_assertions".to_string()));
+
+        fs::write(&cargo_toml, "[package]\nname = \"test\"\n\n[dev-dependencies]\n# pretty_assertions = \"1.4\"\n").unwrap();
+        assert_eq!(detect_rust_assertion_library(temp_dir.path()), None);
+
+        // Only test code gets the import, so a regular dependency doesn't count
+        fs::write(&cargo_toml, "[package]\nname = \"test\"\n\n[dependencies]\npretty_assertions = \"1.4\"\n").unwrap();
+        assert_eq!(detect_rust_assertion_library(temp_dir.path()), None);
+
+        fs::write(&cargo_toml, "[dev-dependencies.pretty_assertions]\nversion = \"1.4\"\n").unwrap();
+        assert_eq!(detect_rust_assertion_library(temp_dir.path()), Some("pretty_assertions".to_string()));
+    }
+
     #[test]
     fn test_detect_java_junit5_maven() {
         let temp_dir = TempDir::new().unwrap();
@@ -201,7 +826,7 @@ mod tests {
         .unwrap();
 
         let framework =
-            detect_java_maven_framework(&pom_xml).expect("Should detect JUnit 5");
+            detect_java_maven_framework(&pom_xml, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH).expect("Should detect JUnit 5");
         assert_eq!(framework, Framework::JUnit);
     }
 
@@ -222,7 +847,7 @@ mod tests {
         .unwrap();
 
         let framework =
-            detect_java_maven_framework(&pom_xml).expect("Should detect JUnit 4");
+            detect_java_maven_framework(&pom_xml, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH).expect("Should detect JUnit 4");
         assert_eq!(framework, Framework::JUnit4);
     }
 
@@ -243,7 +868,7 @@ mod tests {
         .unwrap();
 
         let framework =
-            detect_java_maven_framework(&pom_xml).expect("Should detect TestNG");
+            detect_java_maven_framework(&pom_xml, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH).expect("Should detect TestNG");
         assert_eq!(framework, Framework::TestNG);
     }
 
@@ -260,7 +885,7 @@ mod tests {
         )
         .unwrap();
 
-        let framework = detect_java_gradle_framework(&build_gradle).expect("Should detect JUnit");
+        let framework = detect_java_gradle_framework(&build_gradle, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH).expect("Should detect JUnit");
         assert_eq!(framework, Framework::JUnit);
     }
 
@@ -281,7 +906,7 @@ mod tests {
         )
         .unwrap();
 
-        let framework = detect_java_gradle_framework(&build_gradle).expect("Should detect JUnit 4");
+        let framework = detect_java_gradle_framework(&build_gradle, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH).expect("Should detect JUnit 4");
         assert_eq!(framework, Framework::JUnit4);
     }
 
@@ -303,4 +928,536 @@ mod tests {
         let framework = detect_js_framework(&package_json).expect("Should detect Jest");
         assert_eq!(framework, Framework::Jest);
     }
+
+    #[test]
+    fn test_detect_mocha_and_chai() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+
+        fs::write(&package_json, r#"{"devDependencies": {"mocha": "^10.2.0"}}"#).unwrap();
+        assert_eq!(detect_js_framework(&package_json), Some(Framework::Mocha));
+        assert_eq!(detect_js_assertion_library(temp_dir.path()), None);
+
+        fs::write(&package_json, r#"{"devDependencies": {"mocha": "^10.2.0", "chai": "^4.3.0"}}"#).unwrap();
+        assert_eq!(detect_js_assertion_library(temp_dir.path()), Some("chai".to_string()));
+    }
+
+    #[test]
+    fn test_detect_jasmine() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+
+        fs::write(&package_json, r#"{"devDependencies": {"jasmine": "^5.1.0"}}"#).unwrap();
+        assert_eq!(detect_js_framework(&package_json), Some(Framework::Jasmine));
+
+        // Karma setups only pull in jasmine-core
+        fs::write(&package_json, r#"{"devDependencies": {"jasmine-core": "^5.1.0", "karma": "^6.4.0"}}"#).unwrap();
+        assert_eq!(detect_js_framework(&package_json), Some(Framework::Jasmine));
+
+        // Jest wins when both are declared
+        fs::write(&package_json, r#"{"devDependencies": {"jasmine": "^5.1.0", "jest": "^29.0.0"}}"#).unwrap();
+        assert_eq!(detect_js_framework(&package_json), Some(Framework::Jest));
+    }
+
+    #[test]
+    fn test_detect_jasmine_from_karma_conf() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+
+        // Angular CLI: karma.conf.js runs the Jasmine specs
+        fs::write(&package_json, r#"{"devDependencies": {"jasmine-core": "~5.1.0", "karma-jasmine": "~5.1.0"}}"#).unwrap();
+        fs::write(temp_dir.path().join("karma.conf.js"), "module.exports = function (config) {};\n").unwrap();
+        assert_eq!(detect_js_framework(&package_json), Some(Framework::Jasmine));
+
+        // The karma config alone is enough
+        fs::write(&package_json, r#"{"devDependencies": {"karma": "~6.4.0"}}"#).unwrap();
+        assert_eq!(detect_js_framework(&package_json), Some(Framework::Jasmine));
+
+        // An explicit Jest config wins
+        fs::write(temp_dir.path().join("jest.config.ts"), "export default {};\n").unwrap();
+        assert_eq!(detect_js_framework(&package_json), Some(Framework::Jest));
+    }
+
+    #[test]
+    fn test_vitest_config_overrides_karma_conf() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+
+        fs::write(&package_json, r#"{"devDependencies": {"vitest": "^1.6.0"}}"#).unwrap();
+        fs::write(temp_dir.path().join("karma.conf.js"), "module.exports = function (config) {};\n").unwrap();
+        fs::write(temp_dir.path().join("vitest.config.ts"), "export default {};\n").unwrap();
+        assert_eq!(detect_js_framework(&package_json), None);
+    }
+
+    #[test]
+    fn test_detect_dart_test() {
+        let temp_dir = TempDir::new().unwrap();
+        let pubspec_yaml = temp_dir.path().join("pubspec.yaml");
+        fs::write(
+            &pubspec_yaml,
+            "name: my_app\nenvironment:\n  sdk: ^3.3.0\n\ndev_dependencies:\n  lints: ^3.0.0\n  test: ^1.25.0\n",
+        )
+        .unwrap();
+
+        assert_eq!(detect_dart_framework(&pubspec_yaml), Some(Framework::DartTest));
+    }
+
+    #[test]
+    fn test_dart_test_not_detected_outside_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        let pubspec_yaml = temp_dir.path().join("pubspec.yaml");
+        fs::write(
+            &pubspec_yaml,
+            "name: my_app\ndependencies:\n  http: ^1.2.0\nscripts:\n  test: dart test\n",
+        )
+        .unwrap();
+
+        assert_eq!(detect_dart_framework(&pubspec_yaml), None);
+    }
+
+    #[test]
+    fn test_detect_phpunit() {
+        let temp_dir = TempDir::new().unwrap();
+        let composer_json = temp_dir.path().join("composer.json");
+        fs::write(
+            &composer_json,
+            r#"{"require": {"php": "^8.2"}, "require-dev": {"phpunit/phpunit": "^11.0"}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(detect_php_framework(&composer_json), Some(Framework::PHPUnit));
+    }
+
+    #[test]
+    fn test_phpunit_not_detected_without_dependency() {
+        let temp_dir = TempDir::new().unwrap();
+        let composer_json = temp_dir.path().join("composer.json");
+        fs::write(&composer_json, r#"{"require": {"php": "^8.2"}}"#).unwrap();
+
+        assert_eq!(detect_php_framework(&composer_json), None);
+    }
+
+    #[test]
+    fn test_detect_scalatest() {
+        let temp_dir = TempDir::new().unwrap();
+        let build_sbt = temp_dir.path().join("build.sbt");
+        fs::write(
+            &build_sbt,
+            "// \"org.scalatest\" was here\nlibraryDependencies += \"org.scalatest\" %% \"scalatest\" % \"3.2.18\" % Test\n",
+        )
+        .unwrap();
+
+        assert_eq!(detect_sbt_framework(&build_sbt), Some(Framework::ScalaTest));
+    }
+
+    #[test]
+    fn test_detect_munit_from_sbt() {
+        let temp_dir = TempDir::new().unwrap();
+        let build_sbt = temp_dir.path().join("build.sbt");
+        fs::write(
+            &build_sbt,
+            "libraryDependencies ++= Seq(\n  \"org.typelevel\" %% \"cats-core\" % \"2.10.0\",\n  \"org.scalameta\" %% \"munit\" % \"1.0.0\" % Test\n)\n",
+        )
+        .unwrap();
+
+        assert_eq!(detect_sbt_framework(&build_sbt), Some(Framework::MUnit));
+    }
+
+    #[test]
+    fn test_java_in_sbt_project_detects_junit() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("build.sbt"),
+            "libraryDependencies += \"com.github.sbt\" % \"junit-interface\" % \"0.13.3\" % Test\n",
+        )
+        .unwrap();
+        let source = temp_dir.path().join("src/main/java/Foo.java");
+        fs::create_dir_all(source.parent().unwrap()).unwrap();
+        fs::write(&source, "class Foo {}\n").unwrap();
+
+        assert_eq!(detect_framework(&source, Language::Java, DEFAULT_MAX_SCAN_DEPTH).unwrap(), Some(Framework::JUnit4));
+        assert_eq!(detect_framework(&source, Language::Scala, DEFAULT_MAX_SCAN_DEPTH).unwrap(), None);
+    }
+
+    #[test]
+    fn test_detect_unittest_without_pytest() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("pyproject.toml"), "[project]\nname = \"demo\"\n").unwrap();
+        fs::create_dir_all(temp_dir.path().join("tests")).unwrap();
+        fs::write(temp_dir.path().join("tests/test_parser.py"), "import unittest\n\nclass TestParser(unittest.TestCase):\n    pass\n").unwrap();
+
+        assert_eq!(detect_python_framework(temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH), Some(Framework::Unittest));
+    }
+
+    #[test]
+    fn test_pytest_wins_over_unittest_imports() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("requirements-dev.txt"), "pytest>=8\n").unwrap();
+        fs::write(temp_dir.path().join("test_legacy.py"), "from unittest import mock\n").unwrap();
+
+        assert_eq!(detect_python_framework(temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH), Some(Framework::Pytest));
+    }
+
+    #[test]
+    fn test_detect_proptest() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n\n[dev-dependencies]\n# quickcheck = \"1\"\nproptest = \"1.4\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            detect_property_library(temp_dir.path(), Language::Rust),
+            Some(PropertyLibrary::Proptest)
+        );
+    }
+
+    #[test]
+    fn test_detect_quickcheck_and_prefer_proptest() {
+        let temp_dir = TempDir::new().unwrap();
+        let cargo_toml = temp_dir.path().join("Cargo.toml");
+        fs::write(&cargo_toml, "[dev-dependencies]\nquickcheck = \"1\"\n").unwrap();
+        assert_eq!(detect_rust_property_lib(&cargo_toml), Some(PropertyLibrary::Quickcheck));
+
+        fs::write(&cargo_toml, "[dev-dependencies]\nquickcheck = \"1\"\nproptest.workspace = true\n").unwrap();
+        assert_eq!(detect_rust_property_lib(&cargo_toml), Some(PropertyLibrary::Proptest));
+
+        // proptest as a regular dependency or in a comment is no reason to write property tests
+        fs::write(&cargo_toml, "[dependencies]\nproptest = \"1\"\n\n[dev-dependencies]\n# quickcheck = \"1\"\n").unwrap();
+        assert_eq!(detect_rust_property_lib(&cargo_toml), None);
+    }
+
+    #[test]
+    fn test_detect_jqwik_from_pom() {
+        let temp_dir = TempDir::new().unwrap();
+        let pom_xml = temp_dir.path().join("pom.xml");
+        fs::write(
+            &pom_xml,
+            "<project><dependencies>\n<dependency>\n<groupId>net.jqwik</groupId>\n<artifactId>jqwik</artifactId>\n<scope>test</scope>\n</dependency>\n</dependencies></project>\n",
+        )
+        .unwrap();
+
+        assert_eq!(detect_java_property_lib(&pom_xml), Some(PropertyLibrary::Jqwik));
+        assert_eq!(detect_property_library(temp_dir.path(), Language::Java), Some(PropertyLibrary::Jqwik));
+    }
+
+    #[test]
+    fn test_proptest_not_detected_when_commented_out() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Cargo.toml"), "[dev-dependencies]\n# proptest = \"1.4\"\n").unwrap();
+
+        assert_eq!(detect_property_library(temp_dir.path(), Language::Rust), None);
+    }
+
+    #[test]
+    fn test_detect_jqwik_from_gradle() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("build.gradle"),
+            "dependencies {\n    testImplementation 'net.jqwik:jqwik:1.8.4'\n}\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            detect_property_library(temp_dir.path(), Language::Java),
+            Some(PropertyLibrary::Jqwik)
+        );
+        assert_eq!(detect_property_library(temp_dir.path(), Language::Kotlin), None);
+    }
+
+    #[test]
+    fn test_scalatest_in_comment_not_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        let build_sbt = temp_dir.path().join("build.sbt");
+        fs::write(&build_sbt, "// libraryDependencies += \"org.scalatest\" %% \"scalatest\"\n").unwrap();
+
+        assert_eq!(detect_sbt_framework(&build_sbt), None);
+    }
+
+    #[test]
+    fn test_jest_only_in_script_not_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        let mut file = fs::File::create(&package_json).unwrap();
+        writeln!(
+            file,
+            r#"{{
+            "scripts": {{
+                "test": "jest --coverage"
+            }},
+            "devDependencies": {{
+                "my-jest-helper": "^1.0.0",
+                "jest-worker": "^29.0.0"
+            }}
+        }}"#
+        )
+        .unwrap();
+
+        assert_eq!(detect_js_framework(&package_json), None);
+    }
+
+    #[test]
+    fn test_commented_out_junit4_maven_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        let pom_xml = temp_dir.path().join("pom.xml");
+        let mut file = fs::File::create(&pom_xml).unwrap();
+        writeln!(
+            file,
+            r#"<project>
+            <!-- Migrated off JUnit 4:
+            <dependency>
+                <groupId>junit</groupId>
+                <artifactId>junit</artifactId>
+            </dependency>
+            -->
+            <dependency>
+                <groupId>org.testng</groupId>
+                <artifactId>testng</artifactId>
+            </dependency>
+        </project>"#
+        )
+        .unwrap();
+
+        let framework = detect_java_maven_framework(&pom_xml, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH).expect("Should detect TestNG");
+        assert_eq!(framework, Framework::TestNG);
+    }
+
+    #[test]
+    fn test_junit_pioneer_maven_resolves_to_junit5() {
+        let temp_dir = TempDir::new().unwrap();
+        let pom_xml = temp_dir.path().join("pom.xml");
+        let mut file = fs::File::create(&pom_xml).unwrap();
+        writeln!(
+            file,
+            r#"<project>
+            <dependency>
+                <groupId>org.junit-pioneer</groupId>
+                <artifactId>junit-pioneer</artifactId>
+            </dependency>
+            <dependency>
+                <groupId>org.junit.jupiter</groupId>
+                <artifactId>junit-jupiter-api</artifactId>
+            </dependency>
+        </project>"#
+        )
+        .unwrap();
+
+        let framework = detect_java_maven_framework(&pom_xml, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH).expect("Should detect JUnit 5");
+        assert_eq!(framework, Framework::JUnit);
+    }
+
+    #[test]
+    fn test_mixed_junit_dependencies_follow_existing_tests() {
+        let temp_dir = TempDir::new().unwrap();
+        let pom_xml = temp_dir.path().join("pom.xml");
+        fs::write(
+            &pom_xml,
+            r#"<project>
+            <dependency>
+                <groupId>org.junit.jupiter</groupId>
+                <artifactId>junit-jupiter</artifactId>
+            </dependency>
+            <dependency>
+                <groupId>org.junit.vintage</groupId>
+                <artifactId>junit-vintage-engine</artifactId>
+            </dependency>
+        </project>"#,
+        )
+        .unwrap();
+
+        // No existing tests: default to JUnit 5
+        assert_eq!(detect_java_maven_framework(&pom_xml, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH), Some(Framework::JUnit));
+
+        let test_dir = temp_dir.path().join("src/test/java/com/example");
+        fs::create_dir_all(&test_dir).unwrap();
+        fs::write(test_dir.join("FooTest.java"), "import org.junit.Test;\n\npublic class FooTest {}\n").unwrap();
+        fs::write(test_dir.join("BarTest.java"), "import org.junit.Test;\n\npublic class BarTest {}\n").unwrap();
+        fs::write(
+            test_dir.join("BazTest.java"),
+            "import org.junit.jupiter.api.Test;\n\nclass BazTest {}\n",
+        )
+        .unwrap();
+
+        assert_eq!(detect_java_maven_framework(&pom_xml, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH), Some(Framework::JUnit4));
+    }
+
+    #[test]
+    fn test_junit_pioneer_alone_is_not_junit4() {
+        let temp_dir = TempDir::new().unwrap();
+        let pom_xml = temp_dir.path().join("pom.xml");
+        let mut file = fs::File::create(&pom_xml).unwrap();
+        writeln!(
+            file,
+            r#"<project>
+            <dependency>
+                <groupId>org.junit-pioneer</groupId>
+                <artifactId>junit-pioneer</artifactId>
+            </dependency>
+        </project>"#
+        )
+        .unwrap();
+
+        assert_eq!(detect_java_maven_framework(&pom_xml, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH), None);
+    }
+
+    #[test]
+    fn test_commented_out_junit4_gradle_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        let build_gradle = temp_dir.path().join("build.gradle");
+        let mut file = fs::File::create(&build_gradle).unwrap();
+        writeln!(
+            file,
+            r#"dependencies {{
+            // testImplementation 'junit:junit:4.13.2'
+            /* testImplementation 'junit:junit:4.12' */
+            testImplementation 'org.junit-pioneer:junit-pioneer:2.2.0'
+            testImplementation 'org.junit.jupiter:junit-jupiter:5.10.0'
+        }}"#
+        )
+        .unwrap();
+
+        let framework = detect_java_gradle_framework(&build_gradle, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH).expect("Should detect JUnit 5");
+        assert_eq!(framework, Framework::JUnit);
+    }
+
+    #[test]
+    fn test_detect_junit_gradle_map_notation() {
+        let temp_dir = TempDir::new().unwrap();
+        let build_gradle = temp_dir.path().join("build.gradle");
+        fs::write(
+            &build_gradle,
+            "dependencies {\n    testImplementation group: 'org.junit.jupiter', name: 'junit-jupiter', version: '5.10.0'\n}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            detect_java_gradle_framework(&build_gradle, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH),
+            Some(Framework::JUnit)
+        );
+
+        let build_gradle_kts = temp_dir.path().join("build.gradle.kts");
+        fs::write(
+            &build_gradle_kts,
+            "dependencies {\n    testImplementation(group = \"org.testng\", name = \"testng\", version = \"7.8.0\")\n}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            detect_java_gradle_framework(&build_gradle_kts, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH),
+            Some(Framework::TestNG)
+        );
+    }
+
+    #[test]
+    fn test_detect_junit_gradle_version_catalog() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("gradle")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("app")).unwrap();
+        fs::write(
+            temp_dir.path().join("gradle/libs.versions.toml"),
+            "[versions]\ntestng = \"7.8.0\"\n\n[libraries]\nunit-runner = { module = \"org.testng:testng\", version.ref = \"testng\" }\n\
+             legacy = { group = \"junit\", name = \"junit\", version = \"4.13.2\" }\n",
+        )
+        .unwrap();
+
+        // Resolved through the catalog of the root build, whatever the alias is called
+        let build_gradle = temp_dir.path().join("app/build.gradle.kts");
+        fs::write(&build_gradle, "dependencies {\n    testImplementation(libs.unit.runner)\n}\n").unwrap();
+        let module_dir = temp_dir.path().join("app");
+        assert_eq!(
+            detect_java_gradle_framework(&build_gradle, &module_dir, DEFAULT_MAX_SCAN_DEPTH),
+            Some(Framework::TestNG)
+        );
+
+        fs::write(&build_gradle, "dependencies {\n    testImplementation(libs.legacy)\n}\n").unwrap();
+        assert_eq!(
+            detect_java_gradle_framework(&build_gradle, &module_dir, DEFAULT_MAX_SCAN_DEPTH),
+            Some(Framework::JUnit4)
+        );
+
+        // An alias the catalog doesn't list still reads as JUnit 5 by its name
+        fs::write(&build_gradle, "dependencies {\n    testImplementation(libs.junit.jupiter.api)\n}\n").unwrap();
+        assert_eq!(
+            detect_java_gradle_framework(&build_gradle, &module_dir, DEFAULT_MAX_SCAN_DEPTH),
+            Some(Framework::JUnit)
+        );
+    }
+
+    #[test]
+    fn test_detect_junit_gradle_from_use_junit_platform() {
+        let temp_dir = TempDir::new().unwrap();
+        let build_gradle = temp_dir.path().join("build.gradle");
+
+        // junit-pioneer extends JUnit 5 but isn't JUnit 5 on its own
+        fs::write(
+            &build_gradle,
+            "dependencies {\n    testImplementation 'org.junit-pioneer:junit-pioneer:2.2.0'\n}\n",
+        )
+        .unwrap();
+        assert_eq!(detect_java_gradle_framework(&build_gradle, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH), None);
+
+        fs::write(
+            &build_gradle,
+            "dependencies {\n    testImplementation libs.bundles.testing\n}\n\ntest {\n    useJUnitPlatform()\n}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            detect_java_gradle_framework(&build_gradle, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH),
+            Some(Framework::JUnit)
+        );
+
+        // JUnit 4 on the platform runs through the vintage engine, so the dependency wins
+        fs::write(
+            &build_gradle,
+            "dependencies {\n    testImplementation 'junit:junit:4.13.2'\n}\n\ntest {\n    useJUnitPlatform()\n}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            detect_java_gradle_framework(&build_gradle, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH),
+            Some(Framework::JUnit4)
+        );
+
+        // A commented-out call is no signal
+        fs::write(&build_gradle, "test {\n    // useJUnitPlatform()\n}\n").unwrap();
+        assert_eq!(detect_java_gradle_framework(&build_gradle, temp_dir.path(), DEFAULT_MAX_SCAN_DEPTH), None);
+    }
+
+    #[test]
+    fn test_detect_googletest_from_cmake() {
+        let temp_dir = TempDir::new().unwrap();
+        let cmake = temp_dir.path().join("CMakeLists.txt");
+        let mut file = fs::File::create(&cmake).unwrap();
+        writeln!(
+            file,
+            "cmake_minimum_required(VERSION 3.14)\nfind_package(GTest REQUIRED)\ntarget_link_libraries(foo_test GTest::gtest_main)"
+        )
+        .unwrap();
+
+        assert_eq!(detect_cpp_framework(temp_dir.path()), Some(Framework::GoogleTest));
+    }
+
+    #[test]
+    fn test_detect_googletest_from_test_dir_cmake() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("CMakeLists.txt"), "add_subdirectory(test)\n").unwrap();
+        fs::create_dir_all(temp_dir.path().join("test")).unwrap();
+        fs::write(
+            temp_dir.path().join("test/CMakeLists.txt"),
+            "FetchContent_Declare(googletest URL https://github.com/google/googletest/archive/v1.14.0.zip)\n",
+        )
+        .unwrap();
+
+        assert_eq!(detect_cpp_framework(temp_dir.path()), Some(Framework::GoogleTest));
+    }
+
+    #[test]
+    fn test_commented_out_gtest_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("CMakeLists.txt"),
+            "project(foo)\n# find_package(GTest REQUIRED)\n",
+        )
+        .unwrap();
+
+        assert_eq!(detect_cpp_framework(temp_dir.path()), None);
+    }
 }
@@ -17,22 +17,93 @@ pub fn detect_language(path: &Path) -> Result<Language, TestsmithError> {
         "py" => Ok(Language::Python),
         "js" => Ok(Language::JavaScript),
         "ts" => Ok(Language::TypeScript),
+        "vue" => Ok(Language::Vue),
+        "svelte" => Ok(Language::Svelte),
+        "cpp" | "cc" | "cxx" | "hpp" => Ok(Language::Cpp),
+        "kt" => Ok(Language::Kotlin),
+        "go" => Ok(Language::Go),
+        "ex" | "exs" => Ok(Language::Elixir),
+        "php" => Ok(Language::Php),
+        "scala" => Ok(Language::Scala),
+        "cs" => Ok(Language::CSharp),
+        "zig" => Ok(Language::Zig),
+        "dart" => Ok(Language::Dart),
+        "rb" => Ok(Language::Ruby),
+        "hs" => Ok(Language::Haskell),
+        "swift" => Ok(Language::Swift),
         _ => Err(TestsmithError::UnsupportedLanguage {
             language: extension.to_string(),
         }),
     }
 }
 
+/// Reject JavaScript/TypeScript files that have nothing to generate a test for:
+/// `.d.ts` declarations (no implementation) and `.test`/`.spec` files (already tests)
+pub fn check_source_file(path: &Path, language: Language) -> Result<(), TestsmithError> {
+    if !matches!(language, Language::JavaScript | Language::TypeScript) {
+        return Ok(());
+    }
+
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("");
+    if language == Language::TypeScript && stem.ends_with(".d") {
+        return Err(TestsmithError::InvalidSourceFile {
+            reason: "declaration file has no testable code".to_string(),
+        });
+    }
+    if stem.ends_with(".test") || stem.ends_with(".spec") {
+        return Err(TestsmithError::AlreadyTestFile {
+            path: path.to_path_buf(),
+        });
+    }
+
+    Ok(())
+}
+
 /// Get the default framework for a given language
 pub fn default_framework_for_language(language: Language) -> crate::cli::Framework {
     use crate::cli::Framework;
 
     match language {
-        Language::Java => Framework::JUnit,
-        Language::Rust => Framework::Native,
+        Language::Java | Language::Kotlin => Framework::JUnit,
+        Language::Rust | Language::Zig => Framework::Native,
         Language::Python => Framework::Pytest,
         Language::JavaScript => Framework::Jest,
         Language::TypeScript => Framework::Jest,
+        Language::Vue | Language::Svelte => Framework::Vitest,
+        Language::Cpp => Framework::GoogleTest,
+        Language::Go => Framework::GoTest,
+        Language::Elixir => Framework::ExUnit,
+        Language::Php => Framework::PHPUnit,
+        Language::Scala => Framework::ScalaTest,
+        Language::CSharp => Framework::XUnit,
+        Language::Dart => Framework::DartTest,
+        Language::Ruby => Framework::RSpec,
+        Language::Haskell => Framework::HSpec,
+        Language::Swift => Framework::XCTest,
+    }
+}
+
+/// Get the structure a language uses when none is detected
+pub fn default_structure_for_language(language: Language) -> crate::cli::StructureType {
+    use crate::cli::StructureType;
+
+    match language {
+        Language::Java | Language::Kotlin => StructureType::Maven,
+        Language::Rust | Language::Zig => StructureType::SameFile,
+        Language::Python => StructureType::SameFile,
+        Language::JavaScript => StructureType::SameFile,
+        Language::TypeScript => StructureType::SameFile,
+        Language::Vue | Language::Svelte => StructureType::Adjacent,
+        Language::Cpp => StructureType::Flat,
+        Language::Go => StructureType::Adjacent,
+        Language::Elixir => StructureType::Flat,
+        Language::Php => StructureType::Flat,
+        Language::Scala => StructureType::Maven,
+        Language::CSharp => StructureType::Flat,
+        Language::Dart => StructureType::Flat,
+        Language::Ruby => StructureType::Flat,
+        Language::Haskell => StructureType::Flat,
+        Language::Swift => StructureType::Flat,
     }
 }
 
@@ -71,6 +142,20 @@ mod tests {
         assert!(detect_language(&path).is_err());
     }
 
+    #[test]
+    fn test_declaration_and_test_files_rejected() {
+        let error = check_source_file(&PathBuf::from("src/foo.d.ts"), Language::TypeScript).unwrap_err();
+        assert!(matches!(error, TestsmithError::InvalidSourceFile { ref reason } if reason == "declaration file has no testable code"));
+
+        for file in ["src/foo.test.ts", "src/foo.spec.ts"] {
+            let error = check_source_file(&PathBuf::from(file), Language::TypeScript).unwrap_err();
+            assert!(matches!(error, TestsmithError::AlreadyTestFile { .. }));
+        }
+
+        assert!(check_source_file(&PathBuf::from("src/foo.ts"), Language::TypeScript).is_ok());
+        assert!(check_source_file(&PathBuf::from("src/data.d.py"), Language::Python).is_ok());
+    }
+
     #[test]
     fn test_default_framework_java() {
         let framework = default_framework_for_language(Language::Java);
@@ -82,4 +167,93 @@ mod tests {
         let framework = default_framework_for_language(Language::Rust);
         assert_eq!(framework, crate::cli::Framework::Native);
     }
+
+    #[test]
+    fn test_detect_component_languages() {
+        assert_eq!(detect_language(&PathBuf::from("Foo.vue")).unwrap(), Language::Vue);
+        assert_eq!(detect_language(&PathBuf::from("Button.svelte")).unwrap(), Language::Svelte);
+        assert_eq!(default_framework_for_language(Language::Vue), crate::cli::Framework::Vitest);
+    }
+
+    #[test]
+    fn test_detect_kotlin_language() {
+        assert_eq!(detect_language(&PathBuf::from("Foo.kt")).unwrap(), Language::Kotlin);
+        assert_eq!(default_framework_for_language(Language::Kotlin), crate::cli::Framework::JUnit);
+    }
+
+    #[test]
+    fn test_detect_go_language() {
+        assert_eq!(detect_language(&PathBuf::from("handler.go")).unwrap(), Language::Go);
+        assert_eq!(default_framework_for_language(Language::Go), crate::cli::Framework::GoTest);
+    }
+
+    #[test]
+    fn test_detect_elixir_language() {
+        for file in ["lib/my_app/parser.ex", "test/test_helper.exs"] {
+            assert_eq!(detect_language(&PathBuf::from(file)).unwrap(), Language::Elixir);
+        }
+        assert_eq!(default_framework_for_language(Language::Elixir), crate::cli::Framework::ExUnit);
+    }
+
+    #[test]
+    fn test_detect_php_language() {
+        assert_eq!(detect_language(&PathBuf::from("src/Foo.php")).unwrap(), Language::Php);
+        assert_eq!(default_framework_for_language(Language::Php), crate::cli::Framework::PHPUnit);
+    }
+
+    #[test]
+    fn test_detect_csharp_language() {
+        assert_eq!(detect_language(&PathBuf::from("src/Foo.cs")).unwrap(), Language::CSharp);
+        assert_eq!(default_framework_for_language(Language::CSharp), crate::cli::Framework::XUnit);
+        assert_eq!(default_structure_for_language(Language::CSharp), crate::cli::StructureType::Flat);
+    }
+
+    #[test]
+    fn test_detect_zig_language() {
+        assert_eq!(detect_language(&PathBuf::from("src/parser.zig")).unwrap(), Language::Zig);
+        assert_eq!(default_framework_for_language(Language::Zig), crate::cli::Framework::Native);
+        assert_eq!(default_structure_for_language(Language::Zig), crate::cli::StructureType::SameFile);
+    }
+
+    #[test]
+    fn test_detect_dart_language() {
+        assert_eq!(detect_language(&PathBuf::from("lib/foo.dart")).unwrap(), Language::Dart);
+        assert_eq!(default_framework_for_language(Language::Dart), crate::cli::Framework::DartTest);
+    }
+
+    #[test]
+    fn test_detect_ruby_language() {
+        assert_eq!(detect_language(&PathBuf::from("lib/foo.rb")).unwrap(), Language::Ruby);
+        assert_eq!(default_framework_for_language(Language::Ruby), crate::cli::Framework::RSpec);
+        assert_eq!(default_structure_for_language(Language::Ruby), crate::cli::StructureType::Flat);
+    }
+
+    #[test]
+    fn test_detect_haskell_language() {
+        assert_eq!(detect_language(&PathBuf::from("src/Data/Parser.hs")).unwrap(), Language::Haskell);
+        assert_eq!(default_framework_for_language(Language::Haskell), crate::cli::Framework::HSpec);
+        assert_eq!(default_structure_for_language(Language::Haskell), crate::cli::StructureType::Flat);
+    }
+
+    #[test]
+    fn test_detect_swift_language() {
+        assert_eq!(detect_language(&PathBuf::from("Sources/Parser/Lexer.swift")).unwrap(), Language::Swift);
+        assert_eq!(default_framework_for_language(Language::Swift), crate::cli::Framework::XCTest);
+        assert_eq!(default_structure_for_language(Language::Swift), crate::cli::StructureType::Flat);
+    }
+
+    #[test]
+    fn test_detect_scala_language() {
+        assert_eq!(detect_language(&PathBuf::from("Foo.scala")).unwrap(), Language::Scala);
+        assert_eq!(default_framework_for_language(Language::Scala), crate::cli::Framework::ScalaTest);
+    }
+
+    #[test]
+    fn test_detect_cpp_language() {
+        for file in ["foo.cpp", "foo.cc", "foo.cxx", "foo.hpp"] {
+            assert_eq!(detect_language(&PathBuf::from(file)).unwrap(), Language::Cpp);
+        }
+        assert_eq!(default_framework_for_language(Language::Cpp), crate::cli::Framework::GoogleTest);
+        assert_eq!(default_structure_for_language(Language::Cpp), crate::cli::StructureType::Flat);
+    }
 }
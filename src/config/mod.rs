@@ -1,6 +1,14 @@
+pub mod build_tool;
+pub mod cargo_workspace;
+pub mod defaults;
+pub mod editorconfig;
 pub mod framework;
 pub mod framework_detector;
 pub mod language;
+pub mod module_path;
+pub mod naming;
+pub mod package_import;
 pub mod structure;
 pub mod structure_detector;
 pub mod project_root;
+pub mod testsmithrc;
@@ -0,0 +1,134 @@
+use crate::cli::Language;
+use std::path::{Component, Path};
+
+/// How another file refers to `source_path` in an import, per language:
+/// a Rust path (`crate::net::socket`), a Python module (`app.services`),
+/// a Haskell module (`Data.Parser`) or a JavaScript/TypeScript specifier (`../src/utils`)
+///
+/// `root` is the source root for module languages (`src/`, or the directory holding
+/// the top-level Python package), and the importing file's directory for JavaScript/TypeScript
+pub fn module_path(source_path: &Path, root: &Path, language: Language) -> Option<String> {
+    match language {
+        Language::Rust => rust_module_path(source_path, root),
+        Language::Python => python_module_path(source_path, root),
+        Language::Haskell => Some(module_components(source_path, root)?.join(".")),
+        Language::JavaScript | Language::TypeScript => js_specifier(source_path, root),
+        _ => None,
+    }
+}
+
+/// Path to `target` relative to the directory `from`, using `/` separators
+pub fn relative_path(from: &Path, target: &Path) -> Option<String> {
+    let from: Vec<Component> = from.components().collect();
+    let target: Vec<Component> = target.components().collect();
+    let common = from.iter().zip(&target).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    for component in &target[common..] {
+        parts.push(component.as_os_str().to_str()?.to_string());
+    }
+    Some(parts.join("/"))
+}
+
+/// Directories and file stem of a source below its root (src/net/socket.rs -> [net, socket])
+fn module_components(source_path: &Path, root: &Path) -> Option<Vec<String>> {
+    let inside = source_path.strip_prefix(root).ok()?.with_extension("");
+    inside
+        .components()
+        .map(|component| match component {
+            Component::Normal(part) => part.to_str().map(str::to_string),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .filter(|components| !components.is_empty())
+}
+
+/// `lib.rs`/`main.rs` at the root are the crate itself, and `mod.rs` is its directory's module
+fn rust_module_path(source_path: &Path, root: &Path) -> Option<String> {
+    let mut components = module_components(source_path, root)?;
+    match components.as_slice() {
+        [file] if file == "lib" || file == "main" => components.clear(),
+        [.., file] if file == "mod" => {
+            components.pop();
+        }
+        _ => {}
+    }
+
+    Some(std::iter::once("crate".to_string()).chain(components).collect::<Vec<_>>().join("::"))
+}
+
+/// `__init__.py` is its package, so `app/services/__init__.py` is `app.services`
+fn python_module_path(source_path: &Path, root: &Path) -> Option<String> {
+    let mut components = module_components(source_path, root)?;
+    if components.last().is_some_and(|file| file == "__init__") {
+        components.pop();
+    }
+
+    (!components.is_empty()).then(|| components.join("."))
+}
+
+/// Extensionless and relative, as Node resolves them; `index` files are imported by their directory
+fn js_specifier(source_path: &Path, from_dir: &Path) -> Option<String> {
+    let target = if source_path.file_stem().is_some_and(|stem| stem == "index") {
+        source_path.parent()?.to_path_buf()
+    } else {
+        source_path.with_extension("")
+    };
+
+    let relative = relative_path(from_dir, &target)?;
+    Some(if relative.is_empty() {
+        ".".to_string()
+    } else if relative.starts_with("..") {
+        relative
+    } else {
+        format!("./{}", relative)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rust_module_paths() {
+        let root = Path::new("/crate/src");
+        let path = |file: &str| module_path(&root.join(file), root, Language::Rust);
+
+        assert_eq!(path("net/socket.rs"), Some("crate::net::socket".to_string()));
+        assert_eq!(path("net/mod.rs"), Some("crate::net".to_string()));
+        assert_eq!(path("lib.rs"), Some("crate".to_string()));
+        assert_eq!(path("main.rs"), Some("crate".to_string()));
+        assert_eq!(path("bin/main.rs"), Some("crate::bin::main".to_string()));
+        assert_eq!(module_path(Path::new("/elsewhere/foo.rs"), root, Language::Rust), None);
+    }
+
+    #[test]
+    fn test_typescript_specifiers() {
+        let test_dir = Path::new("/app/test");
+        let specifier = |file: &str| module_path(Path::new(file), test_dir, Language::TypeScript);
+
+        assert_eq!(specifier("/app/src/cart.ts"), Some("../src/cart".to_string()));
+        assert_eq!(specifier("/app/src/utils/index.ts"), Some("../src/utils".to_string()));
+        assert_eq!(specifier("/app/test/helpers.ts"), Some("./helpers".to_string()));
+        assert_eq!(specifier("/app/test/index.ts"), Some(".".to_string()));
+    }
+
+    #[test]
+    fn test_python_module_paths() {
+        let root = Path::new("/project/src");
+        let path = |file: &str| module_path(&root.join(file), root, Language::Python);
+
+        assert_eq!(path("app/services/user.py"), Some("app.services.user".to_string()));
+        assert_eq!(path("app/services/__init__.py"), Some("app.services".to_string()));
+        assert_eq!(path("__init__.py"), None);
+    }
+
+    #[test]
+    fn test_haskell_module_path() {
+        let root = Path::new("/pkg/src");
+        assert_eq!(
+            module_path(&root.join("Data/Parser.hs"), root, Language::Haskell),
+            Some("Data.Parser".to_string())
+        );
+    }
+}
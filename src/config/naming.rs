@@ -0,0 +1,114 @@
+use serde::Deserialize;
+
+/// How test file/class names are derived from source names
+///
+/// A test name is `prefix + separator + base + separator + suffix`, where the
+/// separator is only emitted next to a non-empty prefix or suffix:
+/// - `Foo` with suffix `Test` -> `FooTest`
+/// - `Foo` with prefix `Test` -> `TestFoo`
+/// - `foo` with suffix `test` and separator `.` -> `foo.test`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct NamingConvention {
+    pub suffix: String,
+    pub prefix: String,
+    pub separator: String,
+}
+
+impl Default for NamingConvention {
+    fn default() -> Self {
+        NamingConvention {
+            suffix: "Test".to_string(),
+            prefix: String::new(),
+            separator: String::new(),
+        }
+    }
+}
+
+impl NamingConvention {
+    /// Build a convention from CLI overrides, or None if neither was given
+    /// A prefix without a suffix yields `TestFoo`, not `TestFooTest`
+    pub fn from_overrides(prefix: Option<String>, suffix: Option<String>) -> Option<Self> {
+        if prefix.is_none() && suffix.is_none() {
+            return None;
+        }
+
+        Some(NamingConvention {
+            suffix: suffix.unwrap_or_default(),
+            prefix: prefix.unwrap_or_default(),
+            separator: String::new(),
+        })
+    }
+
+    /// Apply the convention to a base name (without extension)
+    pub fn apply(&self, base_name: &str) -> String {
+        let mut name = String::new();
+
+        if !self.prefix.is_empty() {
+            name.push_str(&self.prefix);
+            name.push_str(&self.separator);
+        }
+
+        name.push_str(base_name);
+
+        if !self.suffix.is_empty() {
+            name.push_str(&self.separator);
+            name.push_str(&self.suffix);
+        }
+
+        name
+    }
+
+    /// Check whether a base name (without extension) follows this convention
+    pub fn matches(&self, base_name: &str) -> bool {
+        let prefix = if self.prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{}{}", self.prefix, self.separator)
+        };
+        let suffix = if self.suffix.is_empty() {
+            String::new()
+        } else {
+            format!("{}{}", self.separator, self.suffix)
+        };
+
+        (!prefix.is_empty() || !suffix.is_empty())
+            && base_name.len() > prefix.len() + suffix.len()
+            && base_name.starts_with(&prefix)
+            && base_name.ends_with(&suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_test_suffix() {
+        assert_eq!(NamingConvention::default().apply("Foo"), "FooTest");
+    }
+
+    #[test]
+    fn test_prefix_only() {
+        let naming = NamingConvention::from_overrides(Some("Test".to_string()), None).unwrap();
+        assert_eq!(naming.apply("Foo"), "TestFoo");
+        assert!(naming.matches("TestFoo"));
+        assert!(!naming.matches("Foo"));
+    }
+
+    #[test]
+    fn test_suffix_with_separator() {
+        let naming = NamingConvention {
+            suffix: "spec".to_string(),
+            prefix: String::new(),
+            separator: ".".to_string(),
+        };
+        assert_eq!(naming.apply("foo"), "foo.spec");
+        assert!(naming.matches("foo.spec"));
+    }
+
+    #[test]
+    fn test_no_overrides() {
+        assert!(NamingConvention::from_overrides(None, None).is_none());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::cli::Language;
+use crate::config::module_path::{self, relative_path};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// Read the package name declared by the project's manifest
+/// (`name:` in pubspec.yaml for Dart, `"name"` in package.json for JavaScript/TypeScript)
+pub fn package_name(project_root: &Path, language: Language) -> Option<String> {
+    match language {
+        Language::Dart => {
+            let content = fs::read_to_string(project_root.join("pubspec.yaml")).ok()?;
+            let name_regex = Regex::new(r#"(?m)^name:\s*['"]?([A-Za-z_][\w-]*)['"]?\s*$"#).unwrap();
+            Some(name_regex.captures(&content)?.get(1)?.as_str().to_string())
+        }
+        Language::JavaScript | Language::TypeScript => {
+            let content = fs::read_to_string(project_root.join("package.json")).ok()?;
+            let manifest: serde_json::Value = serde_json::from_str(&content).ok()?;
+            manifest
+                .get("name")?
+                .as_str()
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+        }
+        _ => None,
+    }
+}
+
+/// Directory under the package root whose files the package exposes by name
+/// (`lib/` for Dart, where `lib/src/foo.dart` is `package:<pkg>/src/foo.dart`)
+fn package_source_dir(language: Language) -> Option<&'static str> {
+    match language {
+        Language::Dart => Some("lib"),
+        _ => None,
+    }
+}
+
+/// Import specifier for the source file as seen from its test: package-style
+/// (`package:<pkg>/foo.dart`) when the manifest names the package and the source is
+/// inside the exposed directory, otherwise a path relative to the test file
+pub fn source_import(
+    project_root: Option<&Path>,
+    source_path: &Path,
+    test_path: &Path,
+    language: Language,
+) -> Option<String> {
+    if let Some(root) = project_root
+        && let Some(dir) = package_source_dir(language)
+        && let Some(package) = package_name(root, language)
+    {
+        let source = source_path.canonicalize().unwrap_or_else(|_| source_path.to_path_buf());
+        if let Ok(inside) = source.strip_prefix(root.join(dir))
+            && let Some(inside) = relative_path(Path::new(""), inside)
+        {
+            return Some(format!("package:{}/{}", package, inside));
+        }
+    }
+
+    match language {
+        Language::JavaScript | Language::TypeScript => {
+            module_path::module_path(source_path, test_path.parent()?, language)
+        }
+        _ => relative_path(test_path.parent()?, source_path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_package_name_from_pubspec() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("pubspec.yaml"),
+            "name: shopping_cart\ndescription: A cart\n\ndev_dependencies:\n  test: ^1.24.0\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            package_name(temp_dir.path(), Language::Dart),
+            Some("shopping_cart".to_string())
+        );
+    }
+
+    #[test]
+    fn test_package_name_from_package_json() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("package.json"), r#"{"name": "@acme/cart"}"#).unwrap();
+
+        assert_eq!(
+            package_name(temp_dir.path(), Language::TypeScript),
+            Some("@acme/cart".to_string())
+        );
+        assert_eq!(package_name(temp_dir.path(), Language::Dart), None);
+    }
+
+    #[test]
+    fn test_dart_source_under_lib_gets_package_import() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::write(root.join("pubspec.yaml"), "name: shopping_cart\n").unwrap();
+        fs::create_dir_all(root.join("lib/src")).unwrap();
+        fs::write(root.join("lib/src/cart.dart"), "class Cart {}\n").unwrap();
+
+        let import = source_import(
+            Some(&root),
+            &root.join("lib/src/cart.dart"),
+            &root.join("test/src/cart_test.dart"),
+            Language::Dart,
+        );
+        assert_eq!(import, Some("package:shopping_cart/src/cart.dart".to_string()));
+    }
+
+    #[test]
+    fn test_dart_without_pubspec_name_falls_back_to_relative_import() {
+        let root = Path::new("/work/app");
+        let import = source_import(
+            Some(root),
+            &root.join("lib/src/cart.dart"),
+            &root.join("test/src/cart_test.dart"),
+            Language::Dart,
+        );
+        assert_eq!(import, Some("../../lib/src/cart.dart".to_string()));
+    }
+
+    #[test]
+    fn test_js_relative_import_drops_extension() {
+        let root = Path::new("/work/app");
+        let nested = source_import(None, &root.join("src/cart.js"), &root.join("test/cart.test.js"), Language::JavaScript);
+        assert_eq!(nested, Some("../src/cart".to_string()));
+
+        let adjacent = source_import(None, &root.join("src/cart.ts"), &root.join("src/cart.test.ts"), Language::TypeScript);
+        assert_eq!(adjacent, Some("./cart".to_string()));
+    }
+}
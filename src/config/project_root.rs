@@ -1,13 +1,53 @@
 use crate::cli::Language;
+use crate::config::testsmithrc::RC_FILE_NAME;
+use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Most parent directories a root search visits before giving up, so pathological
+/// (e.g. cyclic symlink) paths always terminate
+pub(crate) const MAX_ROOT_SEARCH_DEPTH: usize = 100;
+
 /// Config files that identify a project root for each language
 pub fn config_files_for_language(language: Language) -> Vec<&'static str> {
     match language {
-        Language::Java => vec!["pom.xml", "build.gradle", "build.gradle.kts", "build.sbt"],
+        Language::Java | Language::Kotlin => {
+            vec!["pom.xml", "build.gradle", "build.gradle.kts", "build.sbt"]
+        }
         Language::Rust => vec!["Cargo.toml"],
-        Language::JavaScript | Language::TypeScript => vec!["package.json"],
+        Language::JavaScript | Language::TypeScript | Language::Vue | Language::Svelte => {
+            vec!["package.json", "tsconfig.json", "jsconfig.json"]
+        }
         Language::Python => vec!["pyproject.toml", "setup.py", "requirements.txt"],
+        Language::Cpp => vec!["CMakeLists.txt"],
+        Language::Go => vec!["go.mod"],
+        Language::Elixir => vec!["mix.exs"],
+        Language::Php => vec!["composer.json"],
+        Language::Scala => vec!["build.sbt", "pom.xml", "build.gradle", "build.gradle.kts"],
+        Language::CSharp => vec!["*.csproj", "*.sln"],
+        Language::Zig => vec!["build.zig"],
+        Language::Dart => vec!["pubspec.yaml"],
+        Language::Ruby => vec!["Gemfile", ".rspec"],
+        Language::Haskell => vec!["stack.yaml", "*.cabal"],
+        Language::Swift => vec!["Package.swift"],
+    }
+}
+
+/// Files in `dir` matching a config file name; `*.ext` patterns (e.g. `*.csproj`) match by extension
+pub fn config_file_paths(dir: &Path, config_file: &str) -> Vec<PathBuf> {
+    match config_file.strip_prefix("*.") {
+        Some(extension) => fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == extension))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        None => {
+            let path = dir.join(config_file);
+            if path.exists() { vec![path] } else { Vec::new() }
+        }
     }
 }
 
@@ -19,9 +59,13 @@ pub fn config_files_for_language(language: Language) -> Vec<&'static str> {
 /// - Then: `src/main/`
 /// - Then: `src/`
 /// - Then: `/` (project root)
-/// Returns the first match (closest to the source file)
 ///
-/// Handles both absolute and relative paths by canonicalizing them first.
+/// Returns the first match (closest to the source file). A `.testsmithrc` anywhere above
+/// the file is checked first and marks the root for every language, so a polyglot
+/// monorepo can pin its root above nested `package.json`/`Cargo.toml` files.
+///
+/// Handles both absolute and relative paths by canonicalizing them first. The walk gives up
+/// after [`MAX_ROOT_SEARCH_DEPTH`] directories and returns `None`.
 pub fn find_project_root(start_path: &Path, language: Language) -> Option<PathBuf> {
     let config_files = config_files_for_language(language);
 
@@ -44,10 +88,18 @@ pub fn find_project_root(start_path: &Path, language: Language) -> Option<PathBu
         canonical_path.parent()?.to_path_buf()
     };
 
-    loop {
+    if let Some(marked) = current
+        .ancestors()
+        .take(MAX_ROOT_SEARCH_DEPTH)
+        .find(|dir| dir.join(RC_FILE_NAME).is_file())
+    {
+        return Some(marked.to_path_buf());
+    }
+
+    for _ in 0..MAX_ROOT_SEARCH_DEPTH {
         // Check if any config file for this language exists in current directory
         for config_file in &config_files {
-            if current.join(config_file).exists() {
+            if !config_file_paths(&current, config_file).is_empty() {
                 return Some(current);
             }
         }
@@ -71,7 +123,6 @@ pub fn find_project_root(start_path: &Path, language: Language) -> Option<PathBu
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
     use tempfile::TempDir;
 
     #[test]
@@ -153,6 +204,40 @@ mod tests {
         assert_eq!(root.canonicalize().unwrap(), temp_dir.path().join("subproject").canonicalize().unwrap());
     }
 
+    #[test]
+    fn test_testsmithrc_marker_beats_nearer_build_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let crate_src = temp_dir.path().join("tools/parser/src");
+        fs::create_dir_all(&crate_src).unwrap();
+
+        // The marker sits above both the crate's Cargo.toml and an unrelated package.json
+        fs::write(temp_dir.path().join(".testsmithrc"), "{}").unwrap();
+        fs::File::create(temp_dir.path().join("tools/package.json")).unwrap();
+        fs::File::create(temp_dir.path().join("tools/parser/Cargo.toml")).unwrap();
+
+        let root = find_project_root(&crate_src.join("lib.rs"), Language::Rust).unwrap();
+        assert_eq!(root.canonicalize().unwrap(), temp_dir.path().canonicalize().unwrap());
+        let root = find_project_root(&crate_src, Language::JavaScript).unwrap();
+        assert_eq!(root.canonicalize().unwrap(), temp_dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_root_search_gives_up_past_depth_cap() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::File::create(temp_dir.path().join("Cargo.toml")).unwrap();
+
+        // A synthetic path nested deeper than the cap never reaches the Cargo.toml
+        let mut deep = temp_dir.path().to_path_buf();
+        for _ in 0..MAX_ROOT_SEARCH_DEPTH + 10 {
+            deep.push("d");
+        }
+        assert!(find_project_root(&deep.join("lib.rs"), Language::Rust).is_none());
+
+        // Within the cap the root is still found
+        let shallow = temp_dir.path().join("d/d/d/lib.rs");
+        assert!(find_project_root(&shallow, Language::Rust).is_some());
+    }
+
     #[test]
     fn test_config_files_for_java() {
         let files = config_files_for_language(Language::Java);
@@ -168,4 +253,142 @@ mod tests {
         assert_eq!(files.len(), 1);
         assert!(files.contains(&"Cargo.toml"));
     }
+
+    #[test]
+    fn test_find_go_project_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let pkg_dir = temp_dir.path().join("internal/server");
+        fs::create_dir_all(&pkg_dir).unwrap();
+
+        fs::File::create(temp_dir.path().join("go.mod")).unwrap();
+
+        let root = find_project_root(&pkg_dir, Language::Go).unwrap();
+        assert_eq!(root.canonicalize().unwrap(), temp_dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_find_elixir_project_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let lib_dir = temp_dir.path().join("lib/my_app");
+        fs::create_dir_all(&lib_dir).unwrap();
+
+        fs::File::create(temp_dir.path().join("mix.exs")).unwrap();
+
+        let root = find_project_root(&lib_dir, Language::Elixir).unwrap();
+        assert_eq!(root.canonicalize().unwrap(), temp_dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_find_php_project_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src/Http");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::File::create(temp_dir.path().join("composer.json")).unwrap();
+
+        let root = find_project_root(&src_dir, Language::Php).unwrap();
+        assert_eq!(root.canonicalize().unwrap(), temp_dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_find_csharp_project_root_from_csproj() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src/Services");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::File::create(temp_dir.path().join("MyApp.csproj")).unwrap();
+
+        let root = find_project_root(&src_dir, Language::CSharp).unwrap();
+        assert_eq!(root.canonicalize().unwrap(), temp_dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_find_csharp_project_root_from_sln() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::File::create(temp_dir.path().join("MyApp.sln")).unwrap();
+        // A file merely named like the pattern doesn't count
+        fs::File::create(src_dir.join("csproj")).unwrap();
+
+        let root = find_project_root(&src_dir, Language::CSharp).unwrap();
+        assert_eq!(root.canonicalize().unwrap(), temp_dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_find_dart_project_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let lib_dir = temp_dir.path().join("lib/src");
+        fs::create_dir_all(&lib_dir).unwrap();
+
+        fs::File::create(temp_dir.path().join("pubspec.yaml")).unwrap();
+
+        let root = find_project_root(&lib_dir, Language::Dart).unwrap();
+        assert_eq!(root.canonicalize().unwrap(), temp_dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_find_ruby_project_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let lib_dir = temp_dir.path().join("lib/my_gem");
+        fs::create_dir_all(&lib_dir).unwrap();
+
+        fs::File::create(temp_dir.path().join(".rspec")).unwrap();
+
+        let root = find_project_root(&lib_dir, Language::Ruby).unwrap();
+        assert_eq!(root.canonicalize().unwrap(), temp_dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_find_haskell_project_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src/Data");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::File::create(temp_dir.path().join("parser.cabal")).unwrap();
+
+        let root = find_project_root(&src_dir, Language::Haskell).unwrap();
+        assert_eq!(root.canonicalize().unwrap(), temp_dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_find_swift_project_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("Sources/Parser");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::File::create(temp_dir.path().join("Package.swift")).unwrap();
+
+        let root = find_project_root(&src_dir, Language::Swift).unwrap();
+        assert_eq!(root.canonicalize().unwrap(), temp_dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_find_cpp_project_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::File::create(temp_dir.path().join("CMakeLists.txt")).unwrap();
+
+        let root = find_project_root(&src_dir, Language::Cpp).unwrap();
+        assert_eq!(root.canonicalize().unwrap(), temp_dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_nested_tsconfig_wins_over_monorepo_package_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_src = temp_dir.path().join("packages/web/src");
+        fs::create_dir_all(&package_src).unwrap();
+
+        // package.json only at the monorepo root
+        fs::File::create(temp_dir.path().join("package.json")).unwrap();
+
+        // tsconfig.json per package
+        fs::File::create(temp_dir.path().join("packages/web/tsconfig.json")).unwrap();
+
+        let root = find_project_root(&package_src, Language::TypeScript).unwrap();
+        assert_eq!(root.canonicalize().unwrap(), temp_dir.path().join("packages/web").canonicalize().unwrap());
+    }
 }
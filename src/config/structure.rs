@@ -24,6 +24,18 @@ pub fn get_structure_info(structure: StructureType) -> StructureInfo {
             name: "Flat",
             description: "Flat structure with src/ and tests/ directories",
         },
+        StructureType::TestCrate => StructureInfo {
+            name: "Test Crate",
+            description: "Rust workspace with integration tests in a dedicated test crate",
+        },
+        StructureType::Adjacent => StructureInfo {
+            name: "Adjacent",
+            description: "Test file next to the source (e.g., Foo.vue -> Foo.test.ts)",
+        },
+        StructureType::TestsDir => StructureInfo {
+            name: "Tests Dir",
+            description: "Test file in a __tests__/ directory beside the source (e.g., cart.js -> __tests__/cart.test.js)",
+        },
     }
 }
 
@@ -1,39 +1,175 @@
 use crate::cli::{Language, StructureType};
+use crate::config::build_tool::detect_build_tool;
+use crate::config::naming::NamingConvention;
 use crate::error::TestsmithError;
-use std::path::Path;
+use crate::resolver::adjacent::JS_TESTS_DIR;
+use regex::Regex;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A structure detected for one source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetectedStructure {
+    pub structure: StructureType,
+    /// Whether the source's existing test decided it, rather than the project layout
+    pub from_existing_test: bool,
+}
 
-/// Auto-detect the structure type for a given language in a project root
+/// Auto-detect the structure for a source file, preferring where its existing test lives
+/// over the directory-based heuristics of `detect_project_structure`
 pub fn detect_structure(
     project_root: &Path,
+    source_path: &Path,
     language: Language,
+    max_scan_depth: usize,
+) -> Result<DetectedStructure, TestsmithError> {
+    if let Some(structure) = detect_structure_from_existing_test(project_root, source_path, max_scan_depth) {
+        return Ok(DetectedStructure {
+            structure,
+            from_existing_test: true,
+        });
+    }
+
+    Ok(DetectedStructure {
+        structure: detect_project_structure(project_root, language, max_scan_depth)?,
+        from_existing_test: false,
+    })
+}
+
+/// Auto-detect the project-wide structure type for a given language from its directory layout
+/// (looking at most `max_scan_depth` levels deep where it scans for test files)
+pub fn detect_project_structure(
+    project_root: &Path,
+    language: Language,
+    max_scan_depth: usize,
 ) -> Result<StructureType, TestsmithError> {
     match language {
-        Language::Java => detect_java_structure(project_root),
+        Language::Java | Language::Kotlin | Language::Scala => detect_java_structure(project_root),
         Language::Rust => detect_rust_structure(project_root),
-        Language::JavaScript | Language::TypeScript => detect_js_structure(project_root),
+        Language::JavaScript | Language::TypeScript => detect_js_structure(project_root, max_scan_depth),
         Language::Python => detect_python_structure(project_root),
+        // Component tests live next to the component
+        Language::Vue | Language::Svelte => Ok(StructureType::Adjacent),
+        // src/ and test/ (or tests/) side by side
+        Language::Cpp => Ok(StructureType::Flat),
+        // Go tests must sit in the package directory
+        Language::Go => Ok(StructureType::Adjacent),
+        // lib/ and test/ side by side, as laid out by mix
+        Language::Elixir => Ok(StructureType::Flat),
+        // Composer projects keep src/ and tests/ side by side
+        Language::Php => Ok(StructureType::Flat),
+        // .NET solutions keep src/ and tests/ side by side
+        Language::CSharp => Ok(StructureType::Flat),
+        // Zig tests are `test` blocks inside the source file
+        Language::Zig => Ok(StructureType::SameFile),
+        // pub packages keep lib/ and test/ side by side
+        Language::Dart => Ok(StructureType::Flat),
+        // Gems keep lib/ and spec/ side by side
+        Language::Ruby => Ok(StructureType::Flat),
+        // Cabal and Stack packages keep src/ and test/ side by side
+        Language::Haskell => Ok(StructureType::Flat),
+        // SwiftPM packages keep Sources/ and Tests/ side by side
+        Language::Swift => Ok(StructureType::Flat),
     }
 }
 
-/// Detect Java project structure
-/// Priority: Maven > Gradle > Flat
-fn detect_java_structure(project_root: &Path) -> Result<StructureType, TestsmithError> {
-    // Check for Maven structure: src/main/java and src/test/java
-    if project_root.join("src/main/java").exists() && project_root.join("src/test/java").exists()
+/// Whether a file stem names a test for `base` (FooTest, FooTests, TestFoo, foo.test, foo.spec, foo_test, test_foo)
+fn is_test_stem_for(stem: &str, base: &str) -> bool {
+    [
+        format!("{}Test", base),
+        format!("{}Tests", base),
+        format!("Test{}", base),
+        format!("{}.test", base),
+        format!("{}.spec", base),
+        format!("{}_test", base),
+        format!("test_{}", base),
+    ]
+    .contains(&stem.to_string())
+}
+
+/// Whether a directory directly contains a test file for `base`
+fn dir_has_test_for(dir: &Path, base: &str) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+
+    entries.flatten().any(|entry| {
+        let path = entry.path();
+        path.is_file()
+            && path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| is_test_stem_for(stem, base))
+    })
+}
+
+/// Whether a directory tree contains a test file for `base`, descending at most `max_depth` levels
+fn tree_has_test_for(dir: &Path, base: &str, max_depth: usize) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+
+    dir_has_test_for(dir, base)
+        || max_depth > 0
+            && entries
+                .flatten()
+                .map(|entry| entry.path())
+                .any(|path| path.is_dir() && tree_has_test_for(&path, base, max_depth - 1))
+}
+
+/// The source's directory with its last `src/main` swapped for `src/test`
+fn mirrored_src_test_dir(source_dir: &Path) -> Option<PathBuf> {
+    let components: Vec<Component> = source_dir.components().collect();
+    let main_idx = (1..components.len())
+        .rev()
+        .find(|&i| components[i - 1].as_os_str() == "src" && components[i].as_os_str() == "main")?;
+
+    let mut test_dir: PathBuf = components[..main_idx].iter().collect();
+    test_dir.push("test");
+    test_dir.extend(&components[main_idx + 1..]);
+    Some(test_dir)
+}
+
+/// Infer the structure from where an existing test for the source already lives:
+/// `src/test/...` -> Maven, next to the source -> Adjacent, in a `__tests__/` beside it -> TestsDir,
+/// under `test/`/`tests/`/`__tests__/` at the root -> Flat
+/// (searching at most `max_scan_depth` levels below those directories)
+pub fn detect_structure_from_existing_test(
+    project_root: &Path,
+    source_path: &Path,
+    max_scan_depth: usize,
+) -> Option<StructureType> {
+    let base = source_path.file_stem()?.to_str()?;
+    let source_dir = source_path.parent()?;
+
+    if let Some(test_dir) = mirrored_src_test_dir(source_dir)
+        && dir_has_test_for(&test_dir, base)
     {
-        return Ok(StructureType::Maven);
+        return Some(StructureType::Maven);
     }
 
-    // Check for Gradle with build.gradle (which is Maven-like structure)
-    if project_root.join("build.gradle").exists() || project_root.join("build.gradle.kts").exists()
-    {
-        // Gradle can use Maven structure or custom structure
-        // For now, treat it as Maven-like since the resolver handles both
-        return Ok(StructureType::Gradle);
+    if dir_has_test_for(source_dir, base) {
+        return Some(StructureType::Adjacent);
+    }
+
+    if source_dir != project_root && dir_has_test_for(&source_dir.join(JS_TESTS_DIR), base) {
+        return Some(StructureType::TestsDir);
     }
 
-    // Default to Maven for Java (most common)
-    Ok(StructureType::Maven)
+    ["test", "tests", "__tests__"]
+        .iter()
+        .any(|dir| tree_has_test_for(&project_root.join(dir), base, max_scan_depth))
+        .then_some(StructureType::Flat)
+}
+
+/// Detect Java project structure from the build tool, whatever the layout looks like
+/// (Gradle projects use the src/main and src/test layout too)
+fn detect_java_structure(project_root: &Path) -> Result<StructureType, TestsmithError> {
+    match detect_build_tool(project_root) {
+        Some(tool) if tool.is_gradle() => Ok(StructureType::Gradle),
+        // Maven, sbt or no build file at all: Maven is the most common layout
+        _ => Ok(StructureType::Maven),
+    }
 }
 
 /// Detect Rust project structure
@@ -48,9 +184,69 @@ fn detect_rust_structure(project_root: &Path) -> Result<StructureType, Testsmith
     Ok(StructureType::SameFile)
 }
 
+/// `testMatch` and `roots` entries from the `jest` key of package.json, or
+/// from the first jest.config.* file found
+fn jest_test_patterns(project_root: &Path) -> Vec<String> {
+    if let Ok(content) = fs::read_to_string(project_root.join("package.json"))
+        && let Ok(manifest) = serde_json::from_str::<serde_json::Value>(&content)
+        && let Some(jest) = manifest.get("jest")
+    {
+        return ["testMatch", "roots"]
+            .iter()
+            .filter_map(|key| jest.get(key)?.as_array())
+            .flatten()
+            .filter_map(|entry| entry.as_str().map(str::to_string))
+            .collect();
+    }
+
+    let list_regex = Regex::new(r"(?s)\b(?:testMatch|roots)\s*:\s*\[(.*?)\]").unwrap();
+    let entry_regex = Regex::new(r#"['"`]([^'"`]+)['"`]"#).unwrap();
+    ["jest.config.js", "jest.config.ts", "jest.config.mjs", "jest.config.cjs"]
+        .iter()
+        .find_map(|name| fs::read_to_string(project_root.join(name)).ok())
+        .map(|config| {
+            list_regex
+                .captures_iter(&config)
+                .flat_map(|list| {
+                    entry_regex
+                        .captures_iter(list.get(1).map_or("", |m| m.as_str()))
+                        .map(|entry| entry[1].to_string())
+                        .collect::<Vec<_>>()
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Structure implied by jest's `testMatch`/`roots`: `__tests__` folders sit beside
+/// the source, a `test`/`tests` folder means Flat, and patterns that only match
+/// file names (`**/*.test.js`) mean co-located tests
+fn structure_from_jest_patterns(patterns: &[String]) -> Option<StructureType> {
+    if patterns.is_empty() {
+        return None;
+    }
+
+    let has_segment = |names: &[&str]| {
+        patterns
+            .iter()
+            .any(|pattern| pattern.split('/').any(|segment| names.contains(&segment)))
+    };
+    Some(if has_segment(&[JS_TESTS_DIR]) {
+        StructureType::TestsDir
+    } else if has_segment(&["tests", "test"]) {
+        StructureType::Flat
+    } else {
+        StructureType::Adjacent
+    })
+}
+
 /// Detect JavaScript/TypeScript project structure
-/// Priority: __tests__/ > tests/ > test/ > same-file (.test.js/.spec.js)
-fn detect_js_structure(project_root: &Path) -> Result<StructureType, TestsmithError> {
+/// Priority: jest testMatch/roots > __tests__/ > tests/ > test/ > same-file (.test.js/.spec.js)
+fn detect_js_structure(project_root: &Path, max_scan_depth: usize) -> Result<StructureType, TestsmithError> {
+    if let Some(structure) = structure_from_jest_patterns(&jest_test_patterns(project_root)) {
+        return Ok(structure);
+    }
+
     // Check for __tests__ directory (Jest default)
     if project_root.join("__tests__").is_dir() {
         return Ok(StructureType::Flat); // Use Flat to indicate subdirectory strategy
@@ -66,10 +262,39 @@ fn detect_js_structure(project_root: &Path) -> Result<StructureType, TestsmithEr
         return Ok(StructureType::Flat);
     }
 
+    // `foo.test.js`/`foo.spec.ts` files among the sources mean tests sit beside what they test
+    if tree_has_co_located_test(project_root, max_scan_depth) {
+        return Ok(StructureType::Adjacent);
+    }
+
     // Default to same-file (tests co-located with source)
     Ok(StructureType::SameFile)
 }
 
+/// Whether a directory tree holds a `*.test.*` or `*.spec.*` file, descending at most `max_depth`
+/// levels and skipping `node_modules` and hidden directories
+fn tree_has_co_located_test(dir: &Path, max_depth: usize) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+
+    entries.flatten().map(|entry| entry.path()).any(|path| {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            return false;
+        };
+        if path.is_dir() {
+            max_depth > 0
+                && name != "node_modules"
+                && !name.starts_with('.')
+                && tree_has_co_located_test(&path, max_depth - 1)
+        } else {
+            path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| stem.ends_with(".test") || stem.ends_with(".spec"))
+        }
+    })
+}
+
 /// Detect Python project structure
 /// Priority: tests/ > test/ > same-file (test_*.py)
 fn detect_python_structure(project_root: &Path) -> Result<StructureType, TestsmithError> {
@@ -87,10 +312,98 @@ fn detect_python_structure(project_root: &Path) -> Result<StructureType, Testsmi
     Ok(StructureType::SameFile)
 }
 
+/// Test file naming conventions seen in the wild, most common first
+fn naming_candidates(language: Language) -> Vec<NamingConvention> {
+    let convention = |prefix: &str, suffix: &str, separator: &str| NamingConvention {
+        suffix: suffix.to_string(),
+        prefix: prefix.to_string(),
+        separator: separator.to_string(),
+    };
+
+    match language {
+        Language::Java | Language::Kotlin => vec![
+            convention("", "Test", ""),
+            convention("", "Tests", ""),
+            convention("Test", "", ""),
+        ],
+        Language::Scala => vec![
+            convention("", "Spec", ""),
+            convention("", "Test", ""),
+            convention("", "Suite", ""),
+        ],
+        Language::Python => vec![convention("test", "", "_"), convention("", "test", "_")],
+        _ => Vec::new(),
+    }
+}
+
+/// Directories (relative to the project root) holding existing tests, and their file extension
+fn existing_test_dirs(language: Language) -> (Vec<&'static str>, &'static str) {
+    match language {
+        Language::Java => (vec!["src/test/java"], "java"),
+        Language::Kotlin => (vec!["src/test/kotlin"], "kt"),
+        Language::Scala => (vec!["src/test/scala"], "scala"),
+        Language::Python => (vec!["tests", "test"], "py"),
+        _ => (Vec::new(), ""),
+    }
+}
+
+/// Count existing test files matching each naming candidate under a directory,
+/// descending at most `max_depth` levels
+fn count_test_names(
+    dir: &Path,
+    extension: &str,
+    candidates: &[NamingConvention],
+    counts: &mut [usize],
+    max_depth: usize,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if max_depth > 0 {
+                count_test_names(&path, extension, candidates, counts, max_depth - 1);
+            }
+        } else if path.extension().is_some_and(|ext| ext == extension)
+            && let Some(stem) = path.file_stem().and_then(|stem| stem.to_str())
+        {
+            for (candidate, count) in candidates.iter().zip(counts.iter_mut()) {
+                if candidate.matches(stem) {
+                    *count += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Infer the dominant naming of existing test files (`FooTests.java`, `test_foo.py`, ...)
+/// Returns None when the project has no recognisable tests within `max_scan_depth` levels
+pub fn detect_test_naming(project_root: &Path, language: Language, max_scan_depth: usize) -> Option<NamingConvention> {
+    let candidates = naming_candidates(language);
+    let (dirs, extension) = existing_test_dirs(language);
+    let mut counts = vec![0; candidates.len()];
+
+    for dir in dirs {
+        count_test_names(&project_root.join(dir), extension, &candidates, &mut counts, max_scan_depth);
+    }
+
+    // Earlier candidates win ties, so the usual convention is kept when unsure
+    let (best, best_count) = counts
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, count)| **count)?;
+
+    (*best_count > 0).then(|| candidates[best].clone())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
+    use crate::config::testsmithrc::DEFAULT_MAX_SCAN_DEPTH;
     use tempfile::TempDir;
 
     #[test]
@@ -99,7 +412,7 @@ mod tests {
         fs::create_dir_all(temp_dir.path().join("src/main/java")).unwrap();
         fs::create_dir_all(temp_dir.path().join("src/test/java")).unwrap();
 
-        let structure = detect_structure(temp_dir.path(), Language::Java).unwrap();
+        let structure = detect_project_structure(temp_dir.path(), Language::Java, DEFAULT_MAX_SCAN_DEPTH).unwrap();
         assert_eq!(structure, StructureType::Maven);
     }
 
@@ -108,15 +421,30 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         fs::File::create(temp_dir.path().join("build.gradle")).unwrap();
 
-        let structure = detect_structure(temp_dir.path(), Language::Java).unwrap();
+        let structure = detect_project_structure(temp_dir.path(), Language::Java, DEFAULT_MAX_SCAN_DEPTH).unwrap();
+        assert_eq!(structure, StructureType::Gradle);
+    }
+
+    #[test]
+    fn test_gradle_build_with_maven_layout_is_gradle() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("src/main/java")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("src/test/java")).unwrap();
+        fs::File::create(temp_dir.path().join("build.gradle.kts")).unwrap();
+
+        let structure = detect_project_structure(temp_dir.path(), Language::Java, DEFAULT_MAX_SCAN_DEPTH).unwrap();
         assert_eq!(structure, StructureType::Gradle);
+
+        fs::File::create(temp_dir.path().join("pom.xml")).unwrap();
+        let structure = detect_project_structure(temp_dir.path(), Language::Java, DEFAULT_MAX_SCAN_DEPTH).unwrap();
+        assert_eq!(structure, StructureType::Maven);
     }
 
     #[test]
     fn test_detect_java_default_maven() {
         let temp_dir = TempDir::new().unwrap();
 
-        let structure = detect_structure(temp_dir.path(), Language::Java).unwrap();
+        let structure = detect_project_structure(temp_dir.path(), Language::Java, DEFAULT_MAX_SCAN_DEPTH).unwrap();
         assert_eq!(structure, StructureType::Maven);
     }
 
@@ -124,7 +452,7 @@ mod tests {
     fn test_detect_rust_same_file() {
         let temp_dir = TempDir::new().unwrap();
 
-        let structure = detect_structure(temp_dir.path(), Language::Rust).unwrap();
+        let structure = detect_project_structure(temp_dir.path(), Language::Rust, DEFAULT_MAX_SCAN_DEPTH).unwrap();
         assert_eq!(structure, StructureType::SameFile);
     }
 
@@ -133,7 +461,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         fs::create_dir(temp_dir.path().join("__tests__")).unwrap();
 
-        let structure = detect_structure(temp_dir.path(), Language::JavaScript).unwrap();
+        let structure = detect_project_structure(temp_dir.path(), Language::JavaScript, DEFAULT_MAX_SCAN_DEPTH).unwrap();
         assert_eq!(structure, StructureType::Flat);
     }
 
@@ -142,7 +470,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         fs::create_dir(temp_dir.path().join("test")).unwrap();
 
-        let structure = detect_structure(temp_dir.path(), Language::JavaScript).unwrap();
+        let structure = detect_project_structure(temp_dir.path(), Language::JavaScript, DEFAULT_MAX_SCAN_DEPTH).unwrap();
         assert_eq!(structure, StructureType::Flat);
     }
 
@@ -150,16 +478,77 @@ mod tests {
     fn test_detect_js_same_file_default() {
         let temp_dir = TempDir::new().unwrap();
 
-        let structure = detect_structure(temp_dir.path(), Language::JavaScript).unwrap();
+        let structure = detect_project_structure(temp_dir.path(), Language::JavaScript, DEFAULT_MAX_SCAN_DEPTH).unwrap();
         assert_eq!(structure, StructureType::SameFile);
     }
 
+    #[test]
+    fn test_detect_js_adjacent_from_co_located_tests() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("node_modules/lib")).unwrap();
+        fs::File::create(temp_dir.path().join("node_modules/lib/index.test.js")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("src/cart")).unwrap();
+        fs::File::create(temp_dir.path().join("src/cart/total.ts")).unwrap();
+
+        // A dependency's own tests say nothing about this project
+        let structure = detect_project_structure(temp_dir.path(), Language::TypeScript, DEFAULT_MAX_SCAN_DEPTH).unwrap();
+        assert_eq!(structure, StructureType::SameFile);
+
+        fs::File::create(temp_dir.path().join("src/cart/total.spec.ts")).unwrap();
+        let structure = detect_project_structure(temp_dir.path(), Language::TypeScript, DEFAULT_MAX_SCAN_DEPTH).unwrap();
+        assert_eq!(structure, StructureType::Adjacent);
+        assert_eq!(
+            detect_project_structure(temp_dir.path(), Language::TypeScript, 1).unwrap(),
+            StructureType::SameFile
+        );
+    }
+
+    #[test]
+    fn test_detect_js_structure_from_jest_config_test_match() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("jest.config.js"),
+            "module.exports = {\n  testMatch: ['**/__tests__/**'],\n};\n",
+        )
+        .unwrap();
+
+        let structure = detect_project_structure(temp_dir.path(), Language::JavaScript, DEFAULT_MAX_SCAN_DEPTH).unwrap();
+        assert_eq!(structure, StructureType::TestsDir);
+    }
+
+    #[test]
+    fn test_detect_js_flat_from_jest_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("jest.config.js"),
+            "module.exports = {\n  roots: ['<rootDir>/test'],\n};\n",
+        )
+        .unwrap();
+
+        let structure = detect_project_structure(temp_dir.path(), Language::JavaScript, DEFAULT_MAX_SCAN_DEPTH).unwrap();
+        assert_eq!(structure, StructureType::Flat);
+    }
+
+    #[test]
+    fn test_detect_js_co_located_from_package_json_jest_key() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("test")).unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"jest": {"roots": ["<rootDir>/src"], "testMatch": ["**/*.test.ts"]}}"#,
+        )
+        .unwrap();
+
+        let structure = detect_project_structure(temp_dir.path(), Language::TypeScript, DEFAULT_MAX_SCAN_DEPTH).unwrap();
+        assert_eq!(structure, StructureType::Adjacent);
+    }
+
     #[test]
     fn test_detect_python_tests_directory() {
         let temp_dir = TempDir::new().unwrap();
         fs::create_dir(temp_dir.path().join("tests")).unwrap();
 
-        let structure = detect_structure(temp_dir.path(), Language::Python).unwrap();
+        let structure = detect_project_structure(temp_dir.path(), Language::Python, DEFAULT_MAX_SCAN_DEPTH).unwrap();
         assert_eq!(structure, StructureType::Flat);
     }
 
@@ -167,7 +556,156 @@ mod tests {
     fn test_detect_python_same_file_default() {
         let temp_dir = TempDir::new().unwrap();
 
-        let structure = detect_structure(temp_dir.path(), Language::Python).unwrap();
+        let structure = detect_project_structure(temp_dir.path(), Language::Python, DEFAULT_MAX_SCAN_DEPTH).unwrap();
         assert_eq!(structure, StructureType::SameFile);
     }
+
+    #[test]
+    fn test_detect_tests_suffix_from_existing_java_tests() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_dir = temp_dir.path().join("src/test/java/com/example");
+        fs::create_dir_all(&test_dir).unwrap();
+        fs::File::create(test_dir.join("FooTests.java")).unwrap();
+        fs::File::create(test_dir.join("BarTests.java")).unwrap();
+        fs::File::create(test_dir.join("BazTest.java")).unwrap();
+
+        let naming = detect_test_naming(temp_dir.path(), Language::Java, DEFAULT_MAX_SCAN_DEPTH).unwrap();
+        assert_eq!(naming.apply("Qux"), "QuxTests");
+    }
+
+    #[test]
+    fn test_detect_python_test_prefix() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("tests/unit")).unwrap();
+        fs::File::create(temp_dir.path().join("tests/unit/test_parser.py")).unwrap();
+        fs::File::create(temp_dir.path().join("tests/conftest.py")).unwrap();
+
+        let naming = detect_test_naming(temp_dir.path(), Language::Python, DEFAULT_MAX_SCAN_DEPTH).unwrap();
+        assert_eq!(naming.apply("lexer"), "test_lexer");
+    }
+
+    #[test]
+    fn test_detect_test_naming_without_existing_tests() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("src/test/java")).unwrap();
+
+        assert!(detect_test_naming(temp_dir.path(), Language::Java, DEFAULT_MAX_SCAN_DEPTH).is_none());
+    }
+
+    #[test]
+    fn test_existing_maven_test_beats_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("src/main/java/com/example");
+        let test_dir = temp_dir.path().join("src/test/java/com/example");
+        fs::create_dir_all(&source_dir).unwrap();
+        fs::create_dir_all(&test_dir).unwrap();
+        fs::File::create(source_dir.join("Foo.java")).unwrap();
+        fs::File::create(test_dir.join("FooTest.java")).unwrap();
+
+        // The build file alone would make this Gradle
+        fs::File::create(temp_dir.path().join("build.gradle")).unwrap();
+        assert_eq!(detect_project_structure(temp_dir.path(), Language::Java, DEFAULT_MAX_SCAN_DEPTH).unwrap(), StructureType::Gradle);
+
+        let detected =
+            detect_structure(temp_dir.path(), &source_dir.join("Foo.java"), Language::Java, DEFAULT_MAX_SCAN_DEPTH)
+                .unwrap();
+        assert_eq!(detected.structure, StructureType::Maven);
+        assert!(detected.from_existing_test);
+    }
+
+    #[test]
+    fn test_existing_adjacent_test_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("src/utils");
+        fs::create_dir_all(&source_dir).unwrap();
+        fs::File::create(source_dir.join("format.js")).unwrap();
+        fs::File::create(source_dir.join("format.test.js")).unwrap();
+        fs::create_dir(temp_dir.path().join("__tests__")).unwrap();
+
+        let detected = detect_structure(
+            temp_dir.path(),
+            &source_dir.join("format.js"),
+            Language::JavaScript,
+            DEFAULT_MAX_SCAN_DEPTH,
+        )
+        .unwrap();
+        assert_eq!(detected.structure, StructureType::Adjacent);
+    }
+
+    #[test]
+    fn test_existing_tests_dir_test_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        let tests_dir = temp_dir.path().join("src/utils/__tests__");
+        fs::create_dir_all(&tests_dir).unwrap();
+        fs::File::create(temp_dir.path().join("src/utils/format.js")).unwrap();
+        fs::File::create(tests_dir.join("format.test.js")).unwrap();
+
+        assert_eq!(
+            detect_structure_from_existing_test(
+                temp_dir.path(),
+                &temp_dir.path().join("src/utils/format.js"),
+                DEFAULT_MAX_SCAN_DEPTH
+            ),
+            Some(StructureType::TestsDir)
+        );
+    }
+
+    #[test]
+    fn test_existing_flat_test_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("app")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("tests/unit")).unwrap();
+        fs::File::create(temp_dir.path().join("app/parser.py")).unwrap();
+        fs::File::create(temp_dir.path().join("tests/unit/test_parser.py")).unwrap();
+
+        assert_eq!(
+            detect_structure_from_existing_test(temp_dir.path(), &temp_dir.path().join("app/parser.py"), DEFAULT_MAX_SCAN_DEPTH),
+            Some(StructureType::Flat)
+        );
+    }
+
+    #[test]
+    fn test_scans_stop_at_max_depth() {
+        let temp_dir = TempDir::new().unwrap();
+        let deep_dir = temp_dir.path().join("tests/a/b/c");
+        fs::create_dir_all(&deep_dir).unwrap();
+        fs::create_dir_all(temp_dir.path().join("app")).unwrap();
+        fs::File::create(temp_dir.path().join("app/parser.py")).unwrap();
+        fs::File::create(deep_dir.join("test_parser.py")).unwrap();
+
+        let source = temp_dir.path().join("app/parser.py");
+        assert_eq!(detect_structure_from_existing_test(temp_dir.path(), &source, 2), None);
+        assert_eq!(detect_test_naming(temp_dir.path(), Language::Python, 2), None);
+
+        assert_eq!(
+            detect_structure_from_existing_test(temp_dir.path(), &source, 3),
+            Some(StructureType::Flat)
+        );
+        assert!(detect_test_naming(temp_dir.path(), Language::Python, 3).is_some());
+    }
+
+    #[test]
+    fn test_no_existing_test_falls_back_to_heuristics() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("src/main/java")).unwrap();
+        fs::File::create(temp_dir.path().join("src/main/java/Foo.java")).unwrap();
+
+        assert_eq!(
+            detect_structure_from_existing_test(
+                temp_dir.path(),
+                &temp_dir.path().join("src/main/java/Foo.java"),
+                DEFAULT_MAX_SCAN_DEPTH
+            ),
+            None
+        );
+        let detected = detect_structure(
+            temp_dir.path(),
+            &temp_dir.path().join("src/main/java/Foo.java"),
+            Language::Java,
+            DEFAULT_MAX_SCAN_DEPTH,
+        )
+        .unwrap();
+        assert_eq!(detected.structure, StructureType::Maven);
+        assert!(!detected.from_existing_test);
+    }
 }
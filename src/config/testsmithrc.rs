@@ -0,0 +1,232 @@
+use crate::cli::{Framework, Language, StructureType};
+use crate::config::naming::NamingConvention;
+use crate::error::TestsmithError;
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Name of the optional per-project config file; its directory is always the project root
+pub const RC_FILE_NAME: &str = ".testsmithrc";
+
+/// Directory levels a project scan descends below where it starts, unless configured
+pub const DEFAULT_MAX_SCAN_DEPTH: usize = 4;
+
+/// Environment variable setting `max_scan_depth` for projects whose `.testsmithrc` doesn't
+pub const MAX_SCAN_DEPTH_ENV: &str = "TESTSMITH_MAX_SCAN_DEPTH";
+
+/// Contents of a `.testsmithrc` file (JSON)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct TestsmithRc {
+    /// Test file naming convention (defaults to the `Test` suffix)
+    pub naming: Option<NamingConvention>,
+    /// Workspace member holding Rust integration tests (defaults to `tests`)
+    pub test_crate: Option<String>,
+    /// Structure to use instead of auto-detection (e.g. `adjacent` for `foo.test.cpp`)
+    pub structure: Option<String>,
+    /// Framework to use instead of auto-detection (e.g. `junit4`, or `{ "js": "jest", "ts": "jasmine" }`)
+    pub framework: Option<FrameworkSetting>,
+    /// Prefix for PHP test namespaces (defaults to `Tests`; empty keeps the source namespace)
+    pub php_test_namespace: Option<String>,
+    /// Text of the generated TODO comment (defaults to `TODO: Implement test`)
+    pub todo_text: Option<String>,
+    /// Generate tests that fail until implemented (defaults to false)
+    pub failing_stub: Option<bool>,
+    /// Wrap per-method test stubs in `// region` / `// endregion` markers (defaults to false)
+    pub section_markers: Option<bool>,
+    /// Directory levels that existing-test and naming scans descend (defaults to 4)
+    pub max_scan_depth: Option<usize>,
+}
+
+/// The `framework` entry: one framework for the project, or one per file kind
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum FrameworkSetting {
+    All(String),
+    /// Keyed by file extension (`ts`) or language name (`typescript`); the extension wins
+    PerFile(HashMap<String, String>),
+}
+
+impl TestsmithRc {
+    /// Parse the configured structure, using the same names as `--structure`
+    pub fn structure(&self) -> Result<Option<StructureType>, TestsmithError> {
+        self.structure
+            .as_deref()
+            .map(|name| {
+                StructureType::from_str(name, true).map_err(|_| TestsmithError::ConfigError {
+                    reason: format!("Unknown structure '{}' in {}", name, RC_FILE_NAME),
+                })
+            })
+            .transpose()
+    }
+
+    /// Parse the framework configured for this source file, using the same names as `--framework`
+    /// Returns None when a per-file mapping has no entry for the file's extension or language
+    pub fn framework_for(&self, source_path: &Path, language: Language) -> Result<Option<Framework>, TestsmithError> {
+        let name = match self.framework {
+            Some(FrameworkSetting::All(ref name)) => Some(name.as_str()),
+            Some(FrameworkSetting::PerFile(ref frameworks)) => source_path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .and_then(|ext| frameworks.get(ext))
+                .or_else(|| {
+                    let value = language.to_possible_value()?;
+                    frameworks.get(value.get_name())
+                })
+                .map(String::as_str),
+            None => None,
+        };
+
+        name.map(|name| {
+            Framework::from_str(name, true).map_err(|_| TestsmithError::ConfigError {
+                reason: format!("Unknown framework '{}' in {}", name, RC_FILE_NAME),
+            })
+        })
+        .transpose()
+    }
+}
+
+/// How deep project scans descend: `.testsmithrc`, then `TESTSMITH_MAX_SCAN_DEPTH`, then the default
+pub fn max_scan_depth(rc: Option<&TestsmithRc>) -> Result<usize, TestsmithError> {
+    resolve_max_scan_depth(rc, std::env::var(MAX_SCAN_DEPTH_ENV).ok().as_deref())
+}
+
+fn resolve_max_scan_depth(rc: Option<&TestsmithRc>, env_value: Option<&str>) -> Result<usize, TestsmithError> {
+    if let Some(depth) = rc.and_then(|rc| rc.max_scan_depth) {
+        return Ok(depth);
+    }
+
+    match env_value {
+        Some(value) => value.trim().parse().map_err(|_| TestsmithError::ConfigError {
+            reason: format!("{} must be a non-negative integer, got '{}'", MAX_SCAN_DEPTH_ENV, value),
+        }),
+        None => Ok(DEFAULT_MAX_SCAN_DEPTH),
+    }
+}
+
+/// Load `.testsmithrc` from the project root, if present
+pub fn load_rc(project_root: &Path) -> Result<Option<TestsmithRc>, TestsmithError> {
+    let rc_path = project_root.join(RC_FILE_NAME);
+
+    if !rc_path.is_file() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&rc_path).map_err(|e| TestsmithError::FileReadError {
+        path: rc_path.clone(),
+        source: e,
+    })?;
+
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| TestsmithError::ConfigError {
+            reason: format!("Failed to parse {}: {}", rc_path.display(), e),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_missing_rc_file() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(load_rc(temp_dir.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_load_php_test_namespace_from_rc_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(RC_FILE_NAME), r#"{ "php_test_namespace": "" }"#).unwrap();
+
+        let rc = load_rc(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(rc.php_test_namespace.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_load_todo_text_from_rc_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(RC_FILE_NAME), r#"{ "todo_text": "FIXME: add assertions" }"#).unwrap();
+
+        let rc = load_rc(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(rc.todo_text.as_deref(), Some("FIXME: add assertions"));
+    }
+
+    #[test]
+    fn test_load_naming_from_rc_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join(RC_FILE_NAME),
+            r#"{ "naming": { "suffix": "Tests" } }"#,
+        )
+        .unwrap();
+
+        let rc = load_rc(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(rc.naming.unwrap().apply("Foo"), "FooTests");
+    }
+
+    #[test]
+    fn test_load_structure_from_rc_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(RC_FILE_NAME), r#"{ "structure": "adjacent" }"#).unwrap();
+
+        let rc = load_rc(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(rc.structure().unwrap(), Some(StructureType::Adjacent));
+    }
+
+    #[test]
+    fn test_load_framework_from_rc_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(RC_FILE_NAME), r#"{ "framework": "junit4" }"#).unwrap();
+
+        let rc = load_rc(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(rc.framework_for(Path::new("Foo.java"), Language::Java).unwrap(), Some(Framework::JUnit4));
+    }
+
+    #[test]
+    fn test_load_per_file_frameworks_from_rc_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join(RC_FILE_NAME),
+            r#"{ "framework": { "js": "jest", "typescript": "jasmine" } }"#,
+        )
+        .unwrap();
+
+        let rc = load_rc(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(rc.framework_for(Path::new("src/cart.js"), Language::JavaScript).unwrap(), Some(Framework::Jest));
+        assert_eq!(rc.framework_for(Path::new("src/cart.ts"), Language::TypeScript).unwrap(), Some(Framework::Jasmine));
+        assert_eq!(rc.framework_for(Path::new("src/App.vue"), Language::Vue).unwrap(), None);
+    }
+
+    #[test]
+    fn test_max_scan_depth_prefers_rc_over_env() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(RC_FILE_NAME), r#"{ "max_scan_depth": 2 }"#).unwrap();
+        let rc = load_rc(temp_dir.path()).unwrap().unwrap();
+
+        assert_eq!(resolve_max_scan_depth(Some(&rc), Some("9")).unwrap(), 2);
+        assert_eq!(resolve_max_scan_depth(None, Some("9")).unwrap(), 9);
+        assert_eq!(resolve_max_scan_depth(None, None).unwrap(), DEFAULT_MAX_SCAN_DEPTH);
+        assert!(resolve_max_scan_depth(None, Some("deep")).is_err());
+    }
+
+    #[test]
+    fn test_unknown_structure_in_rc_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(RC_FILE_NAME), r#"{ "structure": "sideways" }"#).unwrap();
+
+        let rc = load_rc(temp_dir.path()).unwrap().unwrap();
+        assert!(rc.structure().is_err());
+    }
+
+    #[test]
+    fn test_invalid_rc_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(RC_FILE_NAME), "not json").unwrap();
+
+        assert!(load_rc(temp_dir.path()).is_err());
+    }
+}
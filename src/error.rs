@@ -48,6 +48,9 @@ pub enum TestsmithError {
     #[error("Class name extraction failed for {path}: {reason}")]
     ClassNameExtractionError { path: PathBuf, reason: String },
 
+    #[error("Already a test file: {path} (open its source file to navigate to the test)")]
+    AlreadyTestFile { path: PathBuf },
+
     #[error("Test file already exists: {path}")]
     TestFileAlreadyExists { path: PathBuf },
 
@@ -1,15 +1,19 @@
-/// C FFI bindings for calling Rust code from Lua
-///
-/// This module provides C-compatible functions that can be called via Lua FFI
-/// All memory is managed by the caller to ensure safety and compatibility
+//! C FFI bindings for calling Rust code from Lua
+//!
+//! This module provides C-compatible functions that can be called via Lua FFI
+//! All memory is managed by the caller to ensure safety and compatibility
 
 use crate::cli::{Framework, StructureType};
-use crate::file_ops::FileSystem;
-use crate::generator::{generate, GeneratorOptions};
+use crate::file_ops::{EntryKind, FileSystem};
+use crate::cache::{self, CacheStore};
+use crate::config::defaults;
 use crate::config::language as config_language;
+use crate::config::testsmithrc;
+use crate::error::TestsmithError;
+use crate::generator::{generate_with_cache, generate_with_store, GeneratorOptions, GeneratorResult};
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Result type for FFI operations
 #[repr(C)]
@@ -22,16 +26,38 @@ pub struct TestsmithResult {
     pub created: i32,
     /// Line number where cursor should be positioned (1-indexed)
     pub line_number: i32,
+    /// Byte column where cursor should be positioned on that line (0-indexed, as nvim_win_set_cursor takes)
+    pub column_number: i32,
+    /// Framework that was chosen, e.g. "JUnit" (caller must free; null on error)
+    pub framework: *mut c_char,
+    /// Whether an existing test was opened rather than a new one created (0 = no, 1 = yes)
+    pub found_existing: i32,
+    /// Content a dry run would write, the whole file when adding to an existing test (caller must free; null unless a dry run would write)
+    pub preview_content: *mut c_char,
+    /// Newline-separated warnings worth showing the user (caller must free; null when there are none)
+    pub warnings: *mut c_char,
+    /// Whether the test holds nothing but a TODO stub, so the cursor lands on it (0 = no, 1 = yes)
+    pub has_only_stub: i32,
+    /// Resolver that placed the test, e.g. "Same File" (caller must free; null on error)
+    pub resolver_name: *mut c_char,
 }
 
 impl TestsmithResult {
-    fn success(message: &str, created: bool, line_number: i32) -> Self {
+    fn success(message: &str, created: bool, line_number: i32, framework: &str, found_existing: bool) -> Self {
         let c_string = CString::new(message).unwrap_or_else(|_| CString::new("").unwrap());
+        let framework_string = CString::new(framework).unwrap_or_else(|_| CString::new("").unwrap());
         TestsmithResult {
             success: 1,
             message: c_string.into_raw(),
             created: if created { 1 } else { 0 },
             line_number,
+            column_number: 0,
+            framework: framework_string.into_raw(),
+            found_existing: if found_existing { 1 } else { 0 },
+            preview_content: std::ptr::null_mut(),
+            warnings: std::ptr::null_mut(),
+            has_only_stub: 0,
+            resolver_name: std::ptr::null_mut(),
         }
     }
 
@@ -42,32 +68,216 @@ impl TestsmithResult {
             message: c_string.into_raw(),
             created: 0,
             line_number: 0,
+            column_number: 0,
+            framework: std::ptr::null_mut(),
+            found_existing: 0,
+            preview_content: std::ptr::null_mut(),
+            warnings: std::ptr::null_mut(),
+            has_only_stub: 0,
+            resolver_name: std::ptr::null_mut(),
+        }
+    }
+}
+
+/// Result array returned by testsmith_find_or_create_batch
+#[repr(C)]
+pub struct TestsmithBatchResult {
+    /// One result per input path, in input order (caller must free via testsmith_batch_free)
+    pub results: *mut TestsmithResult,
+    /// Number of entries in `results`
+    pub count: usize,
+}
+
+/// Release the strings owned by a result without freeing the result itself
+unsafe fn free_result_strings(result: &mut TestsmithResult) {
+    unsafe {
+        if !result.message.is_null() {
+            let _ = CString::from_raw(result.message);
+            result.message = std::ptr::null_mut();
+        }
+        if !result.framework.is_null() {
+            let _ = CString::from_raw(result.framework);
+            result.framework = std::ptr::null_mut();
+        }
+        if !result.preview_content.is_null() {
+            let _ = CString::from_raw(result.preview_content);
+            result.preview_content = std::ptr::null_mut();
+        }
+        if !result.warnings.is_null() {
+            let _ = CString::from_raw(result.warnings);
+            result.warnings = std::ptr::null_mut();
+        }
+        if !result.resolver_name.is_null() {
+            let _ = CString::from_raw(result.resolver_name);
+            result.resolver_name = std::ptr::null_mut();
         }
     }
 }
 
 /// Free a TestsmithResult's allocated memory
 /// IMPORTANT: This must be called after reading the result to avoid memory leaks
+///
+/// # Safety
+/// `result` must be null or a pointer previously returned by this library
 #[unsafe(no_mangle)]
-pub extern "C" fn testsmith_result_free(result: *mut TestsmithResult) {
+pub unsafe extern "C" fn testsmith_result_free(result: *mut TestsmithResult) {
     if !result.is_null() {
         unsafe {
-            if !(*result).message.is_null() {
-                let _ = CString::from_raw((*result).message);
-            }
+            free_result_strings(&mut *result);
             let _ = Box::from_raw(result);
         }
     }
 }
 
+/// Free a TestsmithBatchResult and every result it holds
+///
+/// # Safety
+/// `batch` must be null or a pointer previously returned by testsmith_find_or_create_batch
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testsmith_batch_free(batch: *mut TestsmithBatchResult) {
+    if batch.is_null() {
+        return;
+    }
+    unsafe {
+        let batch = Box::from_raw(batch);
+        if !batch.results.is_null() {
+            let slice = std::ptr::slice_from_raw_parts_mut(batch.results, batch.count);
+            let mut results = Box::from_raw(slice);
+            for result in results.iter_mut() {
+                free_result_strings(result);
+            }
+        }
+    }
+}
+
+/// Parse a structure type name as passed from Lua ("auto" or null means detect per file)
+fn parse_structure(structure: *const c_char) -> Result<Option<StructureType>, &'static str> {
+    if structure.is_null() {
+        return Ok(None);
+    }
+
+    let structure_str = unsafe { CStr::from_ptr(structure).to_str() }.map_err(|_| "Invalid structure encoding")?;
+
+    match structure_str {
+        "auto" => Ok(None),
+        "maven" => Ok(Some(StructureType::Maven)),
+        "gradle" => Ok(Some(StructureType::Gradle)),
+        "flat" => Ok(Some(StructureType::Flat)),
+        "same-file" => Ok(Some(StructureType::SameFile)),
+        "test-crate" => Ok(Some(StructureType::TestCrate)),
+        "adjacent" => Ok(Some(StructureType::Adjacent)),
+        "tests-dir" => Ok(Some(StructureType::TestsDir)),
+        _ => Err("Invalid structure type"),
+    }
+}
+
+/// Parse an optional framework name ("auto" or null means auto-detect)
+fn parse_framework(framework: *const c_char) -> Result<Option<Framework>, &'static str> {
+    if framework.is_null() {
+        return Ok(None);
+    }
+
+    match unsafe { CStr::from_ptr(framework).to_str() } {
+        Ok(s) => match s {
+            "auto" => Ok(None),  // Auto-detect in generator
+            "junit" => Ok(Some(Framework::JUnit)),
+            "junit4" => Ok(Some(Framework::JUnit4)),
+            "testng" => Ok(Some(Framework::TestNG)),
+            "native" => Ok(Some(Framework::Native)),
+            "rstest" => Ok(Some(Framework::Rstest)),
+            "proptest" => Ok(Some(Framework::Proptest)),
+            "jest" => Ok(Some(Framework::Jest)),
+            "pytest" => Ok(Some(Framework::Pytest)),
+            "unittest" => Ok(Some(Framework::Unittest)),
+            "vitest" => Ok(Some(Framework::Vitest)),
+            "gtest" | "googletest" => Ok(Some(Framework::GoogleTest)),
+            "gotest" => Ok(Some(Framework::GoTest)),
+            "exunit" => Ok(Some(Framework::ExUnit)),
+            "phpunit" => Ok(Some(Framework::PHPUnit)),
+            "scalatest" => Ok(Some(Framework::ScalaTest)),
+            "munit" => Ok(Some(Framework::MUnit)),
+            "xunit" => Ok(Some(Framework::XUnit)),
+            "jasmine" => Ok(Some(Framework::Jasmine)),
+            "darttest" => Ok(Some(Framework::DartTest)),
+            "mocha" => Ok(Some(Framework::Mocha)),
+            "rspec" => Ok(Some(Framework::RSpec)),
+            "hspec" => Ok(Some(Framework::HSpec)),
+            "xctest" => Ok(Some(Framework::XCTest)),
+            _ => Err("Invalid framework type"),
+        },
+        Err(_) => Err("Invalid framework encoding"),
+    }
+}
+
+/// Build generator options for a source path, auto-detecting its language
+fn options_for(
+    fs: &FileSystem,
+    source_path: &Path,
+    structure: Option<StructureType>,
+    framework: Option<Framework>,
+    create: i32,
+    dry_run: i32,
+    force: i32,
+) -> GeneratorOptions {
+    GeneratorOptions {
+        // Maven lets the generator detect the structure from the file's own project root
+        structure: structure.unwrap_or(StructureType::Maven),
+        language: config_language::detect_language(source_path).ok(),
+        framework,
+        create: create != 0,
+        dry_run: dry_run != 0,
+        force: force != 0,
+        // An unreadable defaults file shouldn't stop generation from the editor
+        defaults: defaults::load_defaults(fs).unwrap_or_default(),
+        ..Default::default()
+    }
+}
+
+/// Convert a generator outcome into an FFI result
+fn to_ffi_result(outcome: Result<GeneratorResult, TestsmithError>) -> TestsmithResult {
+    match outcome {
+        Ok(result) => {
+            let message = result.test_file_path.to_string();
+            let mut ffi_result = TestsmithResult::success(
+                &message,
+                result.created,
+                result.line_number,
+                &result.framework,
+                !result.created,
+            );
+            if let Some(preview) = result.preview_content
+                && let Ok(preview) = CString::new(preview)
+            {
+                ffi_result.preview_content = preview.into_raw();
+            }
+            if !result.warnings.is_empty()
+                && let Ok(warnings) = CString::new(result.warnings.join("\n"))
+            {
+                ffi_result.warnings = warnings.into_raw();
+            }
+            if let Ok(resolver_name) = CString::new(result.resolver_name) {
+                ffi_result.resolver_name = resolver_name.into_raw();
+            }
+            ffi_result.column_number = result.column_number;
+            ffi_result.has_only_stub = if result.has_only_stub { 1 } else { 0 };
+            ffi_result
+        }
+        Err(e) => {
+            let error_msg = format!("Error: {}", e);
+            TestsmithResult::error(&error_msg)
+        }
+    }
+}
+
 /// Find or create test file
 ///
 /// # Arguments
 /// * `source_path` - Null-terminated C string path to source file (used to auto-detect language)
-/// * `structure` - Project structure type: "maven", "gradle", "flat", "same-file"
-/// * `framework` - Test framework: "auto" (auto-detect), "junit", "junit4", "testng", "native", "jest", "pytest"
+/// * `structure` - Project structure type: "auto" (detect from the file's project), "maven", "gradle", "flat", "same-file", "test-crate", "adjacent", "tests-dir"
+/// * `framework` - Test framework: "auto" (auto-detect), "junit", "junit4", "testng", "native", "rstest", "proptest", "jest", "pytest", "unittest", "vitest", "gtest", "gotest", "exunit", "phpunit", "scalatest", "munit", "xunit", "jasmine", "darttest", "mocha", "rspec", "hspec", "xctest"
 /// * `create` - Whether to create the test file (1 = yes, 0 = no)
 /// * `dry_run` - Dry run mode (1 = yes, 0 = no)
+/// * `force` - Allow a test path outside the detected project root (1 = yes, 0 = no)
 ///
 /// # Returns
 /// TestsmithResult containing status and message
@@ -77,85 +287,343 @@ pub extern "C" fn testsmith_result_free(result: *mut TestsmithResult) {
 /// 1. Ensuring source_path is a valid null-terminated C string
 /// 2. Freeing the returned TestsmithResult using testsmith_result_free
 #[unsafe(no_mangle)]
-pub extern "C" fn testsmith_find_or_create(
+pub unsafe extern "C" fn testsmith_find_or_create(
+    source_path: *const c_char,
+    structure: *const c_char,
+    framework: *const c_char,
+    create: i32,
+    dry_run: i32,
+    force: i32,
+) -> *mut TestsmithResult {
+    let store = CacheStore::from_env().ok();
+    let outcome = unsafe { find_or_create(store.as_ref(), source_path, structure, framework, create, dry_run, force) };
+
+    Box::into_raw(Box::new(outcome.map_or_else(|error| error, |result| to_ffi_result(Ok(result)))))
+}
+
+/// Find or create test file, also returning the log of detection decisions
+///
+/// # Arguments
+/// * `source_path`, `structure`, `framework`, `create`, `dry_run`, `force` - As for testsmith_find_or_create
+/// * `decision_log` - Out-parameter; on return points to a newly allocated, null-terminated
+///   string of newline-separated decision lines (e.g. "Framework JUnit4: from cache").
+///   It is set to null on error or when `decision_log` itself is null
+///
+/// # Returns
+/// TestsmithResult containing status and message
+///
+/// # Safety
+/// The caller is responsible for:
+/// 1. Ensuring all string arguments are valid null-terminated C strings
+/// 2. Freeing the returned TestsmithResult using testsmith_result_free
+/// 3. Freeing `*decision_log` (if non-null) using testsmith_string_free; it is owned
+///    separately from the result and must not be freed any other way
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testsmith_find_or_create_verbose(
     source_path: *const c_char,
     structure: *const c_char,
     framework: *const c_char,
     create: i32,
     dry_run: i32,
+    force: i32,
+    decision_log: *mut *mut c_char,
 ) -> *mut TestsmithResult {
-    // Convert C strings to Rust strings
+    let store = CacheStore::from_env().ok();
+    let outcome = unsafe { find_or_create(store.as_ref(), source_path, structure, framework, create, dry_run, force) };
+
+    unsafe { with_decision_log(outcome, decision_log) }
+}
+
+/// Convert an outcome of `find_or_create` into an FFI result, handing its decisions out through
+/// `decision_log` (set to null on error; ignored when `decision_log` itself is null)
+///
+/// # Safety
+/// `decision_log` must be null or valid for writes
+unsafe fn with_decision_log(
+    outcome: Result<GeneratorResult, TestsmithResult>,
+    decision_log: *mut *mut c_char,
+) -> *mut TestsmithResult {
+    if !decision_log.is_null() {
+        let log = outcome
+            .as_ref()
+            .ok()
+            .map_or(std::ptr::null_mut(), |result| CString::new(result.decisions.join("\n")).unwrap_or_default().into_raw());
+        unsafe { *decision_log = log };
+    }
+
+    Box::into_raw(Box::new(outcome.map_or_else(|error| error, |result| to_ffi_result(Ok(result)))))
+}
+
+/// Parse the arguments testsmith_find_or_create and its verbose variant share, then generate
+/// against the cache in `store` (None = no cache); bad arguments and generator errors come back
+/// as the FFI error result, so callers can still read the generator result on success
+///
+/// # Safety
+/// `source_path` must be a valid null-terminated C string, `structure` and `framework` null or one
+unsafe fn find_or_create(
+    store: Option<&CacheStore>,
+    source_path: *const c_char,
+    structure: *const c_char,
+    framework: *const c_char,
+    create: i32,
+    dry_run: i32,
+    force: i32,
+) -> Result<GeneratorResult, TestsmithResult> {
     let source_path_str = match unsafe { CStr::from_ptr(source_path).to_str() } {
         Ok(s) => s,
-        Err(_) => return Box::into_raw(Box::new(TestsmithResult::error("Invalid source path encoding"))),
+        Err(_) => return Err(TestsmithResult::error("Invalid source path encoding")),
     };
+    let structure_type = parse_structure(structure).map_err(TestsmithResult::error)?;
+    let parsed_framework = parse_framework(framework).map_err(TestsmithResult::error)?;
 
-    let structure_str = match unsafe { CStr::from_ptr(structure).to_str() } {
-        Ok(s) => s,
-        Err(_) => return Box::into_raw(Box::new(TestsmithResult::error("Invalid structure encoding"))),
-    };
+    let fs = FileSystem::new_os();
+    let source_path_obj = Path::new(source_path_str);
+    let options = options_for(&fs, source_path_obj, structure_type, parsed_framework, create, dry_run, force);
 
-    // Parse structure type
-    let structure_type = match structure_str {
-        "maven" => StructureType::Maven,
-        "gradle" => StructureType::Gradle,
-        "flat" => StructureType::Flat,
-        "same-file" => StructureType::SameFile,
-        _ => return Box::into_raw(Box::new(TestsmithResult::error("Invalid structure type"))),
-    };
+    generate_with_store(&fs, source_path_obj, options, store).map_err(|e| to_ffi_result(Err(e)))
+}
 
-    // Auto-detect language from source path
-    let source_path_obj = Path::new(source_path_str);
-    let parsed_language = match config_language::detect_language(source_path_obj) {
-        Ok(lang) => Some(lang),
-        Err(_) => None,
+/// Free a string returned through an out-parameter (e.g. the decision log)
+///
+/// # Safety
+/// `string` must be null or a pointer previously handed out by this library
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testsmith_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        unsafe {
+            let _ = CString::from_raw(string);
+        }
+    }
+}
+
+/// Find or create test files for several sources, loading the project cache once
+/// and saving it once (skipped in dry-run mode)
+///
+/// # Arguments
+/// * `source_paths` - Array of `count` null-terminated C string paths
+/// * `count` - Number of paths in `source_paths`
+/// * `structure`, `framework`, `create`, `dry_run`, `force` - As for testsmith_find_or_create, applied to every path;
+///   with "auto" each path gets the structure and framework of its own nearest project root,
+///   so one batch can span a monorepo mixing e.g. Maven modules and Python packages
+///
+/// # Returns
+/// TestsmithBatchResult with one TestsmithResult per path, in input order.
+/// A failure for one path is reported in its entry and does not stop the others.
+///
+/// # Safety
+/// The caller is responsible for:
+/// 1. Ensuring source_paths points to `count` valid null-terminated C strings
+/// 2. Freeing the returned TestsmithBatchResult using testsmith_batch_free
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testsmith_find_or_create_batch(
+    source_paths: *const *const c_char,
+    count: usize,
+    structure: *const c_char,
+    framework: *const c_char,
+    create: i32,
+    dry_run: i32,
+    force: i32,
+) -> *mut TestsmithBatchResult {
+    let paths: &[*const c_char] = if source_paths.is_null() || count == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(source_paths, count) }
     };
 
-    // Parse optional framework ("auto" or explicit framework name)
-    let parsed_framework = if !framework.is_null() {
-        match unsafe { CStr::from_ptr(framework).to_str() } {
-            Ok(s) => match s {
-                "auto" => None,  // Auto-detect in generator
-                "junit" => Some(Framework::JUnit),
-                "junit4" => Some(Framework::JUnit4),
-                "testng" => Some(Framework::TestNG),
-                "native" => Some(Framework::Native),
-                "jest" => Some(Framework::Jest),
-                "pytest" => Some(Framework::Pytest),
-                _ => return Box::into_raw(Box::new(TestsmithResult::error("Invalid framework type"))),
-            },
-            Err(_) => return Box::into_raw(Box::new(TestsmithResult::error("Invalid framework encoding"))),
+    let settings = parse_structure(structure).and_then(|s| parse_framework(framework).map(|f| (s, f)));
+
+    let results = match settings {
+        Ok((structure_type, parsed_framework)) => {
+            let sources: Vec<Result<&Path, &str>> = paths
+                .iter()
+                .map(|&path| {
+                    if path.is_null() {
+                        return Err("Invalid source path");
+                    }
+                    match unsafe { CStr::from_ptr(path).to_str() } {
+                        Ok(s) => Ok(Path::new(s)),
+                        Err(_) => Err("Invalid source path encoding"),
+                    }
+                })
+                .collect();
+
+            let fs = FileSystem::new_os();
+            let options = |source: &Path| {
+                options_for(&fs, source, structure_type, parsed_framework, create, dry_run, force)
+            };
+            generate_batch(&fs, CacheStore::from_env().ok().as_ref(), &sources, options, dry_run != 0)
         }
-    } else {
-        None
+        // Bad settings fail every path without touching the cache
+        Err(reason) => paths.iter().map(|_| TestsmithResult::error(reason)).collect(),
     };
 
-    let fs = FileSystem::new_os();
+    let count = results.len();
+    let results = Box::into_raw(results.into_boxed_slice()) as *mut TestsmithResult;
+    Box::into_raw(Box::new(TestsmithBatchResult { results, count }))
+}
 
-    let options = GeneratorOptions {
-        structure: structure_type,
-        language: parsed_language,
-        framework: parsed_framework,
-        create: create != 0,
-        dry_run: dry_run != 0,
+/// Generate for each source against one load of the cache in `store` (None = no cache),
+/// saved once at the end unless this is a dry run
+fn generate_batch(
+    fs: &FileSystem,
+    store: Option<&CacheStore>,
+    sources: &[Result<&Path, &str>],
+    options_for_source: impl Fn(&Path) -> GeneratorOptions,
+    dry_run: bool,
+) -> Vec<TestsmithResult> {
+    let original_cache = store.and_then(|store| store.load().ok()).unwrap_or_default();
+    let mut project_cache = original_cache.clone();
+    cache::prune_cache(&mut project_cache, cache::CACHE_MAX_AGE_SECS);
+
+    let results = sources
+        .iter()
+        .map(|source| match source {
+            Ok(source) => to_ffi_result(generate_with_cache(fs, source, options_for_source(source), &mut project_cache)),
+            Err(reason) => TestsmithResult::error(reason),
+        })
+        .collect();
+
+    if let Some(store) = store
+        && !dry_run
+        && project_cache != original_cache
+    {
+        let _ = store.save(&project_cache);
+    }
+
+    results
+}
+
+/// Directories never searched for sources to plan tests for
+const PLAN_SKIPPED_DIRS: [&str; 3] = ["node_modules", "target", "build"];
+
+/// Whether a file name looks like a test rather than a source (FooTest.java, test_foo.py, foo.test.ts)
+fn looks_like_test_file(path: &Path) -> bool {
+    let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return false;
     };
+    stem.starts_with("test_")
+        || [".test", ".spec", "_test", "_spec", "Test", "Tests", "Spec"]
+            .iter()
+            .any(|suffix| stem.ends_with(suffix))
+}
 
-    match generate(&fs, source_path_obj, options) {
-        Ok(result) => {
-            let message = format!("{}", result.test_file_path);
-            Box::into_raw(Box::new(TestsmithResult::success(
-                &message,
-                result.created,
-                result.line_number,
-            )))
-        }
-        Err(e) => {
-            let error_msg = format!("Error: {}", e);
-            Box::into_raw(Box::new(TestsmithResult::error(&error_msg)))
+/// Source files under a directory with a detectable language, sorted, skipping hidden
+/// and build output directories, symlinked directories and files that are tests themselves
+/// Descends at most `depth` directories below `dir`
+fn plan_sources(fs: &FileSystem, dir: &Path, depth: usize, sources: &mut Vec<PathBuf>) -> Result<(), TestsmithError> {
+    for (path, kind) in fs.read_dir(dir)? {
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or("");
+        match kind {
+            EntryKind::Dir => {
+                if depth > 0 && !name.starts_with('.') && !PLAN_SKIPPED_DIRS.contains(&name) {
+                    plan_sources(fs, &path, depth - 1, sources)?;
+                }
+            }
+            EntryKind::File => {
+                if config_language::detect_language(&path).is_ok() && !looks_like_test_file(&path) {
+                    sources.push(path);
+                }
+            }
+            // A symlinked directory may loop back or leave the project
+            EntryKind::Symlink => {}
         }
     }
+    Ok(())
+}
+
+/// One `source\ttest_path\taction` line per source under `dir`, planned against `store`'s cache
+fn plan_directory(fs: &FileSystem, dir: &Path, store: &CacheStore) -> Result<Vec<String>, TestsmithError> {
+    let rc = testsmithrc::load_rc(dir)?;
+    let mut sources = Vec::new();
+    plan_sources(fs, dir, testsmithrc::max_scan_depth(rc.as_ref())?, &mut sources)?;
+
+    // Dry runs never save the cache, so this one is only read
+    let mut project_cache = store.load().unwrap_or_default();
+    Ok(sources
+        .iter()
+        .filter_map(|source| {
+            let options = options_for(fs, source, None, None, 1, 1, 0);
+            let result = generate_with_cache(fs, source, options, &mut project_cache).ok()?;
+            let action = match (result.created, Path::new(&result.test_file_path) == source) {
+                (true, true) => "append",
+                (true, false) => "create",
+                (false, _) => "exists",
+            };
+            Some(format!("{}\t{}\t{}", source.display(), result.test_file_path, action))
+        })
+        .collect())
+}
+
+/// Plan the tests for every source under a directory without writing anything
+///
+/// # Arguments
+/// * `dir_path` - Null-terminated C string path to the directory to plan
+///
+/// # Returns
+/// TestsmithResult whose message holds one `source\ttest_path\taction` line per source,
+/// where action is "create" (new test file), "append" (test module added to the source itself)
+/// or "exists" (the test is already there). Sources the generator cannot handle are left out.
+/// The scan descends `max_scan_depth` levels (`.testsmithrc` in the directory, then `TESTSMITH_MAX_SCAN_DEPTH`)
+/// and fails if a directory can't be read
+///
+/// # Safety
+/// The caller is responsible for:
+/// 1. Ensuring dir_path is a valid null-terminated C string
+/// 2. Freeing the returned TestsmithResult using testsmith_result_free
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testsmith_plan_directory(dir_path: *const c_char) -> *mut TestsmithResult {
+    if dir_path.is_null() {
+        return Box::into_raw(Box::new(TestsmithResult::error("Invalid directory path")));
+    }
+    let dir = match unsafe { CStr::from_ptr(dir_path).to_str() } {
+        Ok(s) => Path::new(s),
+        Err(_) => return Box::into_raw(Box::new(TestsmithResult::error("Invalid directory path encoding"))),
+    };
+    if !dir.is_dir() {
+        return Box::into_raw(Box::new(TestsmithResult::error(&format!("Not a directory: {}", dir.display()))));
+    }
+
+    let result = match CacheStore::from_env().and_then(|store| plan_directory(&FileSystem::new_os(), dir, &store)) {
+        Ok(lines) => TestsmithResult::success(&lines.join("\n"), false, 0, "", false),
+        Err(e) => TestsmithResult::error(&format!("Error: {}", e)),
+    };
+
+    Box::into_raw(Box::new(result))
 }
 
+/// Clear the project cache
+///
+/// # Arguments
+/// * `project_root` - Null-terminated C string project root to forget, or null to clear the whole cache
+///
+/// # Returns
+/// TestsmithResult whose `created` flag is 1 if anything was removed
+///
+/// # Safety
+/// The caller is responsible for:
+/// 1. Ensuring project_root is null or a valid null-terminated C string
+/// 2. Freeing the returned TestsmithResult using testsmith_result_free
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testsmith_clear_cache(project_root: *const c_char) -> *mut TestsmithResult {
+    let project_root = if project_root.is_null() {
+        None
+    } else {
+        match unsafe { CStr::from_ptr(project_root).to_str() } {
+            Ok(s) => Some(Path::new(s)),
+            Err(_) => return Box::into_raw(Box::new(TestsmithResult::error("Invalid project root encoding"))),
+        }
+    };
+
+    let result = match cache::clear_cache(project_root) {
+        Ok(removed) => {
+            let message = if removed { "Cleared cache" } else { "Nothing to clear" };
+            TestsmithResult::success(message, removed, 0, "", false)
+        }
+        Err(e) => TestsmithResult::error(&format!("Error: {}", e)),
+    };
+
+    Box::into_raw(Box::new(result))
+}
 
 #[cfg(test)]
 mod tests {
@@ -163,7 +631,7 @@ mod tests {
 
     #[test]
     fn test_result_success() {
-        let result = TestsmithResult::success("test message", true, 9);
+        let result = TestsmithResult::success("test message", true, 9, "JUnit", false);
         assert_eq!(result.success, 1);
         assert_eq!(result.created, 1);
         assert_eq!(result.line_number, 9);
@@ -183,6 +651,8 @@ mod tests {
         assert_eq!(result.created, 0);
         assert_eq!(result.line_number, 0);
         assert!(!result.message.is_null());
+        assert!(result.framework.is_null());
+        assert!(result.preview_content.is_null());
 
         unsafe {
             let msg = CStr::from_ptr(result.message).to_str().unwrap();
@@ -190,4 +660,315 @@ mod tests {
             testsmith_result_free(Box::into_raw(Box::new(result)));
         }
     }
+
+    #[test]
+    fn test_result_success_found_existing() {
+        let result = TestsmithResult::success("FooTest.java", false, 7, "JUnit", true);
+        assert_eq!(result.found_existing, 1);
+        assert_eq!(result.created, 0);
+
+        unsafe {
+            let framework = CStr::from_ptr(result.framework).to_str().unwrap();
+            assert_eq!(framework, "JUnit");
+            testsmith_result_free(Box::into_raw(Box::new(result)));
+        }
+    }
+
+    #[test]
+    fn test_options_for_passes_force_through() {
+        let fs = FileSystem::new_memory();
+        let source = Path::new("/project/src/main/java/Foo.java");
+        assert!(!options_for(&fs, source, None, None, 1, 0, 0).force);
+        assert!(options_for(&fs, source, None, None, 1, 0, 1).force);
+    }
+
+    #[test]
+    fn test_plan_directory_lists_source_test_and_action() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().join("project");
+        std::fs::create_dir_all(&root).unwrap();
+        let root = root.canonicalize().unwrap();
+        std::fs::write(root.join("pom.xml"), "<project></project>\n").unwrap();
+        // src/main/java/com/example is five levels down, one more than the default scan depth
+        std::fs::write(root.join(".testsmithrc"), "{ \"max_scan_depth\": 5 }\n").unwrap();
+        let source_dir = root.join("src/main/java/com/example");
+        let test_dir = root.join("src/test/java/com/example");
+        std::fs::create_dir_all(&source_dir).unwrap();
+        std::fs::create_dir_all(&test_dir).unwrap();
+        std::fs::write(source_dir.join("Foo.java"), "package com.example;\n\npublic class Foo {}\n").unwrap();
+        std::fs::write(source_dir.join("Bar.java"), "package com.example;\n\npublic class Bar {}\n").unwrap();
+        std::fs::write(test_dir.join("BarTest.java"), "class BarTest {\n    @Test\n    void testBar() {}\n}\n").unwrap();
+
+        let store = CacheStore::new(temp_dir.path().join("data"));
+        let lines = plan_directory(&FileSystem::new_os(), &root, &store).unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                format!("{}\t{}\texists", source_dir.join("Bar.java").display(), test_dir.join("BarTest.java").display()),
+                format!("{}\t{}\tcreate", source_dir.join("Foo.java").display(), test_dir.join("FooTest.java").display()),
+            ]
+        );
+        assert!(!test_dir.join("FooTest.java").exists());
+        assert!(!store.cache_file_path().exists());
+    }
+
+    #[test]
+    fn test_plan_sources_stops_at_depth() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("/project/src/lib.rs"), "").unwrap();
+        fs.write_file_new(Path::new("/project/src/a/b/deep.rs"), "").unwrap();
+        fs.write_file_new(Path::new("/project/src/lib_test.rs"), "").unwrap();
+
+        let mut sources = Vec::new();
+        plan_sources(&fs, Path::new("/project"), 1, &mut sources).unwrap();
+        assert_eq!(sources, vec![PathBuf::from("/project/src/lib.rs")]);
+
+        let mut sources = Vec::new();
+        plan_sources(&fs, Path::new("/project"), 3, &mut sources).unwrap();
+        assert_eq!(sources, vec![PathBuf::from("/project/src/a/b/deep.rs"), PathBuf::from("/project/src/lib.rs")]);
+    }
+
+    #[test]
+    fn test_plan_sources_reports_unreadable_dir() {
+        let mut sources = Vec::new();
+        let result = plan_sources(&FileSystem::new_memory(), Path::new("/missing"), 4, &mut sources);
+        assert!(matches!(result, Err(TestsmithError::FileReadError { .. })));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plan_sources_skips_symlinked_dirs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "").unwrap();
+        // A link back to the root would recurse forever if followed
+        std::os::unix::fs::symlink(root, root.join("src/loop")).unwrap();
+
+        let mut sources = Vec::new();
+        plan_sources(&FileSystem::new_os(), root, 10, &mut sources).unwrap();
+        assert_eq!(sources, vec![root.join("src/lib.rs")]);
+    }
+
+    #[test]
+    fn test_batch_returns_result_per_path() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("src/main/java");
+        std::fs::create_dir_all(&source_dir).unwrap();
+        let foo = source_dir.join("Foo.java");
+        let bar = source_dir.join("Bar.java");
+        std::fs::write(&foo, "public class Foo {}\n").unwrap();
+        std::fs::write(&bar, "public class Bar {}\n").unwrap();
+
+        let missing = source_dir.join("Missing.java");
+        let sources = [Ok(foo.as_path()), Ok(missing.as_path()), Ok(bar.as_path()), Err("Invalid source path")];
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let store = CacheStore::new(cache_dir.path().to_path_buf());
+
+        let fs = FileSystem::new_os();
+        let options = |source: &Path| options_for(&fs, source, Some(StructureType::Maven), Some(Framework::JUnit), 1, 1, 0);
+        let results = generate_batch(&fs, Some(&store), &sources, options, true);
+        assert_eq!(results.len(), 4);
+
+        unsafe {
+            assert_eq!(results[0].success, 1);
+            assert!(CStr::from_ptr(results[0].message).to_str().unwrap().ends_with("FooTest.java"));
+            assert_eq!(results[1].success, 0);
+            assert_eq!(results[2].success, 1);
+            assert!(CStr::from_ptr(results[2].message).to_str().unwrap().ends_with("BarTest.java"));
+            assert_eq!(CStr::from_ptr(results[3].message).to_str().unwrap(), "Invalid source path");
+
+            for result in results {
+                testsmith_result_free(Box::into_raw(Box::new(result)));
+            }
+        }
+        // Dry runs leave the cache alone
+        assert!(!store.cache_file_path().exists());
+    }
+
+    #[test]
+    fn test_batch_detects_structure_per_project_in_mixed_tree() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        // A Maven module next to a Python package with unittest tests in tests/
+        let java_module = root.join("billing");
+        std::fs::create_dir_all(java_module.join("src/main/java/com/example")).unwrap();
+        std::fs::write(java_module.join("pom.xml"), "<project></project>\n").unwrap();
+        let invoice = java_module.join("src/main/java/com/example/Invoice.java");
+        std::fs::write(&invoice, "package com.example;\n\npublic class Invoice {}\n").unwrap();
+
+        let python_service = root.join("reports");
+        std::fs::create_dir_all(python_service.join("src")).unwrap();
+        std::fs::create_dir_all(python_service.join("tests")).unwrap();
+        std::fs::write(python_service.join("pyproject.toml"), "[project]\nname = \"reports\"\n").unwrap();
+        std::fs::write(python_service.join("tests/test_export.py"), "import unittest\n").unwrap();
+        let summary = python_service.join("src/summary.py");
+        std::fs::write(&summary, "def summarize(rows):\n    return rows\n").unwrap();
+
+        let sources = [Ok(invoice.as_path()), Ok(summary.as_path())];
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let store = CacheStore::new(cache_dir.path().to_path_buf());
+
+        let fs = FileSystem::new_os();
+        let results = generate_batch(&fs, Some(&store), &sources, |source| options_for(&fs, source, None, None, 1, 0, 0), false);
+
+        unsafe {
+
+            assert_eq!(results[0].success, 1);
+            assert_eq!(
+                Path::new(CStr::from_ptr(results[0].message).to_str().unwrap()),
+                java_module.join("src/test/java/com/example/InvoiceTest.java")
+            );
+            assert_eq!(CStr::from_ptr(results[0].framework).to_str().unwrap(), "JUnit");
+
+            assert_eq!(results[1].success, 1, "{}", CStr::from_ptr(results[1].message).to_str().unwrap());
+            assert_eq!(
+                Path::new(CStr::from_ptr(results[1].message).to_str().unwrap()),
+                python_service.join("tests/test_summary.py")
+            );
+            assert_eq!(CStr::from_ptr(results[1].framework).to_str().unwrap(), "Unittest");
+
+            for result in results {
+                testsmith_result_free(Box::into_raw(Box::new(result)));
+            }
+        }
+        // Both projects are remembered after one save
+        assert_eq!(store.load().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_ffi_result_carries_preview_content() {
+        let outcome = Ok(GeneratorResult {
+            test_file_path: "FooTest.java".to_string(),
+            created: true,
+            dry_run: true,
+            line_number: 7,
+            column_number: 4,
+            framework: "JUnit".to_string(),
+            structure: StructureType::Maven,
+            resolver_name: "Maven".to_string(),
+            decisions: Vec::new(),
+            warnings: Vec::new(),
+            has_only_stub: true,
+            candidate_test_paths: Vec::new(),
+            preview_content: Some("class FooTest {}\n".to_string()),
+        });
+
+        let result = to_ffi_result(outcome);
+        assert!(!result.preview_content.is_null());
+        assert!(result.warnings.is_null());
+        assert_eq!(result.has_only_stub, 1);
+        assert_eq!(result.column_number, 4);
+
+        unsafe {
+            let preview = CStr::from_ptr(result.preview_content).to_str().unwrap();
+            assert_eq!(preview, "class FooTest {}\n");
+            assert_eq!(CStr::from_ptr(result.resolver_name).to_str().unwrap(), "Maven");
+            testsmith_result_free(Box::into_raw(Box::new(result)));
+        }
+    }
+
+    #[test]
+    fn test_ffi_result_carries_warnings() {
+        let outcome = Ok(GeneratorResult {
+            test_file_path: "FooTest.java".to_string(),
+            created: false,
+            dry_run: false,
+            line_number: 1,
+            column_number: 0,
+            framework: "JUnit".to_string(),
+            structure: StructureType::Maven,
+            resolver_name: "Maven".to_string(),
+            decisions: Vec::new(),
+            warnings: vec!["first".to_string(), "second".to_string()],
+            has_only_stub: false,
+            candidate_test_paths: Vec::new(),
+            preview_content: None,
+        });
+
+        let result = to_ffi_result(outcome);
+        unsafe {
+            assert_eq!(CStr::from_ptr(result.warnings).to_str().unwrap(), "first\nsecond");
+            testsmith_result_free(Box::into_raw(Box::new(result)));
+        }
+    }
+
+    #[test]
+    fn test_verbose_returns_decision_log() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(root.join("pom.xml"), "<project></project>\n").unwrap();
+        let foo = root.join("src/main/java/Foo.java");
+        std::fs::create_dir_all(foo.parent().unwrap()).unwrap();
+        std::fs::write(&foo, "public class Foo {}\n").unwrap();
+
+        // A cached framework for the project, as an earlier run would have left it
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let store = CacheStore::new(cache_dir.path().to_path_buf());
+        let mut project_cache = store.load().unwrap();
+        cache::update_cache_entry(&mut project_cache, &root, "Java", &Framework::JUnit4, &StructureType::Maven).unwrap();
+        store.save(&project_cache).unwrap();
+
+        let path = CString::new(foo.to_str().unwrap()).unwrap();
+        let structure = CString::new("gradle").unwrap();
+        let framework = CString::new("auto").unwrap();
+        let mut log: *mut c_char = std::ptr::null_mut();
+
+        unsafe {
+            let outcome = find_or_create(Some(&store), path.as_ptr(), structure.as_ptr(), framework.as_ptr(), 1, 1, 0);
+            let result = with_decision_log(outcome, &mut log);
+            assert_eq!((*result).success, 1);
+            assert_eq!(CStr::from_ptr((*result).framework).to_str().unwrap(), "JUnit4");
+            assert!(!log.is_null());
+
+            let lines = CStr::from_ptr(log).to_str().unwrap();
+            assert!(lines.contains("\nFramework JUnit4: from cache"));
+            assert!(lines.contains("\nStructure Gradle: given explicitly"));
+
+            testsmith_string_free(log);
+            testsmith_result_free(result);
+        }
+    }
+
+    #[test]
+    fn test_verbose_decision_log_is_null_on_error() {
+        let path = CString::new("Foo.java").unwrap();
+        let structure = CString::new("bogus").unwrap();
+        let mut log: *mut c_char = CString::new("stale").unwrap().into_raw();
+        let stale = log;
+
+        unsafe {
+            let outcome = find_or_create(None, path.as_ptr(), structure.as_ptr(), std::ptr::null(), 0, 1, 0);
+            let result = with_decision_log(outcome, &mut log);
+            assert_eq!((*result).success, 0);
+            assert!(log.is_null());
+
+            testsmith_string_free(stale);
+            testsmith_result_free(result);
+        }
+    }
+
+    #[test]
+    fn test_batch_invalid_structure_reports_each_path() {
+        let path = CString::new("Foo.java").unwrap();
+        let path_ptrs = [path.as_ptr()];
+        let structure = CString::new("bogus").unwrap();
+
+        unsafe {
+            let batch = testsmith_find_or_create_batch(
+                path_ptrs.as_ptr(),
+                1,
+                structure.as_ptr(),
+                std::ptr::null(),
+                0,
+                1,
+                0,
+            );
+            let results = std::slice::from_raw_parts((*batch).results, (*batch).count);
+            assert_eq!(results[0].success, 0);
+            assert_eq!(CStr::from_ptr(results[0].message).to_str().unwrap(), "Invalid structure type");
+            testsmith_batch_free(batch);
+        }
+    }
 }
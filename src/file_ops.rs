@@ -1,7 +1,8 @@
 use crate::error::TestsmithError;
+use path_clean::PathClean;
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
 /// Abstraction over file system operations
@@ -13,6 +14,14 @@ pub enum FileSystemBackend {
     Memory(Mutex<MemoryFileSystem>),
 }
 
+/// What a directory entry is, without following symlinks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    File,
+    Dir,
+    Symlink,
+}
+
 /// Simple in-memory file system for testing
 #[derive(Default)]
 pub struct MemoryFileSystem {
@@ -26,8 +35,9 @@ impl MemoryFileSystem {
         }
     }
 
+    /// Key for a path, with `\` separators turned into `/` so lookups are separator-agnostic
     fn normalize_path(path: &Path) -> String {
-        path.to_string_lossy().to_string()
+        path.to_string_lossy().replace('\\', "/")
     }
 
     fn write_file(&mut self, path: &Path, content: &str) -> Result<(), String> {
@@ -49,11 +59,49 @@ impl MemoryFileSystem {
         self.files.contains_key(&path_str)
     }
 
+    /// A file, or a directory holding at least one file
+    fn path_exists(&self, path: &Path) -> bool {
+        let path_str = Self::normalize_path(path);
+        let prefix = format!("{}/", path_str.trim_end_matches('/'));
+        self.files.contains_key(&path_str) || self.files.keys().any(|key| key.starts_with(&prefix))
+    }
+
+    /// Files directly in `path`, and directories holding files further down
+    fn read_dir(&self, path: &Path) -> Result<Vec<(PathBuf, EntryKind)>, String> {
+        let dir_str = Self::normalize_path(path);
+        let prefix = format!("{}/", dir_str.trim_end_matches('/'));
+        let mut entries: Vec<(PathBuf, EntryKind)> = self
+            .files
+            .keys()
+            .filter_map(|key| key.strip_prefix(&prefix))
+            .map(|rest| match rest.split_once('/') {
+                Some((dir, _)) => (PathBuf::from(format!("{}{}", prefix, dir)), EntryKind::Dir),
+                None => (PathBuf::from(format!("{}{}", prefix, rest)), EntryKind::File),
+            })
+            .collect();
+        if entries.is_empty() {
+            return Err(format!("Directory not found: {}", dir_str));
+        }
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries.dedup();
+        Ok(entries)
+    }
+
     fn append_to_file(&mut self, path: &Path, content: &str) -> Result<(), String> {
         let path_str = Self::normalize_path(path);
         if let Some(existing) = self.files.get_mut(&path_str) {
             existing.push('\n');
             existing.push_str(content);
+        } else {
+            self.files.insert(path_str, content.to_string());
+        }
+        Ok(())
+    }
+
+    fn insert_at_line(&mut self, path: &Path, line: usize, content: &str) -> Result<(), String> {
+        let path_str = Self::normalize_path(path);
+        if let Some(existing) = self.files.get_mut(&path_str) {
+            *existing = splice_at_line(existing, line, content);
         } else {
             return Err(format!("File not found: {}", path_str));
         }
@@ -61,6 +109,24 @@ impl MemoryFileSystem {
     }
 }
 
+/// Insert `content` so its first line becomes `line` (1-based) of `existing`
+/// Lines past the end of the file append instead
+fn splice_at_line(existing: &str, line: usize, content: &str) -> String {
+    let lines: Vec<&str> = existing.split_inclusive('\n').collect();
+    let at = line.saturating_sub(1).min(lines.len());
+
+    let mut result: String = lines[..at].concat();
+    if !result.is_empty() && !result.ends_with('\n') {
+        result.push('\n');
+    }
+    result.push_str(content);
+    if !content.ends_with('\n') && at < lines.len() {
+        result.push('\n');
+    }
+    result.push_str(&lines[at..].concat());
+    result
+}
+
 /// Wrapper around file system operations
 pub struct FileSystem {
     backend: FileSystemBackend,
@@ -85,15 +151,16 @@ impl FileSystem {
     pub fn create_parent_directories(&self, path: &Path) -> Result<(), TestsmithError> {
         match &self.backend {
             FileSystemBackend::Os => {
-                if let Some(parent) = path.parent() {
-                    if !parent.as_os_str().is_empty() && !parent.exists() {
-                        fs::create_dir_all(parent).map_err(|e| {
-                            TestsmithError::DirectoryCreateError {
-                                path: parent.to_path_buf(),
-                                source: e,
-                            }
-                        })?;
-                    }
+                if let Some(parent) = path.parent()
+                    && !parent.as_os_str().is_empty()
+                    && !parent.exists()
+                {
+                    fs::create_dir_all(parent).map_err(|e| {
+                        TestsmithError::DirectoryCreateError {
+                            path: parent.to_path_buf(),
+                            source: e,
+                        }
+                    })?;
                 }
                 Ok(())
             }
@@ -114,6 +181,41 @@ impl FileSystem {
         }
     }
 
+    /// Check if a file or directory exists
+    pub fn path_exists(&self, path: &Path) -> bool {
+        match &self.backend {
+            FileSystemBackend::Os => path.exists(),
+            FileSystemBackend::Memory(mem_fs) => mem_fs.lock().unwrap().path_exists(path),
+        }
+    }
+
+    /// Absolute form of an existing path with symlinks resolved
+    /// (the in-memory filesystem has no symlinks, so only `.` and `..` are resolved)
+    pub fn canonicalize(&self, path: &Path) -> Result<PathBuf, TestsmithError> {
+        match &self.backend {
+            FileSystemBackend::Os => fs::canonicalize(path).map_err(|e| TestsmithError::FileReadError {
+                path: path.to_path_buf(),
+                source: e,
+            }),
+            FileSystemBackend::Memory(_) => {
+                let absolute = self.current_dir()?.join(path).clean();
+                if self.path_exists(&absolute) {
+                    Ok(absolute)
+                } else {
+                    Err(TestsmithError::FileNotFound { path: path.to_path_buf() })
+                }
+            }
+        }
+    }
+
+    /// Directory relative paths are resolved against (`/` for the in-memory filesystem)
+    pub fn current_dir(&self) -> Result<PathBuf, TestsmithError> {
+        match &self.backend {
+            FileSystemBackend::Os => Ok(std::env::current_dir()?),
+            FileSystemBackend::Memory(_) => Ok(PathBuf::from("/")),
+        }
+    }
+
     /// Read a file to string
     pub fn read_file(&self, path: &Path) -> Result<String, TestsmithError> {
         match &self.backend {
@@ -136,6 +238,38 @@ impl FileSystem {
         }
     }
 
+    /// Entries of a directory sorted by path, with symlinks reported as such rather than followed
+    pub fn read_dir(&self, path: &Path) -> Result<Vec<(PathBuf, EntryKind)>, TestsmithError> {
+        let read_error = |e| TestsmithError::FileReadError {
+            path: path.to_path_buf(),
+            source: e,
+        };
+        match &self.backend {
+            FileSystemBackend::Os => {
+                let mut entries = Vec::new();
+                for entry in fs::read_dir(path).map_err(read_error)? {
+                    let entry = entry.map_err(read_error)?;
+                    let file_type = entry.file_type().map_err(read_error)?;
+                    let kind = if file_type.is_symlink() {
+                        EntryKind::Symlink
+                    } else if file_type.is_dir() {
+                        EntryKind::Dir
+                    } else {
+                        EntryKind::File
+                    };
+                    entries.push((entry.path(), kind));
+                }
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+                Ok(entries)
+            }
+            FileSystemBackend::Memory(mem_fs) => mem_fs
+                .lock()
+                .unwrap()
+                .read_dir(path)
+                .map_err(|e| read_error(std::io::Error::new(std::io::ErrorKind::NotFound, e))),
+        }
+    }
+
     /// Write content to a file (creates new or overwrites existing)
     pub fn write_file_new(&self, path: &Path, content: &str) -> Result<(), TestsmithError> {
         // Ensure parent directories exist
@@ -155,20 +289,23 @@ impl FileSystem {
                     .write_file(path, content)
                     .map_err(|e| TestsmithError::FileWriteError {
                         path: path.to_path_buf(),
-                        source: std::io::Error::new(std::io::ErrorKind::Other, e),
+                        source: std::io::Error::other(e),
                     })
             }
         }
     }
 
-    /// Append content to a file
+    /// Append content to a file, creating it (and its parent directories) when missing
     pub fn append_to_file(&self, path: &Path, content: &str) -> Result<(), TestsmithError> {
         match &self.backend {
             FileSystemBackend::Os => {
                 use std::fs::OpenOptions;
                 use std::io::Write;
 
+                // The file can vanish between generation and writing (a race, a dangling symlink)
+                self.create_parent_directories(path)?;
                 let mut file = OpenOptions::new()
+                    .create(true)
                     .append(true)
                     .open(path)
                     .map_err(|e| TestsmithError::FileWriteError {
@@ -188,7 +325,32 @@ impl FileSystem {
                     .append_to_file(path, content)
                     .map_err(|e| TestsmithError::FileWriteError {
                         path: path.to_path_buf(),
-                        source: std::io::Error::new(std::io::ErrorKind::Other, e),
+                        source: std::io::Error::other(e),
+                    })
+            }
+        }
+    }
+
+    /// Insert content into an existing file so it starts at the given 1-based line
+    pub fn insert_at_line(&self, path: &Path, line: usize, content: &str) -> Result<(), TestsmithError> {
+        match &self.backend {
+            FileSystemBackend::Os => {
+                let existing = self.read_file(path)?;
+                fs::write(path, splice_at_line(&existing, line, content)).map_err(|e| {
+                    TestsmithError::FileWriteError {
+                        path: path.to_path_buf(),
+                        source: e,
+                    }
+                })
+            }
+            FileSystemBackend::Memory(mem_fs) => {
+                mem_fs
+                    .lock()
+                    .unwrap()
+                    .insert_at_line(path, line, content)
+                    .map_err(|e| TestsmithError::FileWriteError {
+                        path: path.to_path_buf(),
+                        source: std::io::Error::other(e),
                     })
             }
         }
@@ -221,6 +383,44 @@ mod tests {
         assert!(fs.file_exists(&file_path));
     }
 
+    #[test]
+    fn test_memory_paths_are_separator_agnostic() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("C:\\project\\src\\Foo.java"), "class Foo {}").unwrap();
+
+        assert!(fs.file_exists(Path::new("C:/project/src/Foo.java")));
+        assert_eq!(fs.read_file(Path::new("C:/project/src/Foo.java")).unwrap(), "class Foo {}");
+    }
+
+    #[test]
+    fn test_read_dir_lists_files_and_subdirectories() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("/project/src/b.rs"), "").unwrap();
+        fs.write_file_new(Path::new("/project/src/a.rs"), "").unwrap();
+        fs.write_file_new(Path::new("/project/src/nested/c.rs"), "").unwrap();
+        fs.write_file_new(Path::new("/project/src/nested/d.rs"), "").unwrap();
+
+        assert_eq!(
+            fs.read_dir(Path::new("/project/src")).unwrap(),
+            vec![
+                (PathBuf::from("/project/src/a.rs"), EntryKind::File),
+                (PathBuf::from("/project/src/b.rs"), EntryKind::File),
+                (PathBuf::from("/project/src/nested"), EntryKind::Dir),
+            ]
+        );
+        assert!(fs.read_dir(Path::new("/project/missing")).is_err());
+    }
+
+    #[test]
+    fn test_memory_canonicalize_resolves_dot_dot_of_existing_paths() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("/project/src/lib.rs"), "").unwrap();
+
+        assert_eq!(fs.canonicalize(Path::new("/project/tests/../src")).unwrap(), PathBuf::from("/project/src"));
+        assert_eq!(fs.canonicalize(Path::new("project/src/lib.rs")).unwrap(), PathBuf::from("/project/src/lib.rs"));
+        assert!(fs.canonicalize(Path::new("/project/tests")).is_err());
+    }
+
     #[test]
     fn test_file_exists_false() {
         let fs = FileSystem::new_memory();
@@ -264,4 +464,50 @@ mod tests {
         assert!(content.contains("line 1"));
         assert!(content.contains("line 2"));
     }
+
+    #[test]
+    fn test_append_to_missing_file_creates_it() {
+        let fs = FileSystem::new_memory();
+        let file_path = PathBuf::from("/missing.txt");
+
+        fs.append_to_file(&file_path, "line 1").unwrap();
+
+        assert_eq!(fs.read_file(&file_path).unwrap(), "line 1");
+    }
+
+    #[test]
+    fn test_append_to_missing_file_creates_it_on_disk() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("src").join("missing.rs");
+        let fs = FileSystem::new_os();
+
+        fs.append_to_file(&file_path, "line 1").unwrap();
+        fs.append_to_file(&file_path, "line 2").unwrap();
+
+        assert_eq!(fs.read_file(&file_path).unwrap(), "line 1\nline 2\n");
+    }
+
+    #[test]
+    fn test_insert_at_line_mid_file() {
+        let fs = FileSystem::new_memory();
+        let file_path = PathBuf::from("/test.txt");
+
+        fs.write_file_new(&file_path, "line 1\nline 2\nline 3\n").unwrap();
+        fs.insert_at_line(&file_path, 2, "inserted\n").unwrap();
+
+        let content = fs.read_file(&file_path).unwrap();
+        assert_eq!(content, "line 1\ninserted\nline 2\nline 3\n");
+    }
+
+    #[test]
+    fn test_insert_at_line_past_end_appends() {
+        let fs = FileSystem::new_memory();
+        let file_path = PathBuf::from("/test.txt");
+
+        fs.write_file_new(&file_path, "line 1\nline 2").unwrap();
+        fs.insert_at_line(&file_path, 10, "inserted").unwrap();
+
+        let content = fs.read_file(&file_path).unwrap();
+        assert_eq!(content, "line 1\nline 2\ninserted");
+    }
 }
@@ -1,15 +1,33 @@
-use crate::cache;
-use crate::cli::{Framework, Language, StructureType};
-use crate::config::{framework as config_framework, language as config_language, framework_detector, project_root as config_project_root, structure_detector};
+use crate::cache::{self, CacheStore, ProjectCache};
+use crate::cli::{value_name, AppendPosition, OnExists, Framework, Language, MethodNaming, StructureType, TestKind};
+use crate::config::defaults::Defaults;
+use crate::config::naming::NamingConvention;
+use crate::config::{build_tool, cargo_workspace, defaults, editorconfig, framework as config_framework, language as config_language, framework_detector::{self, PropertyLibrary}, package_import, project_root as config_project_root, structure_detector, testsmithrc};
 use crate::error::TestsmithError;
-use crate::file_ops::FileSystem;
-use crate::resolver::maven::MavenResolver;
+use crate::file_ops::{EntryKind, FileSystem};
+use crate::resolver::adjacent::{self, AdjacentResolver};
+use crate::resolver::flat::FlatResolver;
+use crate::resolver::maven::{self, MavenResolver};
 use crate::resolver::same_file::SameFileResolver;
+use crate::resolver::test_crate::TestCrateResolver;
 use crate::resolver::traits::StructureResolver;
+use crate::template::elixir_exunit::ElixirExUnitTemplate;
+use crate::template::go_testing::GoTestingTemplate;
+use crate::template::haskell_hspec::HaskellHSpecTemplate;
 use crate::template::java_junit::JavaJunitTemplate;
+use crate::template::js_index;
+use crate::template::kotlin_junit::KotlinJunitTemplate;
+use crate::template::method_stub;
+use crate::template::php_phpunit::{self, PhpUnitTemplate};
+use crate::template::python_unittest::PythonUnittestTemplate;
 use crate::template::registry::TemplateRegistry;
+use crate::template::scala_scalatest::ScalaTestTemplate;
 use crate::template::traits::TemplateContext;
-use std::path::Path;
+use path_clean::PathClean;
+use regex::Regex;
+use serde::Serialize;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 pub struct GeneratorOptions {
     pub structure: StructureType,
@@ -17,25 +35,182 @@ pub struct GeneratorOptions {
     pub framework: Option<Framework>,
     pub create: bool,
     pub dry_run: bool,
+    /// Pin the resolved framework/structure in the cache so later runs never re-detect them
+    pub pin: bool,
+    /// Ignore the project's cached framework/structure, detect them afresh and overwrite the entry
+    pub refresh: bool,
+    /// Generate one test stub per non-private source method instead of a single example test
+    pub method_stubs: bool,
+    /// Naming scheme for per-method test stubs
+    pub method_naming: MethodNaming,
+    /// Example-based or property-based test stub
+    pub test_kind: TestKind,
+    /// Prefix for generated test function names (None = idiomatic default for the framework)
+    pub method_prefix: Option<String>,
+    /// Test file naming override (falls back to `.testsmithrc`, then the `Test` suffix)
+    pub naming: Option<NamingConvention>,
+    /// Place the test in this directory (plus the Java package path) instead of the resolved one
+    pub test_dir: Option<PathBuf>,
+    /// Create the test file's parent directories even on dry-run (the file itself is not written)
+    pub prepare_dirs: bool,
+    /// Allow a resolved test path outside the detected project root
+    pub force: bool,
+    /// Same-file structure only: insert the test module at this 1-based line instead of appending
+    pub insert_at_line: Option<usize>,
+    /// What to do when the test class or test module already exists: return its path, add a test
+    /// to it (Java test classes and Rust test modules only) or fail
+    pub on_exists: OnExists,
+    /// Whether --append puts the new Rust test first or last in the module
+    pub append_position: AppendPosition,
+    /// List the source's public methods as `// TODO: test foo(int)` comments at the top of the new test
+    pub document_api: bool,
+    /// Text of the generated TODO comment (falls back to `.testsmithrc`, then `TODO: Implement test`)
+    pub todo_text: Option<String>,
+    /// Make new tests fail until implemented (`fail("not implemented")`) instead of passing empty
+    /// (also enabled by `.testsmithrc`)
+    pub failing_stub: bool,
+    /// Generate a JUnit 5 `@ParameterizedTest` with a `@ValueSource` instead of a plain `@Test`
+    pub parameterized: bool,
+    /// Android projects only: put the test in `src/androidTest` (instrumented) instead of `src/test`
+    pub android_instrumented: bool,
+    /// Wrap per-method stubs in `// region` / `// endregion` fold markers (also enabled by `.testsmithrc`)
+    pub section_markers: bool,
+    /// Add what the framework needs to run the tests: the `__main__` guard for Python unittest,
+    /// an `index.test.js` loading every test for JavaScript/TypeScript in `__tests__/`
+    pub with_runner: bool,
+    /// User-wide defaults (`defaults.toml`): the structure for each language
+    pub defaults: Defaults,
 }
 
+impl Default for GeneratorOptions {
+    /// The CLI's defaults: detect the structure and framework, create the test if it's missing
+    fn default() -> Self {
+        GeneratorOptions {
+            // Maven is the auto-detect sentinel (see `generate_with_cache`)
+            structure: StructureType::Maven,
+            language: None,
+            framework: None,
+            create: true,
+            dry_run: false,
+            pin: false,
+            refresh: false,
+            method_stubs: false,
+            method_naming: MethodNaming::default(),
+            test_kind: TestKind::default(),
+            method_prefix: None,
+            naming: None,
+            test_dir: None,
+            prepare_dirs: false,
+            force: false,
+            insert_at_line: None,
+            on_exists: OnExists::default(),
+            append_position: AppendPosition::default(),
+            document_api: false,
+            todo_text: None,
+            failing_stub: false,
+            parameterized: false,
+            android_instrumented: false,
+            section_markers: false,
+            with_runner: false,
+            defaults: Defaults::default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
 pub struct GeneratorResult {
     pub test_file_path: String,
     pub created: bool,
     pub dry_run: bool,
     pub line_number: i32,
+    /// 0-based byte column of the TODO comment on `line_number`, so the cursor lands on the comment
+    /// (tabs count as one byte each)
+    pub column_number: i32,
+    /// Framework the test was generated for or found with (e.g. "JUnit")
+    pub framework: String,
+    /// Structure the test path was resolved with, after auto-detection (serialized as "same-file")
+    #[serde(serialize_with = "serialize_structure")]
+    pub structure: StructureType,
+    /// Name of the resolver that placed the test (e.g. "Same File")
+    pub resolver_name: String,
+    /// Why the framework, structure and naming were chosen, one line per decision
+    #[serde(skip)]
+    pub decisions: Vec<String>,
+    /// Problems worth showing the user even without the decision log
+    /// (e.g. a structure that disagrees with where the source's test already lives)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Whether the test holds nothing but a TODO stub (no assertions written yet)
+    pub has_only_stub: bool,
+    /// Every location the structure accepts for this test, most preferred first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub candidate_test_paths: Vec<String>,
+    /// On a dry run that would create or add to a test, exactly what would be written
+    /// (the whole test file when appending to an existing one)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview_content: Option<String>,
+}
+
+/// Serialize a structure by its `--structure` name
+fn serialize_structure<S: serde::Serializer>(structure: &StructureType, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value_name(structure))
 }
 
 /// Generate or find test files based on source files
+/// Loads the user's project cache, and saves it afterwards unless this is a dry run
 pub fn generate(
     fs: &FileSystem,
     source_path: &Path,
     options: GeneratorOptions,
 ) -> Result<GeneratorResult, TestsmithError> {
-    // Note: We don't validate source file existence here because:
-    // 1. For OS filesystem, the resolver will handle path validation
-    // 2. For memory filesystem, the file must be created by the test
-    // The actual validation happens during resolver.resolve_test_path()
+    // The cache is optional: without a data directory nothing is remembered
+    generate_with_store(fs, source_path, options, CacheStore::from_env().ok().as_ref())
+}
+
+/// Generate or find test files with the project cache kept in `store` (None = no cache)
+/// The cache is saved afterwards unless this is a dry run
+pub fn generate_with_store(
+    fs: &FileSystem,
+    source_path: &Path,
+    options: GeneratorOptions,
+    store: Option<&CacheStore>,
+) -> Result<GeneratorResult, TestsmithError> {
+    // Load cache (don't fail if unavailable - it's optional)
+    let original_cache = store.and_then(|store| store.load().ok()).unwrap_or_default();
+    let mut cache = original_cache.clone();
+    cache::prune_cache(&mut cache, cache::CACHE_MAX_AGE_SECS);
+    let dry_run = options.dry_run;
+
+    let result = generate_with_cache(fs, source_path, options, &mut cache);
+
+    if let Some(store) = store
+        && !dry_run
+        && cache != original_cache
+    {
+        let _ = store.save(&cache);
+    }
+
+    result
+}
+
+/// Generate or find test files using an already-loaded cache
+/// The cache is updated in memory only; the caller decides when to load and save it,
+/// so many generations can share a single `CacheStore::load` / `CacheStore::save`
+pub fn generate_with_cache(
+    fs: &FileSystem,
+    source_path: &Path,
+    options: GeneratorOptions,
+    cache: &mut ProjectCache,
+) -> Result<GeneratorResult, TestsmithError> {
+    // Collapse redundant `.`/`..` once, so detection, resolution and cache keys all see one path
+    let source_path = &source_path.clean();
+
+    // A missing source is reported as such, before language detection can call it unsupported
+    if !fs.file_exists(source_path) {
+        return Err(TestsmithError::FileNotFound {
+            path: source_path.to_path_buf(),
+        });
+    }
 
     // Detect language if not provided
     let language = if let Some(lang) = options.language {
@@ -43,40 +218,133 @@ pub fn generate(
     } else {
         config_language::detect_language(source_path)?
     };
-
-    // Load cache (don't fail if unavailable - it's optional)
-    let mut cache = cache::load_cache().unwrap_or_default();
+    config_language::check_source_file(source_path, language)?;
 
     // Find project root (language-specific)
     let project_root = config_project_root::find_project_root(source_path, language);
-    let language_str = format!("{:?}", language);
+    let mut decisions = Vec::new();
+
+    match project_root {
+        Some(ref root) => decisions.push(format!("Project root: {}", root.display())),
+        None => decisions.push(format!("Project root: none found for {:?}", language)),
+    }
+
+    // The build tool owning the source's module drives both framework and structure detection
+    let module_root = framework_detector::find_module_root(source_path);
+    if let Some((tool, file)) = module_root.as_deref().and_then(build_tool::detect_build_tool_file) {
+        decisions.push(format!("Build tool: {:?} ({})", tool, file));
+    }
+
+    // A build module nested below the project root (a Gradle subproject under a root
+    // .testsmithrc) may use another framework, so it gets its own cache entry
+    let module_dir = project_root.as_ref().and_then(|root| {
+        module_root
+            .as_ref()
+            .and_then(|dir| dir.canonicalize().ok())
+            .and_then(|dir| dir.strip_prefix(root).ok().map(Path::to_path_buf))
+            .filter(|dir| !dir.as_os_str().is_empty())
+    });
+    if let Some(ref module) = module_dir {
+        decisions.push(format!("Build module: {}", module.display()));
+    }
+    let cache_key = cache::entry_key(&format!("{:?}", language), module_dir.as_deref());
+
+    // --refresh drops the entry (even a pinned one) so both lookups below fall through to detection
+    if options.refresh
+        && let Some(ref root) = project_root
+        && cache::remove_cache_entry(cache, root, &cache_key)
+    {
+        decisions.push("Cached framework/structure ignored (--refresh)".to_string());
+    }
+
+    // Per-project settings from .testsmithrc
+    let rc = match project_root {
+        Some(ref root) => testsmithrc::load_rc(root)?,
+        None => None,
+    };
+    let rc_structure = match rc {
+        Some(ref rc) => rc.structure()?,
+        None => None,
+    };
+    let rc_framework = match rc {
+        Some(ref rc) => rc.framework_for(source_path, language)?,
+        None => None,
+    };
+    let max_scan_depth = testsmithrc::max_scan_depth(rc.as_ref())?;
+
+    // Every cursor search looks for this text (see `find_cursor_line`)
+    let todo_text = options
+        .todo_text
+        .clone()
+        .or_else(|| rc.as_ref().and_then(|rc| rc.todo_text.clone()))
+        .unwrap_or_else(|| method_stub::DEFAULT_TODO_TEXT.to_string());
+    if todo_text.trim().is_empty() || todo_text.contains('\n') {
+        return Err(TestsmithError::ConfigError {
+            reason: "TODO text must be a single, non-empty line".to_string(),
+        });
+    }
 
     // Determine framework
     let framework = if let Some(fw) = options.framework {
         // Explicit framework provided - use it
         config_framework::validate_combination(language, fw)?;
+        decisions.push(format!("Framework {:?}: given explicitly", fw));
+        fw
+    } else if let Some(fw) = rc_framework {
+        // A framework configured in .testsmithrc beats the cache and detection
+        config_framework::validate_combination(language, fw)?;
+        decisions.push(format!("Framework {:?}: from .testsmithrc", fw));
         fw
     } else {
         // Try to use cache if we have a project root
         let mut cached_framework = None;
 
-        if let Some(ref root) = project_root {
-            if let Some(cached_entry) = cache::get_cache_entry(&cache, root, &language_str) {
-                let config_files = config_project_root::config_files_for_language(language);
-
-                // Check if cache is stale
-                if !cache::is_cache_stale(root, cached_entry.last_used, &config_files) {
-                    // Cache is valid, parse the framework string
-                    cached_framework = match cached_entry.framework.as_str() {
-                        "JUnit" => Some(Framework::JUnit),
-                        "JUnit4" => Some(Framework::JUnit4),
-                        "TestNG" => Some(Framework::TestNG),
-                        "Native" => Some(Framework::Native),
-                        "Jest" => Some(Framework::Jest),
-                        "Pytest" => Some(Framework::Pytest),
-                        _ => None,
-                    };
-                }
+        if let Some(ref root) = project_root
+            && let Some(cached_entry) = cache::get_cache_entry(cache, root, &cache_key)
+        {
+            let config_files = config_project_root::config_files_for_language(language);
+
+            // Check if cache is stale (pinned entries never are)
+            let config_dir = module_dir.as_ref().map_or_else(|| root.clone(), |module| root.join(module));
+            if cached_entry.pinned || !cache::is_cache_stale(&config_dir, cached_entry.last_used, &config_files) {
+                decisions.push(format!(
+                    "Framework {}: from cache{}",
+                    cached_entry.framework,
+                    if cached_entry.pinned { " (pinned)" } else { "" }
+                ));
+                decisions.extend(cached_entry.version_note());
+                // Cache is valid, parse the framework string
+                cached_framework = match cached_entry.framework.as_str() {
+                    "JUnit" => Some(Framework::JUnit),
+                    "JUnit4" => Some(Framework::JUnit4),
+                    "TestNG" => Some(Framework::TestNG),
+                    "Native" => Some(Framework::Native),
+                    "Rstest" => Some(Framework::Rstest),
+                    "Proptest" => Some(Framework::Proptest),
+                    "Jest" => Some(Framework::Jest),
+                    "Pytest" => Some(Framework::Pytest),
+                    "Unittest" => Some(Framework::Unittest),
+                    "Vitest" => Some(Framework::Vitest),
+                    "GoogleTest" => Some(Framework::GoogleTest),
+                    "GoTest" => Some(Framework::GoTest),
+                    "ExUnit" => Some(Framework::ExUnit),
+                    "PHPUnit" => Some(Framework::PHPUnit),
+                    "ScalaTest" => Some(Framework::ScalaTest),
+                    "MUnit" => Some(Framework::MUnit),
+                    "XUnit" => Some(Framework::XUnit),
+                    "Jasmine" => Some(Framework::Jasmine),
+                    "Mocha" => Some(Framework::Mocha),
+                    "DartTest" => Some(Framework::DartTest),
+                    "RSpec" => Some(Framework::RSpec),
+                    "HSpec" => Some(Framework::HSpec),
+                    "XCTest" => Some(Framework::XCTest),
+                    _ => None,
+                };
+            } else {
+                decisions.push(format!(
+                    "Cached framework {} is stale (project config changed), detecting again",
+                    cached_entry.framework
+                ));
             }
         }
 
@@ -86,59 +354,225 @@ pub fn generate(
             fw
         } else {
             // Try to auto-detect framework from project config files
-            let detected = framework_detector::detect_framework(source_path, language)?;
+            let detected = framework_detector::detect_framework(source_path, language, max_scan_depth)?;
 
             if let Some(fw) = detected {
                 // Validate the detected combination
                 config_framework::validate_combination(language, fw)?;
+                decisions.push(format!("Framework {:?}: detected from project config files", fw));
                 fw
             } else {
                 // Fall back to default framework for language
-                config_language::default_framework_for_language(language)
+                let fw = config_language::default_framework_for_language(language);
+                decisions.push(format!("Framework {:?}: nothing detected, using the {:?} default", fw, language));
+                fw
             }
         }
     };
 
+    // Detection looks at where this source's own test already lives before the project layout;
+    // the existing test is a per-file fact, so only the project layout is ever cached
+    let detected_structure = project_root
+        .as_ref()
+        .and_then(|root| structure_detector::detect_structure(root, source_path, language, max_scan_depth).ok());
+    let existing_test_structure = detected_structure
+        .filter(|detected| detected.from_existing_test)
+        .map(|detected| detected.structure);
+    let cached_structure = project_root
+        .as_ref()
+        .and_then(|root| cache::get_cache_entry(cache, root, &cache_key))
+        .map(|entry| (parse_cached_structure(&entry.structure).unwrap_or(options.structure), entry.pinned));
+
     // Determine structure
+    let mut structure_from_existing_test = false;
     let structure = if options.structure == StructureType::Maven {
         // If explicitly provided (Maven is default), check if we should auto-detect instead
-        if let Some(ref root) = project_root {
-            if let Some(cached_entry) = cache::get_cache_entry(&cache, root, &language_str) {
-                // Parse the cached structure
-                match cached_entry.structure.as_str() {
-                    "Maven" => StructureType::Maven,
-                    "Gradle" => StructureType::Gradle,
-                    "SameFile" => StructureType::SameFile,
-                    "Flat" => StructureType::Flat,
-                    _ => options.structure,
-                }
-            } else {
-                // Not in cache, try to auto-detect
-                structure_detector::detect_structure(root, language).unwrap_or(options.structure)
-            }
+        if let Some(structure) = rc_structure {
+            // A structure configured in .testsmithrc beats the cache and detection
+            decisions.push(format!("Structure {:?}: from .testsmithrc", structure));
+            structure
+        } else if let Some(structure) = options.defaults.structure_for(language) {
+            // Then the user's default for the language, so detection never second-guesses it
+            decisions.push(format!("Structure {:?}: from {}", structure, defaults::DEFAULTS_FILE_NAME));
+            structure
+        } else if let Some((structure, true)) = cached_structure {
+            // Then a pinned structure, which even this source's existing test doesn't override
+            decisions.push(format!("Structure {:?}: from cache (pinned)", structure));
+            structure
+        } else if let Some(structure) = existing_test_structure {
+            // Then wherever this source's test already is, which the project-wide cache can't know
+            decisions.push(format!("Structure {:?}: follows this source's existing test", structure));
+            structure_from_existing_test = true;
+            structure
+        } else if let Some((structure, _)) = cached_structure {
+            decisions.push(format!("Structure {:?}: from cache", structure));
+            structure
+        } else if project_root.is_some() {
+            let structure = detected_structure.map_or(options.structure, |detected| detected.structure);
+            decisions.push(format!("Structure {:?}: detected from the project layout", structure));
+            structure
         } else {
+            decisions.push(format!("Structure {:?}: default (no project root)", options.structure));
             options.structure
         }
     } else {
         // Non-Maven structure explicitly specified
+        decisions.push(format!("Structure {:?}: given explicitly", options.structure));
         options.structure
     };
 
-    // Update cache with current values
+    // Warn when the chosen structure disagrees with where this source's test already lives
+    let mut warnings = Vec::new();
+    if structure != StructureType::TestCrate
+        && let Some(existing) = existing_test_structure
+        && !matches!((existing, structure), (StructureType::Maven, StructureType::Gradle))
+        && existing != structure
+    {
+        warnings.push(format!(
+            "Structure {:?} was chosen, but the existing test for this source suggests {:?}",
+            structure, existing
+        ));
+    }
+
+    // Update cache with current values, keeping the project-wide structure when this one came from a single file
     if let Some(ref root) = project_root {
-        let _ = cache::update_cache_entry(&mut cache, root, &language_str, &framework, &structure);
-        let _ = cache::save_cache(&cache);
+        let cache_structure = if structure_from_existing_test {
+            cached_structure.map(|(structure, _)| structure).unwrap_or_else(|| {
+                structure_detector::detect_project_structure(root, language, max_scan_depth).unwrap_or(options.structure)
+            })
+        } else {
+            structure
+        };
+        let _ = cache::update_cache_entry(cache, root, &cache_key, &framework, &cache_structure);
+        if options.pin {
+            cache::pin_cache_entry(cache, root, &cache_key);
+        }
     }
 
+    // Determine test file naming: explicit override > .testsmithrc > existing tests > resolver default
+    let naming = match options.naming {
+        Some(naming) => {
+            decisions.push("Naming: given explicitly".to_string());
+            Some(naming)
+        }
+        None => match rc.as_ref().and_then(|rc| rc.naming.clone()) {
+            Some(naming) => {
+                decisions.push("Naming: from .testsmithrc".to_string());
+                Some(naming)
+            }
+            None => {
+                let detected = project_root
+                    .as_ref()
+                    .and_then(|root| structure_detector::detect_test_naming(root, language, max_scan_depth));
+                if detected.is_some() {
+                    decisions.push("Naming: follows existing test files".to_string());
+                }
+                detected
+            }
+        },
+    };
+
+    // Android modules keep instrumented tests in their own source set
+    let test_source_set = if options.android_instrumented {
+        let android = matches!(structure, StructureType::Maven | StructureType::Gradle)
+            && framework_detector::find_module_root(source_path).is_some_and(|module| framework_detector::is_android_module(&module));
+        if !android {
+            return Err(TestsmithError::ConfigError {
+                reason: "--android-instrumented needs a Gradle module applying a com.android plugin".to_string(),
+            });
+        }
+        decisions.push("Source set: androidTest (Android instrumented test)".to_string());
+        maven::ANDROID_INSTRUMENTED_SOURCE_SET
+    } else {
+        maven::UNIT_TEST_SOURCE_SET
+    };
+
     // Get the appropriate resolver
     let resolver: Box<dyn StructureResolver> = match structure {
-        StructureType::Maven | StructureType::Gradle => Box::new(MavenResolver::new()),
+        StructureType::Maven | StructureType::Gradle => match naming {
+            Some(naming) => Box::new(MavenResolver::with_naming(naming).with_test_source_set(test_source_set)),
+            None => Box::new(MavenResolver::new().with_test_source_set(test_source_set)),
+        },
         StructureType::SameFile => Box::new(SameFileResolver::new()),
-        StructureType::Flat => Box::new(MavenResolver::new()), // Use Maven as placeholder for flat
+        StructureType::Flat => match naming {
+            Some(naming) => Box::new(FlatResolver::with_naming(naming)),
+            None => Box::new(FlatResolver::new()),
+        },
+        StructureType::Adjacent => match naming {
+            Some(naming) => Box::new(AdjacentResolver::with_naming(naming)),
+            None => Box::new(AdjacentResolver::new()),
+        },
+        StructureType::TestsDir => match naming {
+            Some(naming) => Box::new(AdjacentResolver::with_naming(naming).in_subdirectory(adjacent::JS_TESTS_DIR)),
+            None => Box::new(AdjacentResolver::new().in_subdirectory(adjacent::JS_TESTS_DIR)),
+        },
+        StructureType::TestCrate => {
+            // The test crate is configured in the workspace root's .testsmithrc
+            let test_crate = cargo_workspace::find_workspace_root(source_path)
+                .map(|root| testsmithrc::load_rc(&root))
+                .transpose()?
+                .flatten()
+                .and_then(|rc| rc.test_crate);
+            match test_crate {
+                Some(name) => Box::new(TestCrateResolver::with_test_crate(name)),
+                None => Box::new(TestCrateResolver::new()),
+            }
+        }
     };
 
     // Resolve test file path
-    let test_file_path = resolver.resolve_test_path(fs, source_path, language)?;
+    let mut test_file_path = match options.test_dir {
+        Some(ref test_dir) => resolve_in_test_dir(fs, resolver.as_ref(), source_path, language, structure, test_dir)?,
+        None => resolver.resolve_test_path(fs, source_path, language)?,
+    };
+
+    // Other places the test could go, for editors offering a choice (e.g. `foo.test.js` or `__tests__/`)
+    let candidate_test_paths = match options.test_dir {
+        Some(_) => vec![test_file_path.clone()],
+        None => resolver.candidate_test_paths(fs, source_path, language),
+    }
+    .iter()
+    .map(|path| path.to_string_lossy().to_string())
+    .collect();
+
+    // A test path escaping the project root (symlinks, `..`) is almost always a mistake;
+    // test crates are sibling workspace members, so check those against the workspace
+    let containing_root = if structure == StructureType::TestCrate {
+        cargo_workspace::find_workspace_root(source_path)
+    } else {
+        project_root.clone()
+    };
+    if !options.force
+        && let Some(ref root) = containing_root
+        && escapes_root(fs, &test_file_path, root)
+    {
+        return Err(TestsmithError::InvalidPath {
+            path: test_file_path,
+            reason: format!(
+                "Test path is outside the project root {} (use --force to write it anyway)",
+                root.display()
+            ),
+        });
+    }
+
+    let js_tests_dir = structure == StructureType::TestsDir && matches!(language, Language::JavaScript | Language::TypeScript);
+    if options.with_runner && framework != Framework::Unittest && !js_tests_dir {
+        return Err(TestsmithError::ConfigError {
+            reason: "--with-runner is only supported for Python unittest and for JavaScript/TypeScript tests in __tests__/ (tests-dir structure)".to_string(),
+        });
+    }
+
+    let appendable = match structure {
+        StructureType::SameFile => language == Language::Rust,
+        _ => language == Language::Java,
+    };
+    let append = options.on_exists == OnExists::Append;
+    if append && !appendable {
+        return Err(TestsmithError::ConfigError {
+            reason: "--append is only supported for separate Java test classes and same-file Rust test modules"
+                .to_string(),
+        });
+    }
 
     // Check if test file exists (different logic for same-file vs separate files)
     let mut test_exists = false;
@@ -148,59 +582,144 @@ pub fn generate(
         // For same-file: check if a test module already exists within the file
         if let Ok(content) = fs.read_file(&test_file_path) {
             test_exists = true;
-            has_test_module = content.contains("#[cfg(test)]");
+            has_test_module = SameFileResolver::has_tests(&content, language);
+
+            // `#[cfg(test)] mod tests;` keeps the tests in their own file; open that instead
+            if !has_test_module
+                && language == Language::Rust
+                && let Some(module_path) = SameFileResolver::external_test_module(fs, &test_file_path, &content)
+            {
+                if append {
+                    return Err(TestsmithError::ConfigError {
+                        reason: format!(
+                            "The test module is declared in its own file {}; --append only adds to inline modules",
+                            module_path.display()
+                        ),
+                    });
+                }
+                decisions.push(format!("Test module: declared in {}", module_path.display()));
+                test_file_path = module_path;
+                has_test_module = true;
+            }
         }
     } else {
         // For separate files: just check if file exists
         test_exists = fs.file_exists(&test_file_path);
     }
 
+    if options.on_exists == OnExists::Error && test_exists && (has_test_module || structure != StructureType::SameFile) {
+        return Err(TestsmithError::TestFileAlreadyExists { path: test_file_path });
+    }
+
     // If tests already exist, just position cursor and return
-    if test_exists && has_test_module {
-        let line_number = if let Ok(content) = fs.read_file(&test_file_path) {
-            // Look for first #[test] function
-            content
+    if test_exists && has_test_module && !append {
+        let (line_number, column_number) = if let Ok(content) = fs.read_file(&test_file_path) {
+            // Look for the first test (#[test] function, Zig test block)
+            let line_number = content
                 .lines()
                 .enumerate()
-                .find(|(_, line)| line.contains("#[test]"))
+                .find(|(_, line)| SameFileResolver::is_test_line(line, language))
                 .map(|(idx, _)| (idx + 1) as i32)
-                .unwrap_or_else(|| {
-                    // Fall back to TODO comment
-                    content
-                        .lines()
-                        .enumerate()
-                        .find(|(_, line)| line.contains("// TODO"))
-                        .map(|(idx, _)| (idx + 1) as i32)
-                        .unwrap_or(1)
-                })
+                // Fall back to TODO comment
+                .unwrap_or_else(|| find_cursor_line(&content, &todo_text, 0).unwrap_or(1));
+            (line_number, find_cursor_column(&content, line_number, &todo_text))
         } else {
-            1
+            (1, 0)
         };
+        // Only the test module counts; the rest of the file is the source under test
+        let has_only_stub = fs.read_file(&test_file_path).is_ok_and(|content| {
+            let tests = SameFileResolver::test_module_body(&content).map_or(content.as_str(), |body| &content[body]);
+            has_only_stub(tests, &todo_text, stub_failing_statement(language, framework, project_root.as_deref()))
+        });
 
         return Ok(GeneratorResult {
             test_file_path: test_file_path.to_string_lossy().to_string(),
             created: false,
             dry_run: false,
             line_number,
+            column_number,
+            framework: format!("{:?}", framework),
+            structure,
+            resolver_name: resolver.name().to_string(),
+            decisions,
+            warnings,
+            has_only_stub,
+            candidate_test_paths,
+            preview_content: None,
+        });
+    } else if test_exists && append && (has_test_module || structure != StructureType::SameFile) {
+        // Add another test to the existing class or test module
+        let content = fs.read_file(&test_file_path)?;
+        let prefix = options
+            .method_prefix
+            .as_deref()
+            .unwrap_or_else(|| method_stub::default_test_prefix(framework));
+        let appended = match SameFileResolver::test_module_body(&content) {
+            Some(body) if structure == StructureType::SameFile => {
+                append_rust_test_fn(&content, body, prefix, options.append_position, &todo_text)
+            }
+            _ => append_java_test_method(&content, framework, prefix, &todo_text),
+        };
+        let (updated, line_number) = appended.ok_or_else(|| TestsmithError::InvalidPath {
+            path: test_file_path.clone(),
+            reason: "Test file has no closing brace to add a test method before".to_string(),
+        })?;
+
+        if !options.dry_run {
+            fs.write_file_new(&test_file_path, &updated)?;
+        }
+
+        return Ok(GeneratorResult {
+            test_file_path: test_file_path.to_string_lossy().to_string(),
+            created: false,
+            dry_run: options.dry_run,
+            line_number,
+            column_number: find_cursor_column(&updated, line_number, &todo_text),
+            framework: format!("{:?}", framework),
+            structure,
+            resolver_name: resolver.name().to_string(),
+            decisions,
+            warnings,
+            has_only_stub: has_only_stub(
+                &updated,
+                &todo_text,
+                stub_failing_statement(language, framework, project_root.as_deref()),
+            ),
+            candidate_test_paths,
+            preview_content: options.dry_run.then_some(updated),
         });
     } else if test_exists && !has_test_module && structure != StructureType::SameFile {
         // For non-same-file structures, if file exists but has no tests, return error
-        let line_number = if let Ok(content) = fs.read_file(&test_file_path) {
-            content
-                .lines()
-                .enumerate()
-                .find(|(_, line)| line.contains("// TODO") || line.contains("TODO:"))
-                .map(|(idx, _)| (idx + 1) as i32)
-                .unwrap_or(1)
-        } else {
-            1
-        };
+        let mut content = fs.read_file(&test_file_path).unwrap_or_default();
+        if options.with_runner
+            && framework == Framework::Unittest
+            && !options.dry_run
+            && let Some(guard) = PythonUnittestTemplate::missing_main_guard(&content)
+        {
+            content.push_str(&guard);
+            fs.write_file_new(&test_file_path, &content)?;
+            decisions.push("Runner: added the __main__ guard to the existing test".to_string());
+        }
+        let line_number = find_cursor_line(&content, &todo_text, 0).unwrap_or(1);
 
         return Ok(GeneratorResult {
             test_file_path: test_file_path.to_string_lossy().to_string(),
             created: false,
             dry_run: false,
             line_number,
+            column_number: find_cursor_column(&content, line_number, &todo_text),
+            framework: format!("{:?}", framework),
+            structure,
+            resolver_name: resolver.name().to_string(),
+            decisions,
+            warnings,
+            has_only_stub: has_only_stub(
+                &content,
+                &todo_text,
+                stub_failing_statement(language, framework, project_root.as_deref()),
+            ),
+            candidate_test_paths,
+            preview_content: None,
         });
     }
 
@@ -223,58 +742,242 @@ pub fn generate(
         framework,
     );
 
+    // Package and module names come from the source, read through the file system like everything else
+    let source_content = fs.read_file(source_path).ok();
+
     // For Java, extract package and class names
     if language == Language::Java {
-        if let Ok(package_name) = JavaJunitTemplate::extract_package_name(source_path) {
-            if let Some(pkg) = package_name {
-                context = context.with_package_name(pkg);
-            }
+        if let Ok(Some(pkg)) = JavaJunitTemplate::extract_package_name(source_path) {
+            context = context.with_package_name(pkg);
         }
 
         if let Ok(class_name) = JavaJunitTemplate::extract_class_name(source_path) {
             context = context.with_class_name(class_name);
         }
+
+    }
+
+    if language == Language::Kotlin
+        && let Some(pkg) = source_content.as_deref().and_then(KotlinJunitTemplate::extract_package_name)
+    {
+        context = context.with_package_name(pkg);
+    }
+
+    if language == Language::Go
+        && let Some(pkg) = source_content.as_deref().and_then(GoTestingTemplate::extract_package_name)
+    {
+        context = context.with_package_name(pkg);
+    }
+
+    if language == Language::Scala
+        && let Some(pkg) = source_content.as_deref().and_then(ScalaTestTemplate::extract_package_name)
+    {
+        context = context.with_package_name(pkg);
+    }
+
+    if language == Language::Php
+        && let Some(namespace) = source_content.as_deref().and_then(PhpUnitTemplate::extract_namespace)
+    {
+        let prefix = rc
+            .as_ref()
+            .and_then(|rc| rc.php_test_namespace.as_deref())
+            .unwrap_or(php_phpunit::DEFAULT_TEST_NAMESPACE_PREFIX);
+        context = context.with_package_name(PhpUnitTemplate::test_namespace(&namespace, prefix));
+    }
+
+    if language == Language::Elixir
+        && let Some(module) = source_content.as_deref().and_then(ElixirExUnitTemplate::extract_module_name)
+    {
+        context = context.with_class_name(module);
+    }
+
+    if language == Language::Haskell
+        && let Some(module) = source_content.as_deref().and_then(HaskellHSpecTemplate::extract_module_name)
+    {
+        context = context.with_class_name(module);
+    }
+
+    if language == Language::Dart
+        && let Some(import) = package_import::source_import(project_root.as_deref(), source_path, &test_file_path, language)
+    {
+        context = context.with_source_import(import);
+    }
+
+    if framework == Framework::Mocha
+        && let Some(library) = project_root
+            .as_deref()
+            .and_then(framework_detector::detect_js_assertion_library)
+    {
+        decisions.push(format!("Assertion library {}: detected from package.json", library));
+        context = context.with_assertion_lib(library);
+    }
+
+    if language == Language::Rust
+        && let Some(library) = project_root
+            .as_deref()
+            .and_then(framework_detector::detect_rust_assertion_library)
+    {
+        decisions.push(format!("Assertion library {}: detected from Cargo.toml", library));
+        context = context.with_assertion_lib(library);
+    }
+
+    if let Some(prefix) = options.method_prefix {
+        if !method_stub::is_valid_identifier(&method_stub::prefixed_name(&prefix, "example"), language) {
+            return Err(TestsmithError::ConfigError {
+                reason: format!("Test method prefix '{}' does not form a valid {:?} identifier", prefix, language),
+            });
+        }
+        context = context.with_method_prefix(prefix);
+    }
+
+    if options.parameterized {
+        if framework != Framework::JUnit {
+            return Err(TestsmithError::ConfigError {
+                reason: format!("--parameterized is only supported for JUnit 5, not {:?}", framework),
+            });
+        }
+        context = context.with_parameterized(true);
+    }
+
+    // Property-based stubs need a library the build file already declares
+    if options.test_kind == TestKind::Property {
+        let library = project_root
+            .as_deref()
+            .and_then(|root| framework_detector::detect_property_library(root, language));
+        match (library, framework) {
+            (
+                Some(library @ (PropertyLibrary::Proptest | PropertyLibrary::Quickcheck)),
+                Framework::Native | Framework::Rstest | Framework::Proptest,
+            )
+            | (Some(library @ PropertyLibrary::Jqwik), Framework::JUnit) => {
+                decisions.push(format!("Property library {:?}: detected from the build file", library));
+                context = context.with_property_library(library);
+            }
+            _ => {
+                return Err(TestsmithError::ConfigError {
+                    reason: format!(
+                        "Property-based tests need proptest or quickcheck (Rust) or jqwik with JUnit 5 (Java) in the build file; none found for {:?} with {:?}",
+                        language, framework
+                    ),
+                });
+            }
+        }
+    }
+
+    let public_methods: Vec<_> = if (options.method_stubs || options.document_api)
+        && let Some(source) = source_content
+    {
+        let methods = match language {
+            Language::Java => method_stub::extract_java_methods(&source),
+            Language::Kotlin => method_stub::extract_kotlin_methods(&source),
+            Language::Rust => method_stub::extract_rust_functions(&source),
+            _ => Vec::new(),
+        };
+        methods.into_iter().filter(|method| !method.is_private).collect()
+    } else {
+        Vec::new()
+    };
+
+    if options.method_stubs {
+        context = context.with_methods(public_methods.clone()).with_method_naming(options.method_naming);
+    }
+    if options.section_markers || rc.as_ref().and_then(|rc| rc.section_markers).unwrap_or(false) {
+        context = context.with_section_markers(true);
+    }
+
+    // For Rust integration tests in a test crate, import the source crate
+    if structure == StructureType::TestCrate {
+        let source_member = TestCrateResolver::source_member(source_path)?;
+        context = context.with_module_path(source_member.crate_ident());
+    } else if language == Language::Rust
+        && structure != StructureType::SameFile
+        && let Some(crate_name) = project_root.as_deref().and_then(cargo_workspace::crate_name_from_cargo_toml)
+    {
+        // A separate test file (tests/foo_test.rs) is an integration test: it sees the crate, not `super`
+        context = context.with_module_path(crate_name);
+    }
+
+    // Generate content, formatted per the nearest .editorconfig (if any)
+    let mut content = generator.generate(&context)?;
+    let failing_statement = method_stub::failing_statement(language, framework, context.assertion_lib.as_deref());
+    if options.failing_stub || rc.as_ref().and_then(|rc| rc.failing_stub).unwrap_or(false) {
+        match failing_statement {
+            Some(statement) => content = with_failing_statement(&content, statement),
+            None => warnings.push(format!(
+                "{} stubs have no failing assertion; the new test is left empty",
+                generator.name()
+            )),
+        }
+    }
+    if todo_text != method_stub::DEFAULT_TODO_TEXT {
+        content = content.replace(method_stub::DEFAULT_TODO_TEXT, &todo_text);
+    }
+    let checklist = if options.document_api {
+        method_stub::api_checklist(&public_methods, language)
+    } else {
+        String::new()
+    };
+    // The checklist's TODOs come first, but the cursor belongs on the test body's TODO
+    let checklist_lines = checklist.lines().count();
+    if !checklist.is_empty() {
+        content = format!("{}\n{}", checklist, content);
+    }
+    let editor_config = editorconfig::find_editorconfig(fs, &test_file_path);
+    if !editor_config.is_empty() {
+        content = editor_config.apply(&content);
     }
 
-    // Generate content
-    let content = generator.generate(&context)?;
+    if options.insert_at_line.is_some() && structure != StructureType::SameFile {
+        return Err(TestsmithError::ConfigError {
+            reason: "--insert-at-line can only be used with the same-file structure".to_string(),
+        });
+    }
 
-    // Calculate line number of TODO comment for cursor positioning
-    let line_number = if structure == StructureType::SameFile {
+    // Calculate line and column of TODO comment for cursor positioning
+    let (line_number, column_number) = if structure == StructureType::SameFile {
         // For same-file: calculate where the test module will be in the existing file
         if let Ok(existing_content) = fs.read_file(&test_file_path) {
             let existing_lines = existing_content.lines().count() as i32;
-            // Find the TODO line in the new content to add to existing line count
-            let todo_offset = content
-                .lines()
-                .enumerate()
-                .find(|(_, line)| line.contains("// TODO"))
-                .map(|(idx, _)| (idx + 1) as i32)
-                .unwrap_or(1);
-            existing_lines + todo_offset
+            // Find the TODO line in the new content to add to the module's starting offset
+            let todo_offset = find_cursor_line(&content, &todo_text, checklist_lines).unwrap_or(1);
+            let line_number = match options.insert_at_line {
+                // Lines past the end append, so clamp to just after the last line
+                Some(line) => (line.max(1) as i32).min(existing_lines + 1) - 1 + todo_offset,
+                None => existing_lines + todo_offset,
+            };
+            (line_number, find_cursor_column(&content, todo_offset, &todo_text))
         } else {
             // If can't read existing file, default to 1
-            1
+            (1, 0)
         }
     } else {
-        // For separate files: TODO is relative to start of new file
-        content
-            .lines()
-            .enumerate()
-            .find(|(_, line)| line.contains("// TODO"))
-            .map(|(idx, _)| (idx + 1) as i32)
-            .unwrap_or(1)
+        // For separate files: TODO is relative to start of new file (`#` comments for Elixir)
+        let line_number = find_cursor_line(&content, &todo_text, checklist_lines).unwrap_or(1);
+        (line_number, find_cursor_column(&content, line_number, &todo_text))
     };
 
+    // On dry-run, optionally create the mirrored test directory so it shows up in file explorers
+    if options.dry_run && options.prepare_dirs && structure != StructureType::SameFile {
+        fs.create_parent_directories(&test_file_path)?;
+    }
+
     // Write file (unless dry run)
     if !options.dry_run {
         if structure == StructureType::SameFile {
-            // For same-file structure, append to existing file
-            fs.append_to_file(&test_file_path, &content)?;
+            // For same-file structure, splice into or append to the existing file
+            match options.insert_at_line {
+                Some(line) => fs.insert_at_line(&test_file_path, line, &content)?,
+                None => fs.append_to_file(&test_file_path, &content)?,
+            }
         } else {
             // For other structures, create new test file
             fs.write_file_new(&test_file_path, &content)?;
         }
+
+        if options.with_runner && js_tests_dir {
+            let index_path = write_js_test_index(fs, &test_file_path, language)?;
+            decisions.push(format!("Runner: {} loads every test beside it", index_path.display()));
+        }
     }
 
     Ok(GeneratorResult {
@@ -282,47 +985,445 @@ pub fn generate(
         created: true,
         dry_run: options.dry_run,
         line_number,
+        column_number,
+        framework: format!("{:?}", framework),
+        structure,
+        resolver_name: resolver.name().to_string(),
+        decisions,
+        warnings,
+        has_only_stub: has_only_stub(&content, &todo_text, failing_statement),
+        candidate_test_paths,
+        preview_content: options.dry_run.then_some(content),
     })
 }
 
+/// Rewrite the `index.test.*` next to a test in `__tests__/` so it loads every test there
+fn write_js_test_index(fs: &FileSystem, test_file_path: &Path, language: Language) -> Result<PathBuf, TestsmithError> {
+    let tests_dir = test_file_path.parent().unwrap_or(Path::new("."));
+    let test_file_names: Vec<String> = fs
+        .read_dir(tests_dir)?
+        .into_iter()
+        .filter(|(_, kind)| *kind == EntryKind::File)
+        .filter_map(|(path, _)| path.file_name()?.to_str().map(str::to_string))
+        .filter(|name| js_index::is_indexed_test(name))
+        .collect();
+
+    let index_path = tests_dir.join(js_index::index_file_name(language));
+    fs.write_file_new(&index_path, &js_index::generate_index(&test_file_names, language))?;
+    Ok(index_path)
+}
+
+/// Structure stored in a cache entry, by its `Debug` name
+fn parse_cached_structure(name: &str) -> Option<StructureType> {
+    match name {
+        "Maven" => Some(StructureType::Maven),
+        "Gradle" => Some(StructureType::Gradle),
+        "SameFile" => Some(StructureType::SameFile),
+        "Flat" => Some(StructureType::Flat),
+        "TestCrate" => Some(StructureType::TestCrate),
+        "Adjacent" => Some(StructureType::Adjacent),
+        "TestsDir" => Some(StructureType::TestsDir),
+        _ => None,
+    }
+}
+
+/// Add `statement` after each TODO comment of a freshly generated test, at the comment's indentation
+fn with_failing_statement(content: &str, statement: &str) -> String {
+    content
+        .lines()
+        .flat_map(|line| {
+            let failing = line.contains(method_stub::DEFAULT_TODO_TEXT).then(|| {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                format!("{}{}", indent, statement)
+            });
+            std::iter::once(line.to_string()).chain(failing)
+        })
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// The statement `--failing-stub` writes for this framework, as `failing_statement` picks it on creation
+fn stub_failing_statement(language: Language, framework: Framework, project_root: Option<&Path>) -> Option<&'static str> {
+    let assertion_lib = match framework {
+        Framework::Mocha => project_root.and_then(framework_detector::detect_js_assertion_library),
+        _ => None,
+    };
+    method_stub::failing_statement(language, framework, assertion_lib.as_deref())
+}
+
+/// Whether a test file still holds only its generated stub: a TODO comment and no assertion
+/// calls (`assert_eq!`, `assertEquals(`, `expect(`, `Assert.Equal(`, `XCTAssertEqual(`, `t.Errorf(`, ...)
+/// Import lines and the untouched `--failing-stub` statement are ignored, so neither
+/// `import static ...Assertions.*` nor `expect(false).toBe(true);` counts as an assertion
+fn has_only_stub(content: &str, todo_text: &str, failing_statement: Option<&str>) -> bool {
+    let import = Regex::new(r"^\s*(?:import|use|using|from|package|#include)\b").unwrap();
+    let assertion = Regex::new(
+        r"\b(?:assert\w*|expect\w*|refute\w*|should\w*|verify|Assert\.\w+|EXPECT_\w+|ASSERT_\w+|XCTAssert\w*)\s*[(!.]|^\s*assert\s|\bt\.(?:Error|Fatal|Fail)\w*\(",
+    )
+    .unwrap();
+
+    find_cursor_line(content, todo_text, 0).is_some()
+        && !content
+            .lines()
+            .filter(|line| !import.is_match(line) && Some(line.trim()) != failing_statement)
+            .any(|line| assertion.is_match(line))
+}
+
+/// 1-based line of the first line holding the configured TODO text after the first `skip` lines
+fn find_cursor_line(content: &str, todo_text: &str, skip: usize) -> Option<i32> {
+    content
+        .lines()
+        .enumerate()
+        .skip(skip)
+        .find(|(_, line)| line.contains(todo_text))
+        .map(|(idx, _)| (idx + 1) as i32)
+}
+
+/// 0-based byte column where the cursor should land on `line_number` (1-based): the start of the
+/// TODO comment, including its `//`/`#`/`--` leader, or the line's first non-blank byte without one
+fn find_cursor_column(content: &str, line_number: i32, todo_text: &str) -> i32 {
+    let Some(line) = content.lines().nth((line_number - 1).max(0) as usize) else {
+        return 0;
+    };
+    let marker = [todo_text, "TODO"]
+        .into_iter()
+        .filter(|marker| !marker.is_empty())
+        .find_map(|marker| line.find(marker));
+    let column = match marker {
+        Some(offset) => line[..offset]
+            .trim_end()
+            .trim_end_matches(['/', '#', '-', '*'])
+            .len(),
+        None => line.len() - line.trim_start().len(),
+    };
+    column as i32
+}
+
+/// Insert a `@Test` stub named `<prefix><N>` before the class's final closing brace
+/// Only the method (plus the `@Test` import when nothing imports it yet) is added; the class's
+/// base class, setup methods and other imports are left as they are
+/// Returns the updated file and the stub's TODO line (1-based)
+fn append_java_test_method(
+    content: &str,
+    framework: Framework,
+    prefix: &str,
+    todo_text: &str,
+) -> Option<(String, i32)> {
+    let with_import = add_java_import(content, java_test_annotation(framework));
+    let content = with_import.as_str();
+    let closing = content.rfind('}')?;
+
+    // Number after the existing tests, skipping names already taken
+    let mut number = content.matches("@Test").count() + 1;
+    while content.contains(&format!("{}{}(", prefix, number)) {
+        number += 1;
+    }
+
+    // JUnit 4 and TestNG need public test methods; JUnit 5 doesn't
+    let visibility = if framework == Framework::JUnit { "" } else { "public " };
+    let stub = format!(
+        "\n    @Test\n    {}void {}{}() {{\n        // {}\n    }}\n",
+        visibility, prefix, number, todo_text
+    );
+
+    let updated = format!("{}{}{}", &content[..closing], stub, &content[closing..]);
+    let todo_offset = closing + stub.find(todo_text)?;
+    let line_number = updated[..todo_offset].matches('\n').count() as i32 + 1;
+
+    Some((updated, line_number))
+}
+
+/// Fully qualified `@Test` annotation for a JVM test framework
+fn java_test_annotation(framework: Framework) -> &'static str {
+    match framework {
+        Framework::JUnit4 => "org.junit.Test",
+        Framework::TestNG => "org.testng.annotations.Test",
+        _ => "org.junit.jupiter.api.Test",
+    }
+}
+
+/// Add `import <class>;` after the file's existing imports (or its package line)
+/// Left unchanged when the class, or a wildcard over its package, is already imported
+fn add_java_import(content: &str, class: &str) -> String {
+    let package = class.rsplit_once('.').map_or("", |(package, _)| package);
+    let import_regex = Regex::new(r"^\s*import\s+([\w.]+(?:\.\*)?)\s*;").unwrap();
+
+    let mut insert_at = None;
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        if let Some(caps) = import_regex.captures(line) {
+            let imported = &caps[1];
+            if imported == class || imported.strip_suffix(".*") == Some(package) {
+                return content.to_string();
+            }
+            insert_at = Some((offset + line.len(), ""));
+        } else if insert_at.is_none() && line.trim_start().starts_with("package ") {
+            insert_at = Some((offset + line.len(), "\n"));
+        }
+        offset += line.len();
+    }
+
+    let (at, separator) = insert_at.unwrap_or((0, ""));
+    let trailer = if at == 0 { "\n" } else { "" };
+    let newline = if at > 0 && !content[..at].ends_with('\n') { "\n" } else { "" };
+    format!("{}{}{}import {};\n{}{}", &content[..at], newline, separator, class, trailer, &content[at..])
+}
+
+/// Insert a `#[test]` fn named `<prefix><N>` into an existing Rust test module
+/// `body` is the module's byte range between its braces; `Top` places the fn after the module's `use` lines
+/// Returns the updated file and the stub's TODO line (1-based)
+fn append_rust_test_fn(
+    content: &str,
+    body: Range<usize>,
+    prefix: &str,
+    position: AppendPosition,
+    todo_text: &str,
+) -> Option<(String, i32)> {
+    let module = &content[body.clone()];
+
+    // Number after the module's existing tests, skipping names already taken
+    let mut number = module.matches("#[test]").count() + 1;
+    while content.contains(&format!("fn {}{}(", prefix, number)) {
+        number += 1;
+    }
+
+    let test_fn = format!("    #[test]\n    fn {}{}() {{\n        // {}\n    }}\n", prefix, number, todo_text);
+    let first_line_end = module.find('\n');
+    let (insert_at, stub) = match (position, first_line_end) {
+        (AppendPosition::Top, Some(newline)) => {
+            // Skip the opening line, then any `use` items and blank lines
+            let mut offset = body.start + newline + 1;
+            let mut in_use = false;
+            for line in content[offset..body.end].split_inclusive('\n') {
+                let trimmed = line.trim();
+                if in_use || trimmed.starts_with("use ") || trimmed.starts_with("pub use ") {
+                    // A grouped import can span lines (`use foo::{\n    a,\n};`) and only ends at its `;`
+                    in_use = !trimmed.ends_with(';');
+                } else if !trimmed.is_empty() {
+                    break;
+                }
+                offset += line.len();
+            }
+            (offset, format!("{}\n", test_fn))
+        }
+        // A single-line module (`mod tests {}`) has nothing to go after, so its new fn goes last
+        _ => (body.end, format!("\n{}", test_fn)),
+    };
+
+    let updated = format!("{}{}{}", &content[..insert_at], stub, &content[insert_at..]);
+    let todo_offset = insert_at + stub.find(todo_text)?;
+    let line_number = updated[..todo_offset].matches('\n').count() as i32 + 1;
+
+    Some((updated, line_number))
+}
+
+/// Whether a test path lies outside the project root once `..` and symlinks are resolved
+/// The path usually doesn't exist yet, so its nearest existing ancestor is canonicalized
+fn escapes_root(fs: &FileSystem, test_path: &Path, project_root: &Path) -> bool {
+    let absolute = if test_path.is_absolute() {
+        test_path.to_path_buf()
+    } else {
+        fs.current_dir().unwrap_or_else(|_| PathBuf::from(".")).join(test_path)
+    };
+    let absolute = absolute.clean();
+
+    let mut existing = absolute.as_path();
+    let mut missing = Vec::new();
+    while !fs.path_exists(existing) {
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => break,
+        }
+    }
+
+    let mut resolved = fs.canonicalize(existing).unwrap_or_else(|_| existing.to_path_buf());
+    resolved.extend(missing.iter().rev());
+
+    let root = fs.canonicalize(project_root).unwrap_or_else(|_| project_root.to_path_buf());
+    !resolved.starts_with(root)
+}
+
+/// Resolve the test path inside an explicit test directory
+/// Keeps the resolver's file name and, for JVM languages, nests it under the package path
+fn resolve_in_test_dir(
+    fs: &FileSystem,
+    resolver: &dyn StructureResolver,
+    source_path: &Path,
+    language: Language,
+    structure: StructureType,
+    test_dir: &Path,
+) -> Result<PathBuf, TestsmithError> {
+    if structure == StructureType::SameFile {
+        return Err(TestsmithError::ConfigError {
+            reason: "--test-dir cannot be used with the same-file structure".to_string(),
+        });
+    }
+
+    if !fs.file_exists(source_path) {
+        return Err(TestsmithError::FileNotFound {
+            path: source_path.to_path_buf(),
+        });
+    }
+
+    let source_content = fs.read_file(source_path).ok();
+    let package = match language {
+        Language::Java => JavaJunitTemplate::extract_package_name(source_path).ok().flatten(),
+        Language::Kotlin => source_content.as_deref().and_then(KotlinJunitTemplate::extract_package_name),
+        Language::Scala => source_content.as_deref().and_then(ScalaTestTemplate::extract_package_name),
+        _ => None,
+    };
+
+    let mut test_path = test_dir.to_path_buf();
+    if let Some(package) = package {
+        test_path.extend(package.split('.'));
+    }
+    test_path.push(resolver.test_file_name(source_path, language)?);
+
+    Ok(test_path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
 
     #[test]
     fn test_generate_nonexistent_source_file() {
         let fs = FileSystem::new_memory();
         let options = GeneratorOptions {
-            structure: StructureType::Maven,
             language: Some(Language::Java),
             framework: Some(Framework::JUnit),
-            create: true,
-            dry_run: false,
+            ..Default::default()
         };
 
-        let result = generate(&fs, Path::new("nonexistent.java"), options);
+        let result = generate_with_cache(&fs, Path::new("nonexistent.java"), options, &mut ProjectCache::new());
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_detect_language_from_java_file() {
-        let fs = FileSystem::new_memory();
+    fn test_missing_source_is_not_found_rather_than_unsupported() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let os_source = temp_dir.path().join("notes.xyz");
+
+        for (fs, source) in [
+            (FileSystem::new_memory(), PathBuf::from("/project/notes.xyz")),
+            (FileSystem::new_os(), os_source),
+        ] {
+            let options = GeneratorOptions::default();
+
+            let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new());
+            assert!(matches!(result, Err(TestsmithError::FileNotFound { ref path }) if path == &source));
+        }
+    }
+
+    #[test]
+    fn test_typescript_declaration_and_test_files_are_not_generated_for() {
+        let fs = FileSystem::new_memory();
+        for file in ["/project/src/foo.d.ts", "/project/src/foo.test.ts"] {
+            let source = PathBuf::from(file);
+            fs.write_file_new(&source, "export declare function foo(): void;\n").unwrap();
+
+            let options = GeneratorOptions {
+                structure: StructureType::Adjacent,
+                framework: Some(Framework::Jasmine),
+                ..Default::default()
+            };
+
+            let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new());
+            match file {
+                "/project/src/foo.d.ts" => assert!(matches!(result, Err(TestsmithError::InvalidSourceFile { .. }))),
+                _ => assert!(matches!(result, Err(TestsmithError::AlreadyTestFile { .. }))),
+            }
+        }
+        assert!(!fs.file_exists(Path::new("/project/src/foo.d.test.ts")));
+    }
+
+    #[test]
+    fn test_existing_test_reports_whether_it_is_only_a_stub() {
+        let stub = "import static org.junit.jupiter.api.Assertions.*;\n\nclass FooTest {\n    @Test\n    void testExample() {\n        // TODO: Implement test\n    }\n}\n";
+        let written = "import static org.junit.jupiter.api.Assertions.*;\n\nclass FooTest {\n    @Test\n    void testExample() {\n        // TODO: Implement test\n        assertEquals(2, Foo.add(1, 1));\n    }\n}\n";
+
+        for (content, expected) in [(stub, true), (written, false)] {
+            let fs = FileSystem::new_memory();
+            let source = PathBuf::from("/project/src/main/java/com/example/Foo.java");
+            fs.write_file_new(&source, "package com.example;\n\npublic class Foo {}\n").unwrap();
+            fs.write_file_new(Path::new("/project/src/test/java/com/example/FooTest.java"), content).unwrap();
+
+            let options = GeneratorOptions {
+                structure: StructureType::Gradle,
+                framework: Some(Framework::JUnit),
+                ..Default::default()
+            };
+
+            let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+            assert!(!result.created);
+            assert_eq!(result.has_only_stub, expected);
+            assert_eq!(result.line_number, 6);
+        }
+    }
+
+    #[test]
+    fn test_cursor_line_matches_only_the_configured_todo_text() {
+        let content = "// TODO: cover the error path\n# TODO later\n// FIXME: add assertions\n";
+        assert_eq!(find_cursor_line(content, "FIXME: add assertions", 0), Some(3));
+        assert_eq!(find_cursor_line(content, method_stub::DEFAULT_TODO_TEXT, 0), None);
+    }
+
+    #[test]
+    fn test_has_only_stub_recognises_assertions_across_frameworks() {
+        let todo = method_stub::DEFAULT_TODO_TEXT;
+        assert!(has_only_stub("fn it_works() {\n    // TODO: Implement test\n}\n", todo, None));
+        assert!(!has_only_stub("// TODO: Implement test\nassert_eq!(add(1, 1), 2);\n", todo, None));
+        assert!(!has_only_stub("# TODO: Implement test\n    assert result == 2\n", todo, None));
+        assert!(!has_only_stub("// TODO: Implement test\nexpect(cart.total()).toBe(0);\n", todo, None));
+        assert!(!has_only_stub("// TODO: Implement test\nt.Errorf(\"got %d\", got)\n", todo, None));
+        assert!(!has_only_stub("class FooTest {}\n", todo, None));
+
+        // The untouched --failing-stub statement isn't an assertion, but the same call written by hand elsewhere is
+        let failing = "// TODO: Implement test\n    t.Fatal(\"not implemented\")\n";
+        assert!(has_only_stub(failing, todo, Some("t.Fatal(\"not implemented\")")));
+        assert!(!has_only_stub(failing, todo, None));
+        assert!(!has_only_stub("// TODO: Implement test\nt.Fatal(\"bad input\")\n", todo, Some("t.Fatal(\"not implemented\")")));
+    }
+
+    #[test]
+    fn test_rust_test_module_in_its_own_file_is_opened_not_reappended() {
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/crate/src/lib.rs");
+        let source_content = "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n\n#[cfg(test)]\nmod tests;\n";
+        fs.write_file_new(&source, source_content).unwrap();
+        fs.write_file_new(Path::new("/crate/src/tests.rs"), "use super::*;\n\n#[test]\nfn adds() {\n    assert_eq!(add(1, 1), 2);\n}\n").unwrap();
+
+        let options = GeneratorOptions {
+            structure: StructureType::SameFile,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert!(!result.created);
+        assert_eq!(result.test_file_path, "/crate/src/tests.rs");
+        assert_eq!(result.line_number, 3);
+        assert_eq!(fs.read_file(&source).unwrap(), source_content);
+    }
+
+    #[test]
+    fn test_detect_language_from_java_file() {
+        let fs = FileSystem::new_memory();
         let java_file = PathBuf::from("/src/main/java/Foo.java");
 
         // Create the source file
         fs.write_file_new(&java_file, "public class Foo {}").unwrap();
 
         let options = GeneratorOptions {
-            structure: StructureType::Maven,
             language: None, // Auto-detect
-            framework: None,
             create: false, // Don't create yet
-            dry_run: false,
+            ..Default::default()
         };
 
         // Should fail because test file doesn't exist and create=false
-        let result = generate(&fs, &java_file, options);
+        let result = generate_with_cache(&fs, &java_file, options, &mut ProjectCache::new());
         assert!(result.is_err());
     }
 
@@ -334,18 +1435,1769 @@ mod tests {
         fs.write_file_new(&java_file, "package com.example;\n\npublic class Foo {}").unwrap();
 
         let options = GeneratorOptions {
-            structure: StructureType::Maven,
             language: Some(Language::Java),
             framework: Some(Framework::JUnit),
-            create: true,
             dry_run: true, // Dry run
+            ..Default::default()
         };
 
-        let result = generate(&fs, &java_file, options);
+        let result = generate_with_cache(&fs, &java_file, options, &mut ProjectCache::new());
         assert!(result.is_ok());
 
         let test_file_path_str = result.unwrap().test_file_path;
         let test_file_path = PathBuf::from(&test_file_path_str);
         assert!(!fs.file_exists(&test_file_path));
     }
+
+    #[test]
+    fn test_generate_with_store_saves_the_cache_unless_dry_run() {
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let store = CacheStore::new(cache_dir.path().to_path_buf());
+        let project_dir = tempfile::TempDir::new().unwrap();
+        let fs = FileSystem::new_os();
+        let java_file = project_dir.path().join("src/main/java/Foo.java");
+        fs.write_file_new(&project_dir.path().join("pom.xml"), "<project></project>\n").unwrap();
+        fs.write_file_new(&java_file, "public class Foo {}").unwrap();
+
+        let options = |dry_run| GeneratorOptions {
+            dry_run,
+            ..Default::default()
+        };
+
+        generate_with_store(&fs, &java_file, options(true), Some(&store)).unwrap();
+        assert!(!store.cache_file_path().exists());
+
+        generate_with_store(&fs, &java_file, options(false), Some(&store)).unwrap();
+        assert!(!store.load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_document_api_lists_public_methods_as_todos() {
+        let fs = FileSystem::new_memory();
+        let java_file = PathBuf::from("/src/main/java/Foo.java");
+        fs.write_file_new(
+            &java_file,
+            "public class Foo {\n    public int foo(int count) {\n        return count;\n    }\n\n    public void bar() {\n    }\n\n    private void hidden() {\n    }\n}\n",
+        )
+        .unwrap();
+
+        let options = GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            document_api: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &java_file, options, &mut ProjectCache::new()).unwrap();
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.starts_with("// TODO: test foo(int)\n// TODO: test bar()\n\nimport org.junit.jupiter.api.Test;\n"));
+        assert!(!content.contains("hidden"));
+        // The cursor still lands on the example test's TODO, not the checklist
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines[result.line_number as usize - 1].trim(), "// TODO: Implement test");
+    }
+
+    #[test]
+    fn test_custom_todo_text_replaces_todo_and_places_cursor() {
+        let fs = FileSystem::new_memory();
+        let java_file = PathBuf::from("/src/main/java/Foo.java");
+        fs.write_file_new(&java_file, "public class Foo {\n    public void bar() {\n    }\n}\n").unwrap();
+
+        let options = GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            document_api: true,
+            todo_text: Some("FIXME: add assertions".to_string()),
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &java_file, options, &mut ProjectCache::new()).unwrap();
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(!content.contains("Implement test"));
+        // The checklist keeps its own TODOs; the cursor goes to the configured text
+        assert!(content.starts_with("// TODO: test bar()\n"));
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines[result.line_number as usize - 1].trim(), "// FIXME: add assertions");
+    }
+
+    #[test]
+    fn test_custom_todo_text_places_cursor_in_same_file_and_found_files() {
+        let options = |structure| GeneratorOptions {
+            structure,
+            todo_text: Some("FIXME: add assertions".to_string()),
+            ..Default::default()
+        };
+
+        // Same file: the offset into the appended module lands on the custom text
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let fs = FileSystem::new_os();
+        let source = temp_dir.path().join("src/lib.rs");
+        fs.write_file_new(&source, "pub fn foo() {}\n").unwrap();
+        let result = generate_with_cache(&fs, &source, options(StructureType::SameFile), &mut ProjectCache::new()).unwrap();
+        let content = fs.read_file(&source).unwrap();
+        assert_eq!(
+            content.lines().nth(result.line_number as usize - 1).map(str::trim),
+            Some("// FIXME: add assertions")
+        );
+
+        // Separate file found without tests: the cursor goes to the custom text, not line 1
+        let fs = FileSystem::new_memory();
+        let java_file = PathBuf::from("/src/main/java/Foo.java");
+        fs.write_file_new(&java_file, "public class Foo {}").unwrap();
+        fs.write_file_new(
+            Path::new("/src/test/java/FooTest.java"),
+            "import org.junit.jupiter.api.Test;\n\nclass FooTest {\n    void later() {\n        // FIXME: add assertions\n    }\n}\n",
+        )
+        .unwrap();
+        let result = generate_with_cache(&fs, &java_file, options(StructureType::Maven), &mut ProjectCache::new()).unwrap();
+        assert!(!result.created);
+        assert_eq!(result.line_number, 5);
+    }
+
+    #[test]
+    fn test_todo_text_from_rc_with_hash_comments() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("mix.exs"), "defmodule MyApp.MixProject do\nend\n").unwrap();
+        std::fs::write(root.join(".testsmithrc"), r#"{ "todo_text": "A FAIRE: tester" }"#).unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("lib/my_app/parser.ex");
+        fs.write_file_new(&source, "defmodule MyApp.Parser do\nend\n").unwrap();
+
+        let options = |todo_text: Option<&str>| GeneratorOptions {
+            dry_run: true,
+            todo_text: todo_text.map(str::to_string),
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options(None), &mut ProjectCache::new()).unwrap();
+        let preview = result.preview_content.unwrap();
+        assert!(preview.contains("    # A FAIRE: tester\n"));
+        assert_eq!(preview.lines().nth(result.line_number as usize - 1), Some("    # A FAIRE: tester"));
+
+        // The command-line option beats .testsmithrc
+        let result = generate_with_cache(&fs, &source, options(Some("TBD")), &mut ProjectCache::new()).unwrap();
+        assert!(result.preview_content.unwrap().contains("    # TBD\n"));
+
+        let result = generate_with_cache(&fs, &source, options(Some("")), &mut ProjectCache::new());
+        assert!(matches!(result, Err(TestsmithError::ConfigError { .. })));
+    }
+
+    #[test]
+    fn test_dry_run_previews_what_would_be_written() {
+        let fs = FileSystem::new_memory();
+        let java_file = PathBuf::from("/src/main/java/Foo.java");
+        fs.write_file_new(&java_file, "package com.example;\n\npublic class Foo {}").unwrap();
+
+        let options_for = |dry_run| GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            dry_run,
+            ..Default::default()
+        };
+
+        let preview = generate_with_cache(&fs, &java_file, options_for(true), &mut ProjectCache::new())
+            .unwrap()
+            .preview_content
+            .unwrap();
+        assert!(preview.contains("class FooTest"));
+
+        let result = generate_with_cache(&fs, &java_file, options_for(false), &mut ProjectCache::new()).unwrap();
+        assert_eq!(result.preview_content, None);
+        assert_eq!(fs.read_file(Path::new(&result.test_file_path)).unwrap(), preview);
+    }
+
+    #[test]
+    fn test_append_adds_test_method_to_existing_class() {
+        let fs = FileSystem::new_memory();
+        let java_file = PathBuf::from("/src/main/java/Foo.java");
+        let test_file = PathBuf::from("/src/test/java/FooTest.java");
+        fs.write_file_new(&java_file, "public class Foo {}").unwrap();
+        fs.write_file_new(
+            &test_file,
+            "import org.junit.jupiter.api.Test;\n\nclass FooTest {\n    @Test\n    void test1() {\n    }\n}\n",
+        )
+        .unwrap();
+
+        let options = GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            on_exists: OnExists::Append,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &java_file, options, &mut ProjectCache::new()).unwrap();
+        assert!(!result.created);
+
+        let content = fs.read_file(&test_file).unwrap();
+        assert_eq!(
+            content,
+            "import org.junit.jupiter.api.Test;\n\nclass FooTest {\n    @Test\n    void test1() {\n    }\n\n    @Test\n    void test2() {\n        // TODO: Implement test\n    }\n}\n"
+        );
+        assert_eq!(result.line_number, 10);
+        assert_eq!(result.column_number, 8);
+        assert_eq!(content.lines().nth(9), Some("        // TODO: Implement test"));
+    }
+
+    #[test]
+    fn test_append_dry_run_previews_the_whole_updated_file() {
+        let fs = FileSystem::new_memory();
+        let java_file = PathBuf::from("/src/main/java/Foo.java");
+        let test_file = PathBuf::from("/src/test/java/FooTest.java");
+        let original = "import org.junit.jupiter.api.Test;\n\nclass FooTest {\n    @Test\n    void test1() {\n    }\n}\n";
+        fs.write_file_new(&java_file, "public class Foo {}").unwrap();
+        fs.write_file_new(&test_file, original).unwrap();
+
+        let options = GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            on_exists: OnExists::Append,
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &java_file, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(
+            result.preview_content.as_deref(),
+            Some("import org.junit.jupiter.api.Test;\n\nclass FooTest {\n    @Test\n    void test1() {\n    }\n\n    @Test\n    void test2() {\n        // TODO: Implement test\n    }\n}\n")
+        );
+        assert_eq!(fs.read_file(&test_file).unwrap(), original);
+    }
+
+    #[test]
+    fn test_cursor_column_points_at_indented_todo() {
+        let fs = FileSystem::new_memory();
+        let java_file = PathBuf::from("/src/main/java/Foo.java");
+        fs.write_file_new(&java_file, "public class Foo {}").unwrap();
+
+        let options = GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &java_file, options, &mut ProjectCache::new()).unwrap();
+        let preview = result.preview_content.unwrap();
+        let todo_line = preview.lines().nth(result.line_number as usize - 1).unwrap();
+        assert_eq!(result.column_number as usize, todo_line.find("// TODO").unwrap());
+        assert!(result.column_number > 0);
+    }
+
+    #[test]
+    fn test_cursor_column_counts_bytes() {
+        let content = "class FooTest {\n\t\t// TODO: Implement test\n}\n";
+        assert_eq!(find_cursor_column(content, 2, method_stub::DEFAULT_TODO_TEXT), 2);
+
+        let content = "def test_foo():\n    # A FAIRE: tester\n    pass\n";
+        assert_eq!(find_cursor_column(content, 2, "A FAIRE: tester"), 4);
+
+        let content = "fn test_foo() {\n    run(); // TODO\n}\n";
+        assert_eq!(find_cursor_column(content, 2, method_stub::DEFAULT_TODO_TEXT), 11);
+
+        // Lines without a TODO (the first existing test) use their indentation
+        assert_eq!(find_cursor_column("mod tests {\n    #[test]\n}\n", 2, method_stub::DEFAULT_TODO_TEXT), 4);
+    }
+
+    #[test]
+    fn test_append_keeps_existing_imports_and_setup() {
+        let fs = FileSystem::new_memory();
+        let java_file = PathBuf::from("/src/main/java/com/acme/Foo.java");
+        let test_file = PathBuf::from("/src/test/java/com/acme/FooTest.java");
+        fs.write_file_new(&java_file, "package com.acme;\n\npublic class Foo {}").unwrap();
+        let existing = "package com.acme;\n\nimport org.junit.jupiter.api.BeforeEach;\nimport org.junit.jupiter.api.Test;\n\nclass FooTest extends BaseTest {\n    private Foo foo;\n\n    @BeforeEach\n    void setUp() {\n        foo = new Foo();\n    }\n\n    @Test\n    void test1() {\n    }\n}\n";
+        fs.write_file_new(&test_file, existing).unwrap();
+
+        let options = GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            on_exists: OnExists::Append,
+            ..Default::default()
+        };
+
+        generate_with_cache(&fs, &java_file, options, &mut ProjectCache::new()).unwrap();
+
+        let content = fs.read_file(&test_file).unwrap();
+        assert_eq!(content.matches("import org.junit.jupiter.api.Test;").count(), 1);
+        assert_eq!(content.matches("@BeforeEach").count(), 1);
+        assert!(content.starts_with(&existing[..existing.len() - 2]));
+        assert!(content.contains("class FooTest extends BaseTest {"));
+        assert!(content.contains("    @Test\n    void test2() {\n        // TODO: Implement test\n    }\n}\n"));
+    }
+
+    #[test]
+    fn test_add_java_import_only_when_missing() {
+        let wildcard = "import org.junit.jupiter.api.*;\n\nclass FooTest {\n}\n";
+        assert_eq!(add_java_import(wildcard, "org.junit.jupiter.api.Test"), wildcard);
+
+        let setup_only = "package com.acme;\n\nimport org.junit.jupiter.api.BeforeEach;\n\nclass FooTest {\n}\n";
+        assert_eq!(
+            add_java_import(setup_only, "org.junit.jupiter.api.Test"),
+            "package com.acme;\n\nimport org.junit.jupiter.api.BeforeEach;\nimport org.junit.jupiter.api.Test;\n\nclass FooTest {\n}\n"
+        );
+
+        let no_imports = "package com.acme;\n\nclass FooTest {\n}\n";
+        assert_eq!(
+            add_java_import(no_imports, "org.junit.Test"),
+            "package com.acme;\n\nimport org.junit.Test;\n\nclass FooTest {\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_append_rejected_for_non_java() {
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/project/src/foo.py");
+        fs.write_file_new(&source, "def foo():\n    pass\n").unwrap();
+
+        let options = GeneratorOptions {
+            structure: StructureType::Flat,
+            language: Some(Language::Python),
+            framework: Some(Framework::Pytest),
+            dry_run: true,
+            on_exists: OnExists::Append,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new());
+        assert!(matches!(result, Err(TestsmithError::ConfigError { .. })));
+    }
+
+    #[test]
+    fn test_append_adds_test_fn_to_existing_rust_module() {
+        let source_content = "pub fn foo() {}\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn test_foo() {\n    }\n}\n";
+        let options_for = |append_position| GeneratorOptions {
+            structure: StructureType::SameFile,
+            language: Some(Language::Rust),
+            framework: Some(Framework::Native),
+            on_exists: OnExists::Append,
+            append_position,
+            ..Default::default()
+        };
+
+        // Bottom (default): after the existing tests, before the closing brace
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/project/src/lib.rs");
+        fs.write_file_new(&source, source_content).unwrap();
+        let result =
+            generate_with_cache(&fs, &source, options_for(AppendPosition::Bottom), &mut ProjectCache::new()).unwrap();
+        assert!(!result.created);
+        let content = fs.read_file(&source).unwrap();
+        assert_eq!(
+            content,
+            "pub fn foo() {}\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn test_foo() {\n    }\n\n    #[test]\n    fn test_2() {\n        // TODO: Implement test\n    }\n}\n"
+        );
+        assert_eq!(result.line_number, 13);
+        assert_eq!(content.lines().nth(12), Some("        // TODO: Implement test"));
+
+        // Top: after the module's `use` lines, before the existing tests
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(&source, source_content).unwrap();
+        let result =
+            generate_with_cache(&fs, &source, options_for(AppendPosition::Top), &mut ProjectCache::new()).unwrap();
+        let content = fs.read_file(&source).unwrap();
+        assert_eq!(
+            content,
+            "pub fn foo() {}\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn test_2() {\n        // TODO: Implement test\n    }\n\n    #[test]\n    fn test_foo() {\n    }\n}\n"
+        );
+        assert_eq!(result.line_number, 9);
+        assert_eq!(content.lines().nth(8), Some("        // TODO: Implement test"));
+    }
+
+    #[test]
+    fn test_append_top_skips_multi_line_use_and_literal_braces() {
+        let source_content = concat!(
+            "pub fn foo() {}\n\n#[cfg(test)]\nmod tests {\n",
+            "    use super::*;\n",
+            "    use std::collections::{\n        HashMap,\n        HashSet,\n    };\n\n",
+            "    #[test]\n    fn test_foo() {\n        assert_eq!(format!(\"{}}}\", 1), \"1}\");\n        assert_ne!('}', '{');\n    }\n",
+            "}\n",
+        );
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/project/src/lib.rs");
+        fs.write_file_new(&source, source_content).unwrap();
+
+        let options_for = |append_position| GeneratorOptions {
+            structure: StructureType::SameFile,
+            language: Some(Language::Rust),
+            framework: Some(Framework::Native),
+            on_exists: OnExists::Append,
+            append_position,
+            ..Default::default()
+        };
+        generate_with_cache(&fs, &source, options_for(AppendPosition::Top), &mut ProjectCache::new()).unwrap();
+        let content = fs.read_file(&source).unwrap();
+        assert!(content.contains(
+            "        HashSet,\n    };\n\n    #[test]\n    fn test_2() {\n        // TODO: Implement test\n    }\n\n    #[test]\n    fn test_foo() {"
+        ));
+
+        // The literal braces don't end the module early, so Bottom still lands before its closing brace
+        generate_with_cache(&fs, &source, options_for(AppendPosition::Bottom), &mut ProjectCache::new()).unwrap();
+        let content = fs.read_file(&source).unwrap();
+        assert!(content.ends_with(
+            "        assert_ne!('}', '{');\n    }\n\n    #[test]\n    fn test_3() {\n        // TODO: Implement test\n    }\n}\n"
+        ));
+    }
+
+    #[test]
+    fn test_on_exists_policies_for_existing_rust_module() {
+        let source_content = "pub fn foo() {}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn test_foo() {\n    }\n}\n";
+        let source = PathBuf::from("/project/src/lib.rs");
+        let options_for = |on_exists| GeneratorOptions {
+            structure: StructureType::SameFile,
+            language: Some(Language::Rust),
+            framework: Some(Framework::Native),
+            on_exists,
+            ..Default::default()
+        };
+
+        // Skip: the module is left alone and the cursor goes to its first test
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(&source, source_content).unwrap();
+        let result = generate_with_cache(&fs, &source, options_for(OnExists::Skip), &mut ProjectCache::new()).unwrap();
+        assert!(!result.created);
+        assert_eq!(result.line_number, 5);
+        assert_eq!(fs.read_file(&source).unwrap(), source_content);
+
+        // Append: another test goes into the module
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(&source, source_content).unwrap();
+        let result = generate_with_cache(&fs, &source, options_for(OnExists::Append), &mut ProjectCache::new()).unwrap();
+        assert!(!result.created);
+        let content = fs.read_file(&source).unwrap();
+        assert_eq!(content.matches("#[test]").count(), 2);
+        assert!(content.contains("fn test_2() {"));
+
+        // Error: fails without touching the file
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(&source, source_content).unwrap();
+        let result = generate_with_cache(&fs, &source, options_for(OnExists::Error), &mut ProjectCache::new());
+        assert!(matches!(result, Err(TestsmithError::TestFileAlreadyExists { .. })));
+        assert_eq!(fs.read_file(&source).unwrap(), source_content);
+
+        // Error still lets a source without a test module get one
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(&source, "pub fn foo() {}\n").unwrap();
+        let result = generate_with_cache(&fs, &source, options_for(OnExists::Error), &mut ProjectCache::new()).unwrap();
+        assert!(result.created);
+    }
+
+    #[test]
+    fn test_generations_share_in_memory_cache() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let mut sources = Vec::new();
+        for project in ["alpha", "beta"] {
+            let source_dir = root.join(project).join("src/main/java");
+            std::fs::create_dir_all(&source_dir).unwrap();
+            std::fs::write(root.join(project).join("pom.xml"), "<project></project>\n").unwrap();
+            let source = source_dir.join("Foo.java");
+            std::fs::write(&source, "public class Foo {}\n").unwrap();
+            sources.push(source);
+        }
+
+        let fs = FileSystem::new_os();
+        let mut cache = ProjectCache::new();
+        for source in &sources {
+            let options = GeneratorOptions {
+                language: Some(Language::Java),
+                framework: Some(Framework::JUnit),
+                dry_run: true,
+                ..Default::default()
+            };
+            generate_with_cache(&fs, source, options, &mut cache).unwrap();
+        }
+
+        let mut roots: Vec<&String> = cache.keys().collect();
+        roots.sort();
+        assert_eq!(
+            roots,
+            vec![
+                root.join("alpha").to_str().unwrap(),
+                root.join("beta").to_str().unwrap(),
+            ]
+        );
+        for languages in cache.values() {
+            assert_eq!(languages.len(), 1);
+            assert_eq!(languages["Java"].framework, "JUnit");
+            assert_eq!(languages["Java"].structure, "Maven");
+        }
+    }
+
+    #[test]
+    fn test_source_path_with_dot_dot_is_normalized() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("src/main/java")).unwrap();
+        std::fs::create_dir_all(root.join("nested")).unwrap();
+        std::fs::write(root.join("pom.xml"), "<project></project>\n").unwrap();
+        std::fs::write(root.join("src/main/java/Foo.java"), "public class Foo {}\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let mut cache = ProjectCache::new();
+        let mut test_paths = Vec::new();
+        for source in [
+            root.join("src/main/java/Foo.java"),
+            root.join("src/main/java/../java/Foo.java"),
+            root.join("nested/../src/main/./java/Foo.java"),
+        ] {
+            let options = GeneratorOptions {
+                framework: Some(Framework::JUnit),
+                dry_run: true,
+                ..Default::default()
+            };
+            let result = generate_with_cache(&fs, &source, options, &mut cache).unwrap();
+            test_paths.push(result.test_file_path);
+        }
+
+        let expected = root.join("src/test/java/FooTest.java");
+        assert!(test_paths.iter().all(|path| Path::new(path) == expected));
+        let roots: Vec<&String> = cache.keys().collect();
+        assert_eq!(roots, vec![root.to_str().unwrap()]);
+    }
+
+    #[test]
+    fn test_same_file_source_path_with_dot_dot_is_normalized() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("/project/src/lib.rs"), "pub fn foo() {}\n").unwrap();
+        let source = PathBuf::from("/project/src/../src/./lib.rs");
+
+        let options = GeneratorOptions {
+            structure: StructureType::SameFile,
+            framework: Some(Framework::Native),
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(result.test_file_path, "/project/src/lib.rs");
+    }
+
+    #[test]
+    fn test_pinned_framework_survives_stale_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(
+            root.join("pom.xml"),
+            "<project><dependencies><dependency><groupId>org.junit.jupiter</groupId><artifactId>junit-jupiter</artifactId></dependency></dependencies></project>\n",
+        )
+        .unwrap();
+        let source = root.join("src/main/java/Foo.java");
+        std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+        std::fs::write(&source, "public class Foo {}\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let options = |framework: Option<Framework>, pin: bool| GeneratorOptions {
+            language: Some(Language::Java),
+            framework,
+            dry_run: true,
+            pin,
+            ..Default::default()
+        };
+
+        let mut cache = ProjectCache::new();
+        generate_with_cache(&fs, &source, options(Some(Framework::JUnit4), true), &mut cache).unwrap();
+
+        // Make the entry older than pom.xml, which would normally force re-detection
+        let entry = cache.get_mut(root.to_str().unwrap()).unwrap().get_mut("Java").unwrap();
+        assert!(entry.pinned);
+        entry.last_used = 0;
+
+        let result = generate_with_cache(&fs, &source, options(None, false), &mut cache).unwrap();
+        assert_eq!(result.framework, "JUnit4");
+
+        // Without the pin the stale entry is re-detected from pom.xml
+        let entry = cache.get_mut(root.to_str().unwrap()).unwrap().get_mut("Java").unwrap();
+        entry.pinned = false;
+        entry.last_used = 0;
+        let result = generate_with_cache(&fs, &source, options(None, false), &mut cache).unwrap();
+        assert_eq!(result.framework, "JUnit");
+    }
+
+    #[test]
+    fn test_pinned_structure_beats_existing_test() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(root.join("pom.xml"), "<project></project>\n").unwrap();
+        let source = root.join("src/main/java/Foo.java");
+        let fs = FileSystem::new_os();
+        fs.write_file_new(&source, "public class Foo {}\n").unwrap();
+        fs.write_file_new(&root.join("src/test/java/FooTest.java"), "public class FooTest {}\n").unwrap();
+
+        let options = |structure: StructureType, pin: bool| GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            structure,
+            dry_run: true,
+            pin,
+            ..Default::default()
+        };
+
+        let mut cache = ProjectCache::new();
+        generate_with_cache(&fs, &source, options(StructureType::Gradle, true), &mut cache).unwrap();
+
+        let result = generate_with_cache(&fs, &source, options(StructureType::Maven, false), &mut cache).unwrap();
+        assert_eq!(result.structure, StructureType::Gradle);
+        assert!(result.decisions.contains(&"Structure Gradle: from cache (pinned)".to_string()));
+
+        // Unpinned, the source's existing test wins over the cached structure again
+        cache.get_mut(root.to_str().unwrap()).unwrap().get_mut("Java").unwrap().pinned = false;
+        let result = generate_with_cache(&fs, &source, options(StructureType::Maven, false), &mut cache).unwrap();
+        assert_eq!(result.structure, StructureType::Maven);
+        assert!(result.decisions.contains(&"Structure Maven: follows this source's existing test".to_string()));
+    }
+
+    #[test]
+    fn test_refresh_replaces_cached_entry_with_detection() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(
+            root.join("pom.xml"),
+            "<project><dependencies><dependency><groupId>org.junit.jupiter</groupId><artifactId>junit-jupiter</artifactId></dependency></dependencies></project>\n",
+        )
+        .unwrap();
+        let source = root.join("src/main/java/Foo.java");
+        std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+        std::fs::write(&source, "public class Foo {}\n").unwrap();
+
+        // An entry from before the project migrated to JUnit 5 and Maven
+        let mut cache = ProjectCache::new();
+        cache::update_cache_entry(&mut cache, &root, "Java", &Framework::JUnit4, &StructureType::Gradle).unwrap();
+        cache::pin_cache_entry(&mut cache, &root, "Java");
+
+        let fs = FileSystem::new_os();
+        let options = |refresh: bool| GeneratorOptions {
+            language: Some(Language::Java),
+            dry_run: true,
+            refresh,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options(false), &mut cache).unwrap();
+        assert_eq!(result.framework, "JUnit4");
+
+        let result = generate_with_cache(&fs, &source, options(true), &mut cache).unwrap();
+        assert_eq!(result.framework, "JUnit");
+        assert!(result.decisions.contains(&"Cached framework/structure ignored (--refresh)".to_string()));
+
+        let entry = cache::get_cache_entry(&cache, &root, "Java").unwrap();
+        assert_eq!(entry.framework, "JUnit");
+        assert_eq!(entry.structure, "Maven");
+        assert!(!entry.pinned);
+    }
+
+    #[test]
+    fn test_existing_test_structure_is_per_source_and_not_cached() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(root.join("pyproject.toml"), "[project]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(root.join("tests")).unwrap();
+
+        let fs = FileSystem::new_os();
+        // One source tested in a src/main -> src/test mirror, one under tests/, one not tested yet
+        let mirrored = root.join("src/main/python/lexer.py");
+        let flat = root.join("src/parser.py");
+        let untested = root.join("src/cli.py");
+        for source in [&mirrored, &flat, &untested] {
+            fs.write_file_new(source, "def run():\n    pass\n").unwrap();
+        }
+        fs.write_file_new(&root.join("src/test/python/test_lexer.py"), "").unwrap();
+        fs.write_file_new(&root.join("tests/test_parser.py"), "").unwrap();
+
+        let options = || GeneratorOptions {
+            framework: Some(Framework::Unittest),
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let mut cache = ProjectCache::new();
+        let result = generate_with_cache(&fs, &mirrored, options(), &mut cache).unwrap();
+        assert_eq!(result.structure, StructureType::Maven);
+        assert_eq!(result.test_file_path, root.join("src/test/python/test_lexer.py").to_string_lossy());
+        // The project-wide structure is cached, not this source's
+        assert_eq!(cache::get_cache_entry(&cache, &root, "Python").unwrap().structure, "Flat");
+
+        let result = generate_with_cache(&fs, &flat, options(), &mut cache).unwrap();
+        assert_eq!(result.structure, StructureType::Flat);
+        assert_eq!(result.test_file_path, root.join("tests/test_parser.py").to_string_lossy());
+
+        let result = generate_with_cache(&fs, &untested, options(), &mut cache).unwrap();
+        assert_eq!(result.structure, StructureType::Flat);
+        assert!(result.decisions.contains(&"Structure Flat: from cache".to_string()));
+    }
+
+    #[test]
+    fn test_with_runner_adds_missing_unittest_main_guard() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let fs = FileSystem::new_os();
+        fs.write_file_new(&root.join("pyproject.toml"), "[project]\nname = \"app\"\n").unwrap();
+        let source = root.join("src/parser.py");
+        fs.write_file_new(&source, "def parse():\n    pass\n").unwrap();
+        let test_path = root.join("tests/test_parser.py");
+        let existing = "import unittest\n\n\nclass TestParser(unittest.TestCase):\n    pass\n";
+        fs.write_file_new(&test_path, existing).unwrap();
+
+        let options = |with_runner| GeneratorOptions {
+            structure: StructureType::Flat,
+            framework: Some(Framework::Unittest),
+            with_runner,
+            ..Default::default()
+        };
+
+        generate_with_cache(&fs, &source, options(false), &mut ProjectCache::new()).unwrap();
+        assert_eq!(fs.read_file(&test_path).unwrap(), existing);
+
+        let result = generate_with_cache(&fs, &source, options(true), &mut ProjectCache::new()).unwrap();
+        assert!(!result.created);
+        let content = fs.read_file(&test_path).unwrap();
+        assert!(content.ends_with("    pass\n\n\nif __name__ == \"__main__\":\n    unittest.main()\n"));
+
+        // Already there: nothing more is added
+        generate_with_cache(&fs, &source, options(true), &mut ProjectCache::new()).unwrap();
+        assert_eq!(fs.read_file(&test_path).unwrap(), content);
+    }
+
+    #[test]
+    fn test_with_runner_writes_index_for_js_tests_dir() {
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/project/src/cart.js");
+        fs.write_file_new(&source, "module.exports = {};\n").unwrap();
+        fs.write_file_new(Path::new("/project/src/__tests__/price.test.js"), "").unwrap();
+
+        let options = GeneratorOptions {
+            structure: StructureType::TestsDir,
+            framework: Some(Framework::Jasmine),
+            with_runner: true,
+            ..Default::default()
+        };
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(result.test_file_path, "/project/src/__tests__/cart.test.js");
+
+        assert_eq!(
+            fs.read_file(Path::new("/project/src/__tests__/index.test.js")).unwrap(),
+            "// Generated by testsmith --with-runner: loads every test in this directory\nrequire('./cart.test.js');\nrequire('./price.test.js');\n"
+        );
+    }
+
+    #[test]
+    fn test_with_runner_is_rejected_for_other_frameworks() {
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/project/src/main/java/Foo.java");
+        fs.write_file_new(&source, "public class Foo {}\n").unwrap();
+
+        let options = GeneratorOptions {
+            with_runner: true,
+            ..Default::default()
+        };
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new());
+        assert!(matches!(result, Err(TestsmithError::ConfigError { .. })));
+        assert!(!fs.file_exists(Path::new("/project/src/test/java/FooTest.java")));
+    }
+
+    #[test]
+    fn test_test_dir_keeps_package_path_and_file_name() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("app/Foo.java");
+        std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+        std::fs::write(&source, "package com.example;\n\npublic class Foo {}\n").unwrap();
+        let test_dir = temp_dir.path().join("checks");
+
+        let options = GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            dry_run: true,
+            test_dir: Some(test_dir.clone()),
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&FileSystem::new_os(), &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(
+            PathBuf::from(result.test_file_path),
+            test_dir.join("com/example/FooTest.java")
+        );
+    }
+
+    #[test]
+    fn test_test_dir_for_flat_layout() {
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/project/foo.py");
+        fs.write_file_new(&source, "def foo():\n    pass\n").unwrap();
+
+        let test_path = resolve_in_test_dir(
+            &fs,
+            &FlatResolver::new(),
+            &source,
+            Language::Python,
+            StructureType::Flat,
+            Path::new("/project/tests"),
+        )
+        .unwrap();
+        assert_eq!(test_path, PathBuf::from("/project/tests/test_foo.py"));
+    }
+
+    #[test]
+    fn test_test_dir_rejected_for_same_file() {
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/project/src/lib.rs");
+        fs.write_file_new(&source, "pub fn foo() {}\n").unwrap();
+
+        let options = GeneratorOptions {
+            structure: StructureType::SameFile,
+            language: Some(Language::Rust),
+            framework: Some(Framework::Native),
+            dry_run: true,
+            test_dir: Some(PathBuf::from("/project/tests")),
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new());
+        assert!(matches!(result, Err(TestsmithError::ConfigError { .. })));
+    }
+
+    #[test]
+    fn test_same_file_appends_test_module() {
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/project/src/lib.rs");
+        fs.write_file_new(&source, "pub fn foo() {}\n\npub fn bar() {}\n").unwrap();
+
+        let options = GeneratorOptions {
+            structure: StructureType::SameFile,
+            language: Some(Language::Rust),
+            framework: Some(Framework::Native),
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        let content = fs.read_file(&source).unwrap();
+        assert!(content.starts_with("pub fn foo() {}\n\npub fn bar() {}\n"));
+        assert!(content.contains("#[cfg(test)]"));
+        let todo_line = content.lines().position(|line| line.contains("// TODO")).unwrap() + 1;
+        assert!(todo_line > 3);
+        assert!(result.line_number >= 4);
+    }
+
+    #[test]
+    fn test_result_reports_structure_and_resolver() {
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/project/src/lib.rs");
+        fs.write_file_new(&source, "pub fn foo() {}\n\npub fn bar() {}\n").unwrap();
+
+        let options = GeneratorOptions {
+            structure: StructureType::SameFile,
+            language: Some(Language::Rust),
+            framework: Some(Framework::Native),
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(result.structure, StructureType::SameFile);
+        assert_eq!(result.resolver_name, "Same File");
+
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"structure\":\"same-file\",\"resolver_name\":\"Same File\""));
+    }
+
+    #[test]
+    fn test_rust_test_imports_crate_or_super_by_structure() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"my-crate\"\nversion = \"0.1.0\"\n").unwrap();
+        std::fs::create_dir_all(root.join("tests")).unwrap();
+
+        let fs = FileSystem::new_os();
+        let parser = root.join("src/parser.rs");
+        let lexer = root.join("src/lexer.rs");
+        fs.write_file_new(&parser, "pub fn parse() {}\n").unwrap();
+        fs.write_file_new(&lexer, "pub fn lex() {}\n").unwrap();
+
+        let options_for = |structure| GeneratorOptions {
+            structure,
+            ..Default::default()
+        };
+
+        // A separate test file is an integration test that imports the crate by its package name
+        let result = generate_with_cache(&fs, &parser, options_for(StructureType::Flat), &mut ProjectCache::new()).unwrap();
+        assert_eq!(PathBuf::from(&result.test_file_path), root.join("tests/parser_test.rs"));
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.starts_with("use my_crate::*;\n\n#[test]\nfn test_example() {\n"));
+        assert!(!content.contains("use super::*;"));
+
+        // A same-file `mod tests` still reaches the source through `super`
+        generate_with_cache(&fs, &lexer, options_for(StructureType::SameFile), &mut ProjectCache::new()).unwrap();
+        let content = fs.read_file(&lexer).unwrap();
+        assert!(content.contains("#[cfg(test)]\nmod tests {\n    use super::*;\n"));
+        assert!(!content.contains("use my_crate::*;"));
+    }
+
+    #[test]
+    fn test_zig_same_file_appends_test_block_then_finds_it() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let fs = FileSystem::new_os();
+        let source = temp_dir.path().join("src/math.zig");
+        fs.write_file_new(&source, "pub fn add(a: i32, b: i32) i32 {\n    return a + b;\n}\n").unwrap();
+
+        let options = || GeneratorOptions {
+            structure: StructureType::SameFile,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options(), &mut ProjectCache::new()).unwrap();
+        assert!(result.created);
+        assert_eq!(result.framework, "Native");
+        let content = fs.read_file(&source).unwrap();
+        assert!(content.starts_with(
+            "pub fn add(a: i32, b: i32) i32 {\n    return a + b;\n}\ntest \"example\" {\n    // TODO: Implement test\n}\n"
+        ));
+        assert_eq!(result.line_number, 5);
+
+        // The test block now exists, so the second run just points at it
+        let result = generate_with_cache(&fs, &source, options(), &mut ProjectCache::new()).unwrap();
+        assert!(!result.created);
+        assert_eq!(result.line_number, 4);
+        assert_eq!(fs.read_file(&source).unwrap(), content);
+    }
+
+    #[test]
+    fn test_same_file_inserts_test_module_mid_file() {
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/project/src/lib.rs");
+        fs.write_file_new(&source, "pub fn foo() {}\n\npub fn bar() {}\n").unwrap();
+
+        let options = GeneratorOptions {
+            structure: StructureType::SameFile,
+            language: Some(Language::Rust),
+            framework: Some(Framework::Native),
+            insert_at_line: Some(2),
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        let content = fs.read_file(&source).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines[0], "pub fn foo() {}");
+        assert_eq!(lines[1], "#[cfg(test)]");
+        assert_eq!(lines.last(), Some(&"pub fn bar() {}"));
+        let todo_line = lines.iter().position(|line| line.contains("// TODO")).unwrap() + 1;
+        assert_eq!(result.line_number, todo_line as i32);
+    }
+
+    #[test]
+    fn test_same_file_finds_feature_gated_test_module() {
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/project/src/lib.rs");
+        let original = "pub fn foo() {}\n\n#[cfg(all(test, feature = \"x\"))]\nmod tests {\n    #[test]\n    fn it_works() {}\n}\n";
+        fs.write_file_new(&source, original).unwrap();
+
+        let options = GeneratorOptions {
+            structure: StructureType::SameFile,
+            language: Some(Language::Rust),
+            framework: Some(Framework::Native),
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert!(!result.created);
+        assert_eq!(result.line_number, 5);
+        assert_eq!(fs.read_file(&source).unwrap(), original);
+    }
+
+    #[test]
+    fn test_insert_at_line_rejected_for_separate_files() {
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/project/src/main/java/Foo.java");
+        fs.write_file_new(&source, "public class Foo {}\n").unwrap();
+
+        let options = GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            dry_run: true,
+            insert_at_line: Some(1),
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new());
+        assert!(matches!(result, Err(TestsmithError::ConfigError { .. })));
+    }
+
+    #[test]
+    fn test_generated_file_follows_editorconfig() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join(".editorconfig"),
+            "root = true\n\n[*.java]\nindent_style = tab\nend_of_line = crlf\n",
+        )
+        .unwrap();
+
+        let fs = FileSystem::new_os();
+        let java_file = root.join("src/main/java/Foo.java");
+        fs.write_file_new(&java_file, "public class Foo {}").unwrap();
+
+        let options = GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &java_file, options, &mut ProjectCache::new()).unwrap();
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.contains("class FooTest {\r\n\t@Test\r\n\tvoid testExample() {\r\n\t\t// TODO"));
+        assert!(!content.contains("    "));
+        assert_eq!(result.line_number, 7);
+    }
+
+    #[test]
+    fn test_dry_run_with_prepare_dirs_creates_directory_only() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let fs = FileSystem::new_os();
+        let java_file = temp_dir.path().join("src/main/java/com/example/Foo.java");
+        fs.write_file_new(&java_file, "package com.example;\n\npublic class Foo {}").unwrap();
+
+        let options = GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            dry_run: true,
+            prepare_dirs: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &java_file, options, &mut ProjectCache::new()).unwrap();
+        let test_file_path = PathBuf::from(&result.test_file_path);
+        assert!(temp_dir.path().join("src/test/java/com/example").is_dir());
+        assert!(!test_file_path.exists());
+    }
+
+    #[test]
+    fn test_cpp_source_gets_googletest_file_in_test_dir() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("CMakeLists.txt"), "find_package(GTest REQUIRED)\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("src/foo.cpp");
+        fs.write_file_new(&source, "int foo() { return 1; }\n").unwrap();
+
+        let options = GeneratorOptions::default();
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(PathBuf::from(&result.test_file_path), root.join("test/foo_test.cpp"));
+        assert_eq!(result.framework, "GoogleTest");
+
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.contains("#include <gtest/gtest.h>"));
+        assert!(content.contains("TEST(FooTest, Example) {"));
+    }
+
+    #[test]
+    fn test_elixir_source_gets_exunit_script_in_test_dir() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("mix.exs"), "defmodule MyApp.MixProject do\nend\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("lib/my_app/parser.ex");
+        fs.write_file_new(&source, "defmodule MyApp.Parser do\n  def parse(input), do: input\nend\n").unwrap();
+
+        let options = GeneratorOptions::default();
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(PathBuf::from(&result.test_file_path), root.join("test/my_app/parser_test.exs"));
+        assert_eq!(result.framework, "ExUnit");
+        assert_eq!(result.line_number, 5);
+
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.starts_with("defmodule MyApp.ParserTest do\n  use ExUnit.Case\n"));
+    }
+
+    #[test]
+    fn test_php_source_gets_phpunit_class_in_tests_dir() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("composer.json"), r#"{"require-dev": {"phpunit/phpunit": "^11.0"}}"#).unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("src/Http/Client.php");
+        fs.write_file_new(&source, "<?php\n\nnamespace App\\Http;\n\nclass Client {}\n").unwrap();
+
+        let options = GeneratorOptions::default();
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(PathBuf::from(&result.test_file_path), root.join("tests/Http/ClientTest.php"));
+        assert_eq!(result.framework, "PHPUnit");
+
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.contains("namespace Tests\\App\\Http;\n"));
+        assert!(content.contains("use PHPUnit\\Framework\\TestCase;"));
+        assert!(content.contains("class ClientTest extends TestCase"));
+    }
+
+    #[test]
+    fn test_csharp_source_gets_xunit_class_in_tests_dir() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("MyApp.csproj"), "<Project Sdk=\"Microsoft.NET.Sdk\" />\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("src/Http/Client.cs");
+        fs.write_file_new(&source, "namespace MyApp.Http;\n\npublic class Client {}\n").unwrap();
+
+        let options = GeneratorOptions::default();
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(PathBuf::from(&result.test_file_path), root.join("tests/Http/ClientTests.cs"));
+        assert_eq!(result.framework, "XUnit");
+
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.starts_with("using Xunit;\n"));
+        assert!(content.contains("public class ClientTests\n"));
+        assert!(content.contains("    [Fact]\n    public void TestExample()"));
+    }
+
+    #[test]
+    fn test_dart_test_imports_source_by_package_name() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("pubspec.yaml"), "name: shopping_cart\n\ndev_dependencies:\n  test: ^1.24.0\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("lib/src/cart.dart");
+        fs.write_file_new(&source, "class Cart {}\n").unwrap();
+
+        let options = GeneratorOptions {
+            structure: StructureType::Flat,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(PathBuf::from(&result.test_file_path), root.join("test/src/cart_test.dart"));
+
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.starts_with(
+            "import 'package:shopping_cart/src/cart.dart';\nimport 'package:test/test.dart';\n"
+        ));
+    }
+
+    #[test]
+    fn test_rc_maps_framework_per_file_extension() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("package.json"), r#"{"devDependencies": {"jest": "^29.0.0"}}"#).unwrap();
+        std::fs::write(
+            root.join(".testsmithrc"),
+            r#"{ "framework": { "js": "mocha", "ts": "jasmine" }, "structure": "adjacent" }"#,
+        )
+        .unwrap();
+
+        let fs = FileSystem::new_os();
+        let mut cache = ProjectCache::new();
+        for (file, framework) in [("src/cart.js", "Mocha"), ("src/price.ts", "Jasmine")] {
+            let source = root.join(file);
+            fs.write_file_new(&source, "export {};\n").unwrap();
+
+            let options = GeneratorOptions {
+                dry_run: true,
+                ..Default::default()
+            };
+
+            let result = generate_with_cache(&fs, &source, options, &mut cache).unwrap();
+            assert_eq!(result.framework, framework);
+            assert!(result.decisions.contains(&format!("Framework {}: from .testsmithrc", framework)));
+        }
+    }
+
+    #[test]
+    fn test_ruby_source_gets_rspec_spec_in_spec_dir() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("Gemfile"), "source 'https://rubygems.org'\n\ngem 'rspec'\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("lib/my_gem/parser.rb");
+        fs.write_file_new(&source, "module MyGem\n  class Parser\n  end\nend\n").unwrap();
+
+        let options = GeneratorOptions::default();
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(PathBuf::from(&result.test_file_path), root.join("spec/my_gem/parser_spec.rb"));
+        assert_eq!(result.framework, "RSpec");
+        assert_eq!(result.line_number, 5);
+
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.contains("RSpec.describe MyGem::Parser do\n"));
+    }
+
+    #[test]
+    fn test_haskell_source_gets_hspec_spec_module() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("parser.cabal"), "cabal-version: 2.4\nname: parser\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("src/Data/Parser.hs");
+        fs.write_file_new(&source, "module Data.Parser (parse) where\n\nparse :: String -> Int\nparse = length\n").unwrap();
+
+        let options = GeneratorOptions::default();
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(PathBuf::from(&result.test_file_path), root.join("test/Data/ParserSpec.hs"));
+        assert_eq!(result.framework, "HSpec");
+        assert_eq!(result.line_number, 10);
+
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.starts_with("module Data.ParserSpec where\n"));
+        assert!(content.contains("import Data.Parser\n"));
+    }
+
+    #[test]
+    fn test_package_and_module_names_are_read_through_the_file_system() {
+        // Nothing here exists on disk, so every name must come from the in-memory backend
+        let fs = FileSystem::new_memory();
+        let cases = [
+            ("/app/src/main/kotlin/Foo.kt", Language::Kotlin, StructureType::Maven, "package com.example.kt\n\nclass Foo\n", "package com.example.kt\n"),
+            ("/app/server/handler.go", Language::Go, StructureType::Adjacent, "package server\n\nfunc Handle() {}\n", "package server\n"),
+            ("/app/lib/parser.ex", Language::Elixir, StructureType::Flat, "defmodule MyApp.Parser do\nend\n", "defmodule MyApp.ParserTest do\n"),
+            ("/app/src/main/scala/Foo.scala", Language::Scala, StructureType::Maven, "package com.example.sc\n\nclass Foo\n", "package com.example.sc\n"),
+            ("/app/src/Data/Parser.hs", Language::Haskell, StructureType::Flat, "module Data.Parser where\n", "module Data.ParserSpec where\n"),
+        ];
+
+        for (source, language, structure, source_content, expected) in cases {
+            let source = Path::new(source);
+            fs.write_file_new(source, source_content).unwrap();
+            let options = GeneratorOptions {
+                language: Some(language),
+                structure,
+                dry_run: true,
+                ..Default::default()
+            };
+
+            let result = generate_with_cache(&fs, source, options, &mut ProjectCache::new()).unwrap();
+            let preview = result.preview_content.unwrap();
+            assert!(preview.contains(expected), "{:?}: {}", language, preview);
+        }
+    }
+
+    #[test]
+    fn test_swift_source_gets_xctest_case_in_tests_target() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("Package.swift"), "// swift-tools-version:5.9\nimport PackageDescription\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("Sources/Parser/Lexer.swift");
+        fs.write_file_new(&source, "struct Lexer {\n    func tokens() -> [String] { [] }\n}\n").unwrap();
+
+        let options = GeneratorOptions::default();
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(PathBuf::from(&result.test_file_path), root.join("Tests/ParserTests/LexerTests.swift"));
+        assert_eq!(result.framework, "XCTest");
+        assert_eq!(result.line_number, 6);
+
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.starts_with("import XCTest\n@testable import Parser\n"));
+        assert!(content.contains("final class LexerTests: XCTestCase {\n"));
+    }
+
+    #[test]
+    fn test_failing_statement_follows_each_framework_todo() {
+        let cases = [
+            (Language::Java, Framework::JUnit, "        fail(\"not implemented\");"),
+            (Language::Java, Framework::JUnit4, "        fail(\"not implemented\");"),
+            (Language::Rust, Framework::Native, "        unimplemented!();"),
+            (Language::Python, Framework::Unittest, "        self.fail(\"not implemented\")"),
+            (Language::JavaScript, Framework::Jasmine, "        expect(false).toBe(true);"),
+            (Language::Vue, Framework::Vitest, "        expect(false).toBe(true);"),
+        ];
+
+        for (language, framework, expected) in cases {
+            let context = TemplateContext::new("src/foo".into(), "test/foo".into(), language, framework);
+            let stub = crate::render_template(language, framework, &context).unwrap();
+            let statement = method_stub::failing_statement(language, framework, None).unwrap();
+
+            let failing = with_failing_statement(&stub, statement);
+            let lines: Vec<&str> = failing.lines().collect();
+            let todo = lines.iter().position(|line| line.contains(method_stub::DEFAULT_TODO_TEXT)).unwrap();
+            assert_eq!(lines[todo + 1], expected, "{:?}/{:?}", language, framework);
+            assert_eq!(failing.lines().count(), stub.lines().count() + 1);
+        }
+
+        assert_eq!(
+            method_stub::failing_statement(Language::JavaScript, Framework::Mocha, Some("chai")),
+            Some("expect.fail('not implemented');")
+        );
+        assert_eq!(
+            method_stub::failing_statement(Language::Swift, Framework::XCTest, None),
+            Some("XCTFail(\"not implemented\")")
+        );
+        assert_eq!(method_stub::failing_statement(Language::Haskell, Framework::HSpec, None), None);
+        // No template renders Pytest or Jest stubs, so there is nothing to make fail
+        assert_eq!(method_stub::failing_statement(Language::Python, Framework::Pytest, None), None);
+        assert_eq!(method_stub::failing_statement(Language::JavaScript, Framework::Jest, None), None);
+    }
+
+    #[test]
+    fn test_failing_stub_option_makes_new_test_fail() {
+        let fs = FileSystem::new_memory();
+        let source = PathBuf::from("/project/src/main/java/com/example/Foo.java");
+        fs.write_file_new(&source, "package com.example;\n\npublic class Foo {}\n").unwrap();
+
+        let options = GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            failing_stub: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.contains("        // TODO: Implement test\n        fail(\"not implemented\");\n"));
+        assert!(result.has_only_stub);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_reopened_failing_stub_is_still_only_a_stub() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let fs = FileSystem::new_os();
+        fs.write_file_new(&root.join("web/package.json"), "{\"name\": \"web\"}\n").unwrap();
+        fs.write_file_new(&root.join("web/src/cart.js"), "export function total() {}\n").unwrap();
+        fs.write_file_new(&root.join("web/src/order.js"), "export function place() {}\n").unwrap();
+        fs.write_file_new(&root.join("api/go.mod"), "module example.com/api\n").unwrap();
+        fs.write_file_new(&root.join("api/cart.go"), "package api\n\nfunc Total() int { return 0 }\n").unwrap();
+
+        for (source, language, framework) in [
+            (root.join("web/src/cart.js"), Language::JavaScript, Framework::Jasmine),
+            (root.join("web/src/order.js"), Language::JavaScript, Framework::Mocha),
+            (root.join("api/cart.go"), Language::Go, Framework::GoTest),
+        ] {
+            let options = || GeneratorOptions {
+                language: Some(language),
+                framework: Some(framework),
+                structure: StructureType::Adjacent,
+                failing_stub: true,
+                ..Default::default()
+            };
+
+            let created = generate_with_cache(&fs, &source, options(), &mut ProjectCache::new()).unwrap();
+            assert!(created.created);
+            assert!(created.has_only_stub, "{:?}", framework);
+
+            let reopened = generate_with_cache(&fs, &source, options(), &mut ProjectCache::new()).unwrap();
+            assert!(!reopened.created);
+            assert_eq!(reopened.test_file_path, created.test_file_path);
+            assert!(reopened.has_only_stub, "{:?}", framework);
+        }
+    }
+
+    #[test]
+    fn test_nested_gradle_modules_cache_their_own_framework() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(root.join(".testsmithrc"), "{}").unwrap();
+        std::fs::create_dir_all(root.join("legacy")).unwrap();
+        std::fs::create_dir_all(root.join("modern")).unwrap();
+        std::fs::write(
+            root.join("legacy/build.gradle"),
+            "testing {\n    suites {\n        test {\n            useJUnit('4.13.2')\n        }\n    }\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("modern/build.gradle"),
+            "dependencies {\n    testImplementation 'org.junit.jupiter:junit-jupiter'\n}\n",
+        )
+        .unwrap();
+
+        let fs = FileSystem::new_os();
+        let mut project_cache = ProjectCache::new();
+        let mut generate_for = |module: &str| {
+            let source = root.join(module).join("src/main/java/Foo.java");
+            if !fs.file_exists(&source) {
+                fs.write_file_new(&source, "public class Foo {}\n").unwrap();
+            }
+            let options = GeneratorOptions {
+                dry_run: true,
+                ..Default::default()
+            };
+            generate_with_cache(&fs, &source, options, &mut project_cache).unwrap()
+        };
+
+        assert_eq!(generate_for("legacy").framework, "JUnit4");
+        assert_eq!(generate_for("modern").framework, "JUnit");
+
+        // The second lookup for each module comes from its own cache entry
+        let legacy = generate_for("legacy");
+        assert_eq!(legacy.framework, "JUnit4");
+        assert!(legacy.decisions.iter().any(|decision| decision == "Framework JUnit4: from cache"));
+        assert_eq!(generate_for("modern").framework, "JUnit");
+
+        let entries = &project_cache[root.to_str().unwrap()];
+        assert_eq!(entries["Java@legacy"].framework, "JUnit4");
+        assert_eq!(entries["Java@modern"].framework, "JUnit");
+        assert!(!entries.contains_key("Java"));
+        assert!(!legacy.decisions.iter().any(|decision| decision.starts_with("Cache entry written by")));
+
+        // An entry from another testsmith version is still used, and the log says where it came from
+        project_cache.get_mut(root.to_str().unwrap()).unwrap().get_mut("Java@legacy").unwrap().written_by =
+            "0.0.1".to_string();
+        let source = root.join("legacy/src/main/java/Foo.java");
+        let options = GeneratorOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let result = generate_with_cache(&fs, &source, options, &mut project_cache).unwrap();
+        assert_eq!(result.framework, "JUnit4");
+        assert!(result.decisions.iter().any(|decision| decision.starts_with("Cache entry written by testsmith 0.0.1")));
+    }
+
+    #[test]
+    fn test_android_unit_and_instrumented_test_dirs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("app/src/main/java/com/example")).unwrap();
+        std::fs::write(root.join("app/build.gradle"), "plugins {\n    id 'com.android.application'\n}\n").unwrap();
+        let source = root.join("app/src/main/java/com/example/Foo.java");
+        std::fs::write(&source, "package com.example;\n\npublic class Foo {}\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let options_for = |android_instrumented| GeneratorOptions {
+            structure: StructureType::Gradle,
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit4),
+            dry_run: true,
+            android_instrumented,
+            ..Default::default()
+        };
+
+        let unit = generate_with_cache(&fs, &source, options_for(false), &mut ProjectCache::new()).unwrap();
+        assert_eq!(
+            PathBuf::from(unit.test_file_path),
+            root.join("app/src/test/java/com/example/FooTest.java")
+        );
+        assert!(unit.decisions.contains(&"Build tool: Gradle (build.gradle)".to_string()));
+
+        let instrumented = generate_with_cache(&fs, &source, options_for(true), &mut ProjectCache::new()).unwrap();
+        assert_eq!(
+            PathBuf::from(instrumented.test_file_path),
+            root.join("app/src/androidTest/java/com/example/FooTest.java")
+        );
+
+        // Without an Android plugin there is no androidTest source set to target
+        std::fs::write(root.join("app/build.gradle"), "plugins {\n    id 'java'\n}\n").unwrap();
+        let result = generate_with_cache(&fs, &source, options_for(true), &mut ProjectCache::new());
+        assert!(matches!(result, Err(TestsmithError::ConfigError { .. })));
+    }
+
+    #[test]
+    fn test_default_structure_overrides_detection() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(root.join("package.json"), "{}").unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("tests")).unwrap();
+        std::fs::write(root.join("tests/util.test.js"), "test('util', () => {});\n").unwrap();
+        let source = root.join("src/cart.js");
+        std::fs::write(&source, "export function total() {}\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let options_for = |defaults| GeneratorOptions {
+            framework: Some(Framework::Mocha),
+            dry_run: true,
+            defaults,
+            ..Default::default()
+        };
+
+        // The existing tests/ directory makes detection pick the flat layout
+        let detected = generate_with_cache(&fs, &source, options_for(Defaults::default()), &mut ProjectCache::new()).unwrap();
+        assert_eq!(PathBuf::from(detected.test_file_path), root.join("tests/cart_test.js"));
+
+        let defaults = Defaults::parse("[structure]\njavascript = \"adjacent\"\n", Path::new("defaults.toml")).unwrap();
+        let configured = generate_with_cache(&fs, &source, options_for(defaults), &mut ProjectCache::new()).unwrap();
+        assert_eq!(PathBuf::from(configured.test_file_path), root.join("src/cart.test.js"));
+        assert!(configured.decisions.contains(&"Structure Adjacent: from defaults.toml".to_string()));
+    }
+
+    #[test]
+    fn test_php_test_namespace_prefix_from_rc() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("composer.json"), "{}").unwrap();
+        std::fs::write(root.join(".testsmithrc"), r#"{ "php_test_namespace": "" }"#).unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("src/Client.php");
+        fs.write_file_new(&source, "<?php\n\nnamespace App;\n\nclass Client {}\n").unwrap();
+
+        let options = GeneratorOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert!(result.preview_content.unwrap().starts_with("<?php\n\nnamespace App;\n"));
+    }
+
+    #[test]
+    fn test_scala_source_gets_scalatest_spec() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("build.sbt"),
+            "libraryDependencies += \"org.scalatest\" %% \"scalatest\" % \"3.2.18\" % Test\n",
+        )
+        .unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("src/main/scala/com/example/Foo.scala");
+        fs.write_file_new(&source, "package com.example\n\nclass Foo\n").unwrap();
+
+        let options = GeneratorOptions::default();
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(
+            PathBuf::from(&result.test_file_path),
+            root.join("src/test/scala/com/example/FooSpec.scala")
+        );
+        assert_eq!(result.framework, "ScalaTest");
+
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.starts_with("package com.example\n\nimport org.scalatest.funsuite.AnyFunSuite\n"));
+        assert!(content.contains("class FooSpec extends AnyFunSuite {"));
+    }
+
+    #[test]
+    fn test_property_kind_writes_jqwik_property() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("pom.xml"),
+            "<project><dependencies><dependency><groupId>net.jqwik</groupId><artifactId>jqwik</artifactId></dependency></dependencies></project>\n",
+        )
+        .unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("src/main/java/com/example/Foo.java");
+        fs.write_file_new(&source, "package com.example;\n\npublic class Foo {}\n").unwrap();
+
+        let options = GeneratorOptions {
+            test_kind: TestKind::Property,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        let content = fs.read_file(Path::new(&result.test_file_path)).unwrap();
+        assert!(content.contains("import net.jqwik.api.*;"));
+        assert!(content.contains("    @Property\n    void propExample(@ForAll int x) {"));
+        assert_eq!(result.line_number, 8);
+    }
+
+    #[test]
+    fn test_property_kind_rejected_without_library() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("src/lib.rs");
+        fs.write_file_new(&source, "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n").unwrap();
+
+        let options = GeneratorOptions {
+            structure: StructureType::SameFile,
+            test_kind: TestKind::Property,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new());
+        assert!(matches!(result, Err(TestsmithError::ConfigError { .. })));
+        assert!(!fs.read_file(&source).unwrap().contains("mod tests"));
+    }
+
+    #[test]
+    fn test_rc_marker_root_sets_framework_for_nested_module() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join(".testsmithrc"), r#"{ "framework": "junit4" }"#).unwrap();
+        std::fs::create_dir_all(root.join("services/billing")).unwrap();
+        std::fs::write(root.join("services/billing/pom.xml"), "<project></project>\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("services/billing/src/main/java/Invoice.java");
+        fs.write_file_new(&source, "public class Invoice {}\n").unwrap();
+
+        let options = GeneratorOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(result.framework, "JUnit4");
+        assert!(result.decisions.iter().any(|d| d.starts_with("Project root: ") && !d.contains("billing")));
+        assert!(result.decisions.contains(&"Framework JUnit4: from .testsmithrc".to_string()));
+        assert_eq!(
+            PathBuf::from(&result.test_file_path),
+            root.join("services/billing/src/test/java/InvoiceTest.java")
+        );
+    }
+
+    #[test]
+    fn test_rc_structure_places_cpp_test_adjacent() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("CMakeLists.txt"), "find_package(GTest REQUIRED)\n").unwrap();
+        std::fs::write(root.join(".testsmithrc"), r#"{ "structure": "adjacent" }"#).unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("src/foo.cpp");
+        fs.write_file_new(&source, "int foo() { return 1; }\n").unwrap();
+
+        let options = GeneratorOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(PathBuf::from(&result.test_file_path), root.join("src/foo.test.cpp"));
+    }
+
+    #[test]
+    fn test_new_test_follows_existing_tests_suffix() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("pom.xml"), "<project></project>").unwrap();
+        std::fs::create_dir_all(root.join("src/test/java/com/example")).unwrap();
+        std::fs::write(root.join("src/test/java/com/example/BarTests.java"), "class BarTests {}").unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("src/main/java/com/example/Foo.java");
+        fs.write_file_new(&source, "package com.example;\n\npublic class Foo {}").unwrap();
+
+        let options = GeneratorOptions {
+            framework: Some(Framework::JUnit),
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(
+            PathBuf::from(&result.test_file_path),
+            root.join("src/test/java/com/example/FooTests.java")
+        );
+    }
+
+    #[test]
+    fn test_structure_mismatch_warns_without_decision_log() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("pom.xml"), "<project></project>\n").unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("src/main/java/com/example/Foo.java");
+        fs.write_file_new(&source, "package com.example;\n\npublic class Foo {}\n").unwrap();
+        fs.write_file_new(&root.join("src/test/java/com/example/FooTest.java"), "class FooTest {}\n").unwrap();
+
+        let options = GeneratorOptions {
+            structure: StructureType::Flat,
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut ProjectCache::new()).unwrap();
+        assert_eq!(
+            result.warnings,
+            vec!["Structure Flat was chosen, but the existing test for this source suggests Maven".to_string()]
+        );
+        assert!(serde_json::to_string(&result).unwrap().contains("\"warnings\":[\"Structure Flat"));
+    }
+
+    #[test]
+    fn test_decisions_explain_cached_framework() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(root.join("pom.xml"), "<project></project>").unwrap();
+
+        let fs = FileSystem::new_os();
+        let source = root.join("src/main/java/Foo.java");
+        fs.write_file_new(&source, "public class Foo {}").unwrap();
+
+        let mut project_cache = ProjectCache::new();
+        cache::update_cache_entry(&mut project_cache, &root, "Java", &Framework::JUnit4, &StructureType::Maven).unwrap();
+        cache::pin_cache_entry(&mut project_cache, &root, "Java");
+
+        let options = GeneratorOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&fs, &source, options, &mut project_cache).unwrap();
+        assert_eq!(result.framework, "JUnit4");
+        assert!(result.decisions.contains(&"Framework JUnit4: from cache (pinned)".to_string()));
+        assert!(result.decisions.contains(&"Structure Maven: from cache (pinned)".to_string()));
+    }
+
+    #[cfg(unix)]
+    fn escaping_source(root: &Path) -> PathBuf {
+        // project/src/test is a symlink to a directory beside the project, so the
+        // mirrored test path looks like it's inside the project but isn't on disk
+        let outside = root.parent().unwrap().join("elsewhere");
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::create_dir_all(root.join("src/main/java")).unwrap();
+        std::fs::write(root.join("pom.xml"), "<project></project>").unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("src/test")).unwrap();
+        std::fs::write(root.join("src/main/java/Foo.java"), "public class Foo {}").unwrap();
+        root.join("src/main/java/Foo.java")
+    }
+
+    #[test]
+    fn test_escapes_root_resolves_paths_through_the_file_system() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("/project/pom.xml"), "<project></project>").unwrap();
+        let root = Path::new("/project");
+
+        assert!(!escapes_root(&fs, Path::new("/project/src/test/java/FooTest.java"), root));
+        assert!(!escapes_root(&fs, Path::new("project/src/test/java/FooTest.java"), root));
+        assert!(escapes_root(&fs, Path::new("/project/../elsewhere/FooTest.java"), root));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_test_path_escaping_project_root_is_refused() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().join("project");
+        let source = escaping_source(&root);
+
+        let options = GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&FileSystem::new_os(), &source, options, &mut ProjectCache::new());
+        match result {
+            Err(TestsmithError::InvalidPath { path, reason }) => {
+                assert_eq!(path, root.join("src/test/java/FooTest.java"));
+                assert!(reason.contains("outside the project root"));
+            }
+            other => panic!("expected InvalidPath, got {:?}", other),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_force_allows_test_path_outside_project_root() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = escaping_source(&temp_dir.path().join("project"));
+
+        let options = GeneratorOptions {
+            language: Some(Language::Java),
+            framework: Some(Framework::JUnit),
+            dry_run: true,
+            force: true,
+            ..Default::default()
+        };
+
+        let result = generate_with_cache(&FileSystem::new_os(), &source, options, &mut ProjectCache::new());
+        assert!(result.is_ok());
+    }
 }
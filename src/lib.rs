@@ -10,3 +10,47 @@ pub mod ffi;
 
 pub use error::TestsmithError;
 pub use file_ops::FileSystem;
+
+use crate::cli::{Framework, Language};
+use crate::template::{TemplateContext, TemplateRegistry};
+
+/// Render the test boilerplate for a language/framework without touching the filesystem,
+/// e.g. for editor code actions that insert the text themselves
+pub fn render_template(
+    language: Language,
+    framework: Framework,
+    context: &TemplateContext,
+) -> Result<String, TestsmithError> {
+    TemplateRegistry::new()
+        .get_generator(language, framework)?
+        .generate(context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template_for_java_junit() {
+        let context = TemplateContext::new(
+            "src/main/java/com/example/Foo.java".into(),
+            "src/test/java/com/example/FooTest.java".into(),
+            Language::Java,
+            Framework::JUnit,
+        )
+        .with_class_name("Foo".to_string())
+        .with_package_name("com.example".to_string());
+
+        let content = render_template(Language::Java, Framework::JUnit, &context).unwrap();
+        assert_eq!(
+            content,
+            "package com.example;\n\nimport org.junit.jupiter.api.Test;\nimport static org.junit.jupiter.api.Assertions.*;\n\nclass FooTest {\n    @Test\n    void testExample() {\n        // TODO: Implement test\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_render_template_rejects_unsupported_combination() {
+        let context = TemplateContext::new("foo.py".into(), "test_foo.py".into(), Language::Python, Framework::JUnit);
+        assert!(render_template(Language::Python, Framework::JUnit, &context).is_err());
+    }
+}
@@ -1,5 +1,9 @@
 use clap::Parser;
-use testsmith_nvim::cli::{Cli, StructureType};
+use testsmith_nvim::cache;
+use testsmith_nvim::cli::{list_combinations, value_name, Cli, Command, OnExists, OutputFormat, StructureType};
+use testsmith_nvim::config::defaults::load_defaults;
+use testsmith_nvim::config::language::detect_language;
+use testsmith_nvim::config::naming::NamingConvention;
 use testsmith_nvim::file_ops::FileSystem;
 use testsmith_nvim::generator::{generate, GeneratorOptions};
 use std::path::Path;
@@ -8,7 +12,8 @@ use std::process;
 /// Auto-detect the appropriate structure based on file extension
 fn auto_detect_structure(source_file: &Path) -> StructureType {
     match source_file.extension().and_then(|e| e.to_str()) {
-        Some("rs") => StructureType::SameFile, // Rust files use same-file structure
+        Some("rs") | Some("zig") => StructureType::SameFile, // Rust and Zig tests live in the source file
+        Some("vue") | Some("svelte") => StructureType::Adjacent, // Component tests sit next to the component
         _ => StructureType::Maven, // Default to Maven for Java and others
     }
 }
@@ -16,23 +21,102 @@ fn auto_detect_structure(source_file: &Path) -> StructureType {
 fn main() {
     let cli = Cli::parse();
 
+    if let Some(Command::Clear { project }) = cli.command {
+        match cache::clear_cache(project.as_deref()) {
+            Ok(true) => println!("Cleared cache"),
+            Ok(false) => println!("Nothing to clear"),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if cli.list {
+        print!("{}", list_combinations());
+        return;
+    }
+
+    // clap guarantees FILE is present unless --list or a subcommand was given
+    let source_file = cli.source_file.expect("FILE is required");
+
     let fs = FileSystem::new_os();
 
-    // Auto-detect structure if not explicitly provided
-    let structure = cli.structure.unwrap_or_else(|| auto_detect_structure(&cli.source_file));
+    let defaults = match load_defaults(&fs) {
+        Ok(defaults) => defaults,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    // Auto-detect structure if not explicitly provided; a default from defaults.toml is left to
+    // the generator (Maven means "detect"), where a project's .testsmithrc can still override it
+    let language = cli.language.or_else(|| detect_language(&source_file).ok());
+    let structure = match cli.structure {
+        Some(structure) => structure,
+        None if language.and_then(|language| defaults.structure_for(language)).is_some() => StructureType::Maven,
+        None => auto_detect_structure(&source_file),
+    };
 
     let options = GeneratorOptions {
         structure,
         language: cli.language,
         framework: cli.framework,
         create: cli.create,
-        dry_run: cli.dry_run,
+        dry_run: cli.dry_run || cli.list_candidates,
+        pin: cli.pin,
+        refresh: cli.refresh,
+        method_stubs: cli.method_stubs,
+        method_naming: cli.method_naming,
+        test_kind: cli.kind,
+        method_prefix: cli.method_prefix,
+        naming: NamingConvention::from_overrides(cli.test_prefix, cli.test_suffix),
+        test_dir: cli.test_dir,
+        prepare_dirs: cli.prepare_dirs,
+        force: cli.force,
+        insert_at_line: cli.insert_at_line,
+        on_exists: if cli.append { OnExists::Append } else { cli.on_exists },
+        append_position: cli.append_position,
+        document_api: cli.document_api,
+        todo_text: cli.todo_text,
+        failing_stub: cli.failing_stub,
+        parameterized: cli.parameterized,
+        android_instrumented: cli.android_instrumented,
+        section_markers: cli.section_markers,
+        with_runner: cli.with_runner,
+        defaults,
     };
 
-    match generate(&fs, &cli.source_file, options) {
+    match generate(&fs, &source_file, options) {
+        Ok(result) if cli.format == OutputFormat::Json => {
+            println!("{}", serde_json::to_string(&result).unwrap_or_default());
+        }
+        Err(e) if cli.format == OutputFormat::Json => {
+            println!("{}", serde_json::json!({ "error": e.to_string() }));
+            process::exit(1);
+        }
+        Ok(result) if cli.list_candidates => {
+            for candidate in &result.candidate_test_paths {
+                println!("{}", candidate);
+            }
+        }
         Ok(result) => {
+            for warning in &result.warnings {
+                eprintln!("warning: {}", warning);
+            }
+            if cli.verbose {
+                eprintln!("structure: {} (resolver: {})", value_name(&result.structure), result.resolver_name);
+                for decision in &result.decisions {
+                    eprintln!("decision: {}", decision);
+                }
+            }
             if result.dry_run {
                 println!("Would create test file: {}", result.test_file_path);
+                if let Some(preview) = result.preview_content {
+                    print!("\n{}", preview);
+                }
             } else if result.created {
                 println!("Created test file: {}", result.test_file_path);
             } else {
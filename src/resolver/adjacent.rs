@@ -0,0 +1,271 @@
+use crate::cli::Language;
+use crate::config::language::detect_language;
+use crate::config::naming::NamingConvention;
+use crate::error::TestsmithError;
+use crate::resolver::traits::StructureResolver;
+use std::path::{Path, PathBuf};
+
+/// Directory Jest looks in for tests beside their source
+pub const JS_TESTS_DIR: &str = "__tests__";
+
+/// Resolves test files next to their source (`Foo.vue` -> `Foo.test.ts`, `foo.go` -> `foo_test.go`),
+/// or in a subdirectory beside it (`cart.js` -> `__tests__/cart.test.js`)
+pub struct AdjacentResolver {
+    naming: Option<NamingConvention>,
+    subdirectory: Option<String>,
+}
+
+impl AdjacentResolver {
+    pub fn new() -> Self {
+        AdjacentResolver { naming: None, subdirectory: None }
+    }
+
+    /// Create a resolver that names test files using the given convention
+    pub fn with_naming(naming: NamingConvention) -> Self {
+        AdjacentResolver { naming: Some(naming), subdirectory: None }
+    }
+
+    /// Put test files in a directory beside the source instead of next to it
+    pub fn in_subdirectory(mut self, dir: &str) -> Self {
+        self.subdirectory = Some(dir.to_string());
+        self
+    }
+
+    /// Configured naming, or the language's own convention (`_test` for Go, `.test` otherwise)
+    fn naming_for(&self, language: Option<Language>) -> NamingConvention {
+        if let Some(ref naming) = self.naming {
+            return naming.clone();
+        }
+
+        let separator = match language {
+            Some(Language::Go) => "_",
+            _ => ".",
+        };
+        NamingConvention {
+            suffix: "test".to_string(),
+            prefix: String::new(),
+            separator: separator.to_string(),
+        }
+    }
+
+    /// Extension of the test file; component files are tested from TypeScript
+    /// and C++ headers from a source file
+    fn test_extension(language: Language, source_extension: &str) -> String {
+        match (language, source_extension) {
+            (Language::Vue | Language::Svelte, _) => "ts".to_string(),
+            (Language::Cpp, "hpp") => "cpp".to_string(),
+            _ => source_extension.to_string(),
+        }
+    }
+
+    fn file_name(&self, source_path: &Path, language: Language) -> Result<String, TestsmithError> {
+        let stem = source_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| TestsmithError::InvalidPath {
+                path: source_path.to_path_buf(),
+                reason: "Filename contains invalid UTF-8".to_string(),
+            })?;
+        let extension = source_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("");
+
+        Ok(format!(
+            "{}.{}",
+            self.naming_for(Some(language)).apply(stem),
+            Self::test_extension(language, extension)
+        ))
+    }
+
+    fn transform_path(&self, source_path: &Path, language: Language) -> Result<PathBuf, TestsmithError> {
+        let file_name = self.file_name(source_path, language)?;
+        Ok(match self.subdirectory {
+            Some(ref dir) => source_path.with_file_name(dir).join(file_name),
+            None => source_path.with_file_name(file_name),
+        })
+    }
+}
+
+impl Default for AdjacentResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StructureResolver for AdjacentResolver {
+    fn resolve_test_path(
+        &self,
+        fs: &crate::file_ops::FileSystem,
+        source_path: &Path,
+        language: Language,
+    ) -> Result<PathBuf, TestsmithError> {
+        if !fs.file_exists(source_path) {
+            return Err(TestsmithError::FileNotFound {
+                path: source_path.to_path_buf(),
+            });
+        }
+
+        self.transform_path(source_path, language)
+    }
+
+    /// JavaScript and TypeScript tests sit either next to the source or in a sibling `__tests__/`
+    /// directory; the configured place comes first
+    fn candidate_test_paths(
+        &self,
+        fs: &crate::file_ops::FileSystem,
+        source_path: &Path,
+        language: Language,
+    ) -> Vec<PathBuf> {
+        let Ok(preferred) = self.resolve_test_path(fs, source_path, language) else {
+            return Vec::new();
+        };
+
+        match (language, preferred.file_name()) {
+            (Language::JavaScript | Language::TypeScript, Some(file_name)) => {
+                let other = match self.subdirectory {
+                    Some(_) => source_path.with_file_name(file_name),
+                    None => source_path.with_file_name(JS_TESTS_DIR).join(file_name),
+                };
+                vec![preferred, other]
+            }
+            _ => vec![preferred],
+        }
+    }
+
+    fn test_file_name(&self, source_path: &Path, language: Language) -> Result<String, TestsmithError> {
+        self.file_name(source_path, language)
+    }
+
+    fn is_source_path(&self, path: &Path) -> bool {
+        !self.is_test_path(path)
+    }
+
+    fn is_test_path(&self, path: &Path) -> bool {
+        let naming = self.naming_for(detect_language(path).ok());
+        path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| naming.matches(stem))
+    }
+
+    fn name(&self) -> &'static str {
+        match self.subdirectory {
+            Some(_) => "Tests Dir",
+            None => "Adjacent",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_ops::FileSystem;
+
+    #[test]
+    fn test_vue_component_maps_to_adjacent_ts_test() {
+        let resolver = AdjacentResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("src/components/Foo.vue"), Language::Vue)
+            .unwrap();
+        assert_eq!(test_path, Path::new("src/components/Foo.test.ts"));
+    }
+
+    #[test]
+    fn test_svelte_component_maps_to_adjacent_ts_test() {
+        let resolver = AdjacentResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("src/lib/Button.svelte"), Language::Svelte)
+            .unwrap();
+        assert_eq!(test_path, Path::new("src/lib/Button.test.ts"));
+    }
+
+    #[test]
+    fn test_cpp_source_maps_to_adjacent_test_cpp() {
+        let resolver = AdjacentResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("src/foo.cpp"), Language::Cpp)
+            .unwrap();
+        assert_eq!(test_path, Path::new("src/foo.test.cpp"));
+
+        let header_test_path = resolver
+            .transform_path(Path::new("src/foo.hpp"), Language::Cpp)
+            .unwrap();
+        assert_eq!(header_test_path, Path::new("src/foo.test.cpp"));
+    }
+
+    #[test]
+    fn test_is_test_path() {
+        let resolver = AdjacentResolver::new();
+        assert!(resolver.is_test_path(Path::new("src/Foo.test.ts")));
+        assert!(!resolver.is_test_path(Path::new("src/Foo.vue")));
+    }
+
+    #[test]
+    fn test_go_source_maps_to_underscore_test() {
+        let resolver = AdjacentResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("pkg/server/handler.go"), Language::Go)
+            .unwrap();
+        assert_eq!(test_path, Path::new("pkg/server/handler_test.go"));
+        assert!(resolver.is_test_path(&test_path));
+        assert!(!resolver.is_test_path(Path::new("pkg/server/handler.go")));
+    }
+
+    #[test]
+    fn test_is_test_path_cpp() {
+        let resolver = AdjacentResolver::new();
+        assert!(resolver.is_test_path(Path::new("src/foo.test.cpp")));
+        assert!(!resolver.is_test_path(Path::new("src/foo.cpp")));
+        assert!(resolver.is_source_path(Path::new("src/foo.cpp")));
+    }
+
+    #[test]
+    fn test_js_candidates_include_tests_dir() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("/app/src/cart.js"), "").unwrap();
+
+        let resolver = AdjacentResolver::new();
+        let candidates = resolver.candidate_test_paths(&fs, Path::new("/app/src/cart.js"), Language::JavaScript);
+        assert_eq!(
+            candidates,
+            vec![
+                PathBuf::from("/app/src/cart.test.js"),
+                PathBuf::from("/app/src/__tests__/cart.test.js"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tests_dir_puts_the_test_beside_the_source() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("/app/src/cart.js"), "").unwrap();
+
+        let resolver = AdjacentResolver::new().in_subdirectory(JS_TESTS_DIR);
+        let candidates = resolver.candidate_test_paths(&fs, Path::new("/app/src/cart.js"), Language::JavaScript);
+        assert_eq!(
+            candidates,
+            vec![
+                PathBuf::from("/app/src/__tests__/cart.test.js"),
+                PathBuf::from("/app/src/cart.test.js"),
+            ]
+        );
+        assert!(resolver.is_test_path(&candidates[0]));
+        assert_eq!(resolver.name(), "Tests Dir");
+    }
+
+    #[test]
+    fn test_go_has_single_candidate() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("/app/server/handler.go"), "").unwrap();
+
+        let resolver = AdjacentResolver::new();
+        let candidates = resolver.candidate_test_paths(&fs, Path::new("/app/server/handler.go"), Language::Go);
+        assert_eq!(candidates, vec![PathBuf::from("/app/server/handler_test.go")]);
+    }
+
+    #[test]
+    fn test_resolver_name() {
+        let resolver = AdjacentResolver::new();
+        assert_eq!(resolver.name(), "Adjacent");
+    }
+}
@@ -0,0 +1,482 @@
+use crate::cli::Language;
+use crate::config::language::detect_language;
+use crate::config::naming::NamingConvention;
+use crate::error::TestsmithError;
+use crate::resolver::slash_path;
+use crate::resolver::traits::StructureResolver;
+use path_clean::PathClean;
+use std::path::{Component, Path, PathBuf};
+
+/// Resolves sources under `src/` (`lib/` for Elixir, Dart and Ruby, `Sources/` for Swift) to a
+/// sibling test directory
+///
+/// `src/net/socket.cpp` -> `test/net/socket_test.cpp` (or `tests/` if the project uses it)
+/// `lib/my_app/parser.ex` -> `test/my_app/parser_test.exs`
+/// `src/Http/Client.php` -> `tests/Http/ClientTest.php`
+/// `src/Services/Parser.cs` -> `tests/Services/ParserTests.cs`
+/// `lib/src/parser.dart` -> `test/src/parser_test.dart`
+/// `lib/my_gem/parser.rb` -> `spec/my_gem/parser_spec.rb`
+/// `src/Data/Parser.hs` -> `test/Data/ParserSpec.hs`
+/// `src/app/parser.py` -> `tests/app/test_parser.py`
+/// `Sources/Parser/Lexer.swift` -> `Tests/ParserTests/LexerTests.swift`
+pub struct FlatResolver {
+    naming: Option<NamingConvention>,
+}
+
+impl FlatResolver {
+    pub fn new() -> Self {
+        FlatResolver { naming: None }
+    }
+
+    /// Create a resolver that names test files using the given convention
+    pub fn with_naming(naming: NamingConvention) -> Self {
+        FlatResolver { naming: Some(naming) }
+    }
+
+    /// Naming for a language: the configured convention, else `FooTest` for PHP, `FooTests` for C#
+    /// and Swift, `foo_spec` for Ruby, `FooSpec` for Haskell, `test_foo` for Python (what unittest
+    /// and pytest discover) and `foo_test` otherwise
+    fn naming_for(&self, language: Option<Language>) -> NamingConvention {
+        if let Some(ref naming) = self.naming {
+            return naming.clone();
+        }
+
+        match language {
+            Some(Language::Php) => NamingConvention::default(),
+            Some(Language::CSharp | Language::Swift) => NamingConvention {
+                suffix: "Tests".to_string(),
+                ..NamingConvention::default()
+            },
+            Some(Language::Haskell) => NamingConvention {
+                suffix: "Spec".to_string(),
+                ..NamingConvention::default()
+            },
+            Some(Language::Ruby) => NamingConvention {
+                suffix: "spec".to_string(),
+                prefix: String::new(),
+                separator: "_".to_string(),
+            },
+            Some(Language::Python) => NamingConvention {
+                suffix: String::new(),
+                prefix: "test".to_string(),
+                separator: "_".to_string(),
+            },
+            _ => NamingConvention {
+                suffix: "test".to_string(),
+                prefix: String::new(),
+                separator: "_".to_string(),
+            },
+        }
+    }
+
+    /// Extension of the test file; C++ headers are tested from a source file,
+    /// and ExUnit only runs `.exs` scripts
+    fn test_extension(language: Language, source_extension: &str) -> String {
+        match (language, source_extension) {
+            (Language::Cpp, "hpp") => "cpp".to_string(),
+            (Language::Elixir, "ex") => "exs".to_string(),
+            _ => source_extension.to_string(),
+        }
+    }
+
+    /// Directory holding the sources that tests mirror
+    fn source_root(language: Language) -> &'static str {
+        match language {
+            Language::Elixir | Language::Dart | Language::Ruby => "lib",
+            Language::Swift => "Sources",
+            _ => "src",
+        }
+    }
+
+    /// The language's usual test directory and its alternative, if it has one
+    /// (`tests/` for PHP, C# and Python, `spec/` for Ruby, only `Tests/` for SwiftPM, `test/` otherwise)
+    fn test_dir_names(language: Language) -> (&'static str, Option<&'static str>) {
+        match language {
+            Language::Php | Language::CSharp | Language::Python => ("tests", Some("test")),
+            Language::Ruby => ("spec", Some("test")),
+            Language::Swift => ("Tests", None),
+            _ => ("test", Some("tests")),
+        }
+    }
+
+    /// Test directory next to the source root (the usual one unless only the alternative exists),
+    /// and the other directory tests may go in
+    fn test_dir_choice(project_dir: &Path, language: Language) -> (&'static str, Option<&'static str>) {
+        match Self::test_dir_names(language) {
+            (preferred, Some(other)) if project_dir.join(other).is_dir() && !project_dir.join(preferred).is_dir() => {
+                (other, Some(preferred))
+            }
+            names => names,
+        }
+    }
+
+    fn file_name(&self, source_path: &Path, language: Language) -> Result<String, TestsmithError> {
+        let stem = source_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| TestsmithError::InvalidPath {
+                path: source_path.to_path_buf(),
+                reason: "Filename contains invalid UTF-8".to_string(),
+            })?;
+        let extension = source_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("");
+
+        Ok(format!(
+            "{}.{}",
+            self.naming_for(Some(language)).apply(stem),
+            Self::test_extension(language, extension)
+        ))
+    }
+
+    /// Replace the source root directory nearest the file with the test directory
+    fn transform_path(&self, source_path: &Path, language: Language) -> Result<PathBuf, TestsmithError> {
+        let (project_dir, nested) = Self::split_at_source_root(source_path, language)?;
+        let nested = Self::test_target(&nested, language);
+
+        Ok(project_dir
+            .join(Self::test_dir_choice(&project_dir, language).0)
+            .join(nested)
+            .join(self.file_name(source_path, language)?))
+    }
+
+    /// Directory below the test root for a source's directory: SwiftPM tests live in a separate
+    /// `<Target>Tests` target (`Parser/Tokens` -> `ParserTests/Tokens`), other languages mirror it
+    fn test_target(nested: &Path, language: Language) -> PathBuf {
+        let mut components = nested.components();
+        match (language, components.next()) {
+            (Language::Swift, Some(target)) => {
+                let mut test_target = target.as_os_str().to_os_string();
+                test_target.push("Tests");
+                Path::new(&test_target).join(components.as_path())
+            }
+            _ => nested.to_path_buf(),
+        }
+    }
+
+    /// The project directory holding the source root, and the source's directory below it
+    fn split_at_source_root(source_path: &Path, language: Language) -> Result<(PathBuf, PathBuf), TestsmithError> {
+        let normalized = source_path.clean();
+        let parent = normalized.parent().unwrap_or_else(|| Path::new(""));
+        let components: Vec<Component> = parent.components().collect();
+        let source_root = Self::source_root(language);
+
+        let src_idx = components
+            .iter()
+            .rposition(|component| component.as_os_str() == source_root)
+            .ok_or_else(|| TestsmithError::InvalidPath {
+                path: source_path.to_path_buf(),
+                reason: format!("Path does not contain a '{}' directory", source_root),
+            })?;
+
+        let project_dir: PathBuf = components[..src_idx].iter().collect();
+        let nested: PathBuf = components[src_idx + 1..].iter().collect();
+        Ok((project_dir, nested))
+    }
+}
+
+impl Default for FlatResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StructureResolver for FlatResolver {
+    fn resolve_test_path(
+        &self,
+        fs: &crate::file_ops::FileSystem,
+        source_path: &Path,
+        language: Language,
+    ) -> Result<PathBuf, TestsmithError> {
+        if !fs.file_exists(source_path) {
+            return Err(TestsmithError::FileNotFound {
+                path: source_path.to_path_buf(),
+            });
+        }
+
+        self.transform_path(source_path, language)
+    }
+
+    /// The resolved test directory first, then its alternative (`test/` vs `tests/`)
+    fn candidate_test_paths(
+        &self,
+        fs: &crate::file_ops::FileSystem,
+        source_path: &Path,
+        language: Language,
+    ) -> Vec<PathBuf> {
+        let Ok(resolved) = self.resolve_test_path(fs, source_path, language) else {
+            return Vec::new();
+        };
+        let Ok((project_dir, nested)) = Self::split_at_source_root(source_path, language) else {
+            return vec![resolved];
+        };
+
+        match Self::test_dir_choice(&project_dir, language).1 {
+            Some(alternative) => {
+                let alternative = project_dir.join(alternative).join(nested).join(resolved.file_name().unwrap_or_default());
+                vec![resolved, alternative]
+            }
+            None => vec![resolved],
+        }
+    }
+
+    fn test_file_name(&self, source_path: &Path, language: Language) -> Result<String, TestsmithError> {
+        self.file_name(source_path, language)
+    }
+
+    fn is_source_path(&self, path: &Path) -> bool {
+        slash_path(path).is_some_and(|path| {
+            path.split('/')
+                .any(|segment| matches!(segment, "src" | "lib" | "Sources"))
+        })
+    }
+
+    fn is_test_path(&self, path: &Path) -> bool {
+        let in_test_dir = slash_path(path).is_some_and(|path| {
+            path.split('/')
+                .any(|segment| matches!(segment, "test" | "tests" | "spec" | "Tests"))
+        });
+        let naming = self.naming_for(detect_language(path).ok());
+        let is_test_stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| naming.matches(stem));
+        in_test_dir && is_test_stem
+    }
+
+    fn name(&self) -> &'static str {
+        "Flat"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_ops::FileSystem;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cpp_source_maps_to_test_dir() {
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("project/src/foo.cpp"), Language::Cpp)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/test/foo_test.cpp"));
+    }
+
+    #[test]
+    fn test_nested_source_keeps_subdirectories() {
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("project/src/net/socket.cc"), Language::Cpp)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/test/net/socket_test.cc"));
+    }
+
+    #[test]
+    fn test_header_maps_to_cpp_test() {
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("project/src/foo.hpp"), Language::Cpp)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/test/foo_test.cpp"));
+    }
+
+    #[test]
+    fn test_existing_tests_dir_is_used() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("tests")).unwrap();
+
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(&temp_dir.path().join("src/foo.cpp"), Language::Cpp)
+            .unwrap();
+        assert_eq!(test_path, temp_dir.path().join("tests/foo_test.cpp"));
+    }
+
+    #[test]
+    fn test_candidates_include_alternative_test_dir() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("/project/lib/my_app/parser.ex"), "").unwrap();
+
+        let resolver = FlatResolver::new();
+        let candidates =
+            resolver.candidate_test_paths(&fs, Path::new("/project/lib/my_app/parser.ex"), Language::Elixir);
+        assert_eq!(
+            candidates,
+            vec![
+                PathBuf::from("/project/test/my_app/parser_test.exs"),
+                PathBuf::from("/project/tests/my_app/parser_test.exs"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_swift_has_no_alternative_test_dir() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("/project/Sources/Parser/Lexer.swift"), "").unwrap();
+
+        let resolver = FlatResolver::new();
+        let candidates =
+            resolver.candidate_test_paths(&fs, Path::new("/project/Sources/Parser/Lexer.swift"), Language::Swift);
+        assert_eq!(candidates, vec![PathBuf::from("/project/Tests/ParserTests/LexerTests.swift")]);
+    }
+
+    #[test]
+    fn test_path_without_src_is_rejected() {
+        let resolver = FlatResolver::new();
+        let result = resolver.transform_path(Path::new("project/lib/foo.cpp"), Language::Cpp);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_elixir_source_maps_to_exs_test() {
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("project/lib/my_app/parser.ex"), Language::Elixir)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/test/my_app/parser_test.exs"));
+
+        let test_path = resolver
+            .transform_path(Path::new("project/lib/foo.ex"), Language::Elixir)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/test/foo_test.exs"));
+    }
+
+    #[test]
+    fn test_dart_source_maps_to_test_dir() {
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("project/lib/foo.dart"), Language::Dart)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/test/foo_test.dart"));
+
+        let test_path = resolver
+            .transform_path(Path::new("project/lib/src/parser.dart"), Language::Dart)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/test/src/parser_test.dart"));
+    }
+
+    #[test]
+    fn test_python_source_maps_to_test_prefixed_file() {
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("project/src/app/parser.py"), Language::Python)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/tests/app/test_parser.py"));
+        assert!(resolver.is_test_path(&test_path));
+    }
+
+    #[test]
+    fn test_ruby_source_maps_to_spec_dir() {
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("project/lib/foo.rb"), Language::Ruby)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/spec/foo_spec.rb"));
+
+        let test_path = resolver
+            .transform_path(Path::new("project/lib/my_gem/parser.rb"), Language::Ruby)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/spec/my_gem/parser_spec.rb"));
+    }
+
+    #[test]
+    fn test_ruby_test_file_name_uses_spec_suffix() {
+        let resolver = FlatResolver::new();
+        let name = resolver.test_file_name(Path::new("lib/parser.rb"), Language::Ruby).unwrap();
+        assert_eq!(name, "parser_spec.rb");
+        assert!(resolver.is_test_path(Path::new("project/spec/parser_spec.rb")));
+        assert!(!resolver.is_test_path(Path::new("project/spec/parser_test.rb")));
+    }
+
+    #[test]
+    fn test_haskell_source_maps_to_spec_module() {
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("project/src/Data/Parser.hs"), Language::Haskell)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/test/Data/ParserSpec.hs"));
+        assert!(resolver.is_test_path(&test_path));
+    }
+
+    #[test]
+    fn test_swift_source_maps_to_tests_target() {
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("project/Sources/Parser/Lexer.swift"), Language::Swift)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/Tests/ParserTests/LexerTests.swift"));
+        assert!(resolver.is_test_path(&test_path));
+        assert!(!resolver.is_test_path(Path::new("project/Sources/Parser/Lexer.swift")));
+    }
+
+    #[test]
+    fn test_swift_nested_source_keeps_subdirectories() {
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("project/Sources/Parser/Tokens/Token.swift"), Language::Swift)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/Tests/ParserTests/Tokens/TokenTests.swift"));
+    }
+
+    #[test]
+    fn test_php_source_maps_to_tests_dir() {
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("project/src/Http/Client.php"), Language::Php)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/tests/Http/ClientTest.php"));
+    }
+
+    #[test]
+    fn test_php_uses_existing_test_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("test")).unwrap();
+
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(&temp_dir.path().join("src/Foo.php"), Language::Php)
+            .unwrap();
+        assert_eq!(test_path, temp_dir.path().join("test/FooTest.php"));
+    }
+
+    #[test]
+    fn test_csharp_source_maps_to_tests_dir() {
+        let resolver = FlatResolver::new();
+        let test_path = resolver
+            .transform_path(Path::new("project/src/Services/Parser.cs"), Language::CSharp)
+            .unwrap();
+        assert_eq!(test_path, Path::new("project/tests/Services/ParserTests.cs"));
+    }
+
+    #[test]
+    fn test_csharp_test_file_name_uses_tests_suffix() {
+        let resolver = FlatResolver::new();
+        let name = resolver.test_file_name(Path::new("src/Parser.cs"), Language::CSharp).unwrap();
+        assert_eq!(name, "ParserTests.cs");
+        assert!(resolver.is_test_path(Path::new("project/tests/ParserTests.cs")));
+        assert!(!resolver.is_test_path(Path::new("project/tests/ParserTest.cs")));
+    }
+
+    #[test]
+    fn test_is_test_path() {
+        let resolver = FlatResolver::new();
+        assert!(resolver.is_test_path(Path::new("project/test/foo_test.cpp")));
+        assert!(!resolver.is_test_path(Path::new("project/src/foo.cpp")));
+        assert!(resolver.is_source_path(Path::new("project/src/foo.cpp")));
+        assert!(resolver.is_test_path(Path::new("project/test/my_app/parser_test.exs")));
+        assert!(resolver.is_source_path(Path::new("project/lib/my_app/parser.ex")));
+        assert!(resolver.is_test_path(Path::new("project/tests/FooTest.php")));
+        assert!(!resolver.is_test_path(Path::new("project/tests/foo_test.php")));
+    }
+
+    #[test]
+    fn test_is_test_path_with_backslash_separators() {
+        let resolver = FlatResolver::new();
+        assert!(resolver.is_source_path(Path::new("C:\\project\\src\\foo.cpp")));
+        assert!(resolver.is_test_path(Path::new("C:\\project\\test/foo_test.cpp")));
+    }
+}
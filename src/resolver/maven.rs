@@ -1,19 +1,98 @@
 use crate::cli::Language;
+use crate::config::language::detect_language;
+use crate::config::naming::NamingConvention;
 use crate::error::TestsmithError;
+use crate::resolver::slash_path;
 use crate::resolver::traits::StructureResolver;
 use path_clean::PathClean;
 use std::path::{Path, PathBuf};
 
-pub struct MavenResolver;
+/// Source file extensions that live under `src/<language>` in Maven/Gradle projects
+const JVM_SOURCE_EXTENSIONS: &[&str] = &["java", "kt", "scala", "groovy"];
+
+/// Source set holding unit tests (`src/test`)
+pub const UNIT_TEST_SOURCE_SET: &str = "test";
+
+/// Source set holding Android instrumented tests, which run on a device (`src/androidTest`)
+pub const ANDROID_INSTRUMENTED_SOURCE_SET: &str = "androidTest";
+
+/// Directories under `src` whose files are tests rather than sources
+const TEST_SOURCE_SETS: &[&str] = &[UNIT_TEST_SOURCE_SET, ANDROID_INSTRUMENTED_SOURCE_SET];
+
+pub struct MavenResolver {
+    naming: Option<NamingConvention>,
+    test_source_set: &'static str,
+}
 
 impl MavenResolver {
     pub fn new() -> Self {
-        MavenResolver
+        MavenResolver {
+            naming: None,
+            test_source_set: UNIT_TEST_SOURCE_SET,
+        }
+    }
+
+    /// Create a resolver that names test files using the given convention
+    pub fn with_naming(naming: NamingConvention) -> Self {
+        MavenResolver {
+            naming: Some(naming),
+            test_source_set: UNIT_TEST_SOURCE_SET,
+        }
+    }
+
+    /// Put tests in another source set, e.g. `androidTest` for `src/androidTest/java`
+    pub fn with_test_source_set(mut self, source_set: &'static str) -> Self {
+        self.test_source_set = source_set;
+        self
+    }
+
+    /// Whether a `/`-separated path lies under one of the test source sets (`src/test`, `src/androidTest`)
+    fn in_test_source_set(path_str: &str) -> bool {
+        TEST_SOURCE_SETS
+            .iter()
+            .any(|set| path_str.contains(&format!("src/{}/", set)))
     }
 
-    /// Transform a source path to test path by replacing src/main with src/test
-    /// and adding "Test" suffix to the filename
-    fn transform_path(source_path: &Path, _language: Language) -> Result<PathBuf, TestsmithError> {
+    /// Naming for a language: the configured convention, else `FooSpec` for Scala and `FooTest` otherwise
+    fn naming_for(&self, language: Option<Language>) -> NamingConvention {
+        if let Some(ref naming) = self.naming {
+            return naming.clone();
+        }
+
+        match language {
+            Some(Language::Scala) => NamingConvention {
+                suffix: "Spec".to_string(),
+                ..NamingConvention::default()
+            },
+            _ => NamingConvention::default(),
+        }
+    }
+
+    /// Replace the last occurrence of `src/main` (or `src\main`, or a mix) with `src/<source_set>`,
+    /// keeping the separator the path used there
+    fn replace_last_src_main(path_str: &str, source_set: &str) -> String {
+        // Swapping `\` for `/` keeps byte offsets, so a match in the slashed path indexes the original
+        const SRC_MAIN: &str = "src/main";
+        match path_str.replace('\\', "/").rfind(SRC_MAIN) {
+            Some(idx) => format!(
+                "{}src{}{}{}",
+                &path_str[..idx],
+                &path_str[idx + 3..idx + 4],
+                source_set,
+                &path_str[idx + SRC_MAIN.len()..]
+            ),
+            None => path_str.to_string(),
+        }
+    }
+
+    /// Transform a source path to test path by replacing src/main with src/test (or another
+    /// test source set) and applying the naming convention ("Test" suffix by default) to the filename
+    fn transform_path(
+        source_path: &Path,
+        _language: Language,
+        naming: &NamingConvention,
+        source_set: &str,
+    ) -> Result<PathBuf, TestsmithError> {
         let normalized = source_path.clean();
         let path_str = normalized
             .to_str()
@@ -23,33 +102,47 @@ impl MavenResolver {
             })?;
 
         // Check if path contains src/main
-        if !path_str.contains("src/main") && !path_str.contains("src\\main") {
+        let slashed = path_str.replace('\\', "/");
+        if !slashed.contains("src/main") {
+            // A file under src/test (or src/androidTest) is a test already, not a misplaced source
+            if Self::in_test_source_set(&slashed) {
+                return Err(TestsmithError::AlreadyTestFile {
+                    path: source_path.to_path_buf(),
+                });
+            }
+
             return Err(TestsmithError::InvalidPath {
                 path: source_path.to_path_buf(),
                 reason: "Path does not contain 'src/main' directory".to_string(),
             });
         }
 
-        // Replace src/main with src/test
-        let test_path_str = path_str
-            .replace("src/main", "src/test")
-            .replace("src\\main", "src\\test");
+        // Replace only the src/main nearest the file (monorepos may nest several)
+        let test_path_str = Self::replace_last_src_main(path_str, source_set);
 
         // Add "Test" suffix before the extension
         let path = Path::new(&test_path_str);
         let parent = path.parent().unwrap_or_else(|| Path::new(""));
-        let file_name = path
+        let mut result = parent.to_path_buf();
+        result.push(Self::file_name(source_path, naming)?);
+
+        Ok(result.clean())
+    }
+
+    /// Apply the naming convention to the source file name, keeping its extension
+    fn file_name(source_path: &Path, naming: &NamingConvention) -> Result<String, TestsmithError> {
+        let file_name_str = source_path
             .file_name()
             .ok_or_else(|| TestsmithError::InvalidPath {
                 path: source_path.to_path_buf(),
                 reason: "File has no name".to_string(),
+            })?
+            .to_str()
+            .ok_or_else(|| TestsmithError::InvalidPath {
+                path: source_path.to_path_buf(),
+                reason: "Filename contains invalid UTF-8".to_string(),
             })?;
 
-        let file_name_str = file_name.to_str().ok_or_else(|| TestsmithError::InvalidPath {
-            path: source_path.to_path_buf(),
-            reason: "Filename contains invalid UTF-8".to_string(),
-        })?;
-
         // Extract extension
         let (base_name, extension) = if let Some(dot_idx) = file_name_str.rfind('.') {
             (
@@ -60,11 +153,7 @@ impl MavenResolver {
             (file_name_str, "")
         };
 
-        let test_file_name = format!("{}Test{}", base_name, extension);
-        let mut result = parent.to_path_buf();
-        result.push(test_file_name);
-
-        Ok(result.clean())
+        Ok(format!("{}{}", naming.apply(base_name), extension))
     }
 }
 
@@ -88,21 +177,30 @@ impl StructureResolver for MavenResolver {
             });
         }
 
-        Self::transform_path(source_path, language)
+        Self::transform_path(source_path, language, &self.naming_for(Some(language)), self.test_source_set)
+    }
+
+    fn test_file_name(&self, source_path: &Path, language: Language) -> Result<String, TestsmithError> {
+        Self::file_name(source_path, &self.naming_for(Some(language)))
     }
 
     fn is_source_path(&self, path: &Path) -> bool {
-        if let Some(path_str) = path.to_str() {
-            path_str.contains("src/main") || path_str.contains("src\\main")
-        } else {
-            false
-        }
+        slash_path(path).is_some_and(|path_str| path_str.contains("src/main"))
     }
 
     fn is_test_path(&self, path: &Path) -> bool {
-        if let Some(path_str) = path.to_str() {
-            (path_str.contains("src/test") || path_str.contains("src\\test"))
-                && path_str.ends_with("Test.java")
+        if let Some(path_str) = slash_path(path) {
+            let naming = self.naming_for(detect_language(path).ok());
+            let is_test_stem = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| naming.matches(stem));
+            // Resources under src/test (e.g. FooTest.xml) are not tests
+            let is_source_file = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| JVM_SOURCE_EXTENSIONS.contains(&ext));
+            Self::in_test_source_set(&path_str) && is_test_stem && is_source_file
         } else {
             false
         }
@@ -120,17 +218,42 @@ mod tests {
     #[test]
     fn test_transform_java_path() {
         let source = Path::new("src/main/java/com/example/Foo.java");
-        let result = MavenResolver::transform_path(source, Language::Java);
+        let result = MavenResolver::transform_path(source, Language::Java, &NamingConvention::default(), UNIT_TEST_SOURCE_SET);
         assert!(result.is_ok());
         let test_path = result.unwrap();
         assert!(test_path.to_str().unwrap().contains("src/test"));
         assert!(test_path.to_str().unwrap().ends_with("FooTest.java"));
     }
 
+    #[test]
+    fn test_transform_kotlin_path() {
+        let source = Path::new("src/main/kotlin/com/example/Foo.kt");
+        let test_path = MavenResolver::transform_path(source, Language::Kotlin, &NamingConvention::default(), UNIT_TEST_SOURCE_SET).unwrap();
+        assert_eq!(test_path, Path::new("src/test/kotlin/com/example/FooTest.kt"));
+    }
+
+    #[test]
+    fn test_resolve_scala_path_swaps_source_dir_and_uses_spec_suffix() {
+        let fs = crate::file_ops::FileSystem::new_memory();
+        let source = Path::new("/project/src/main/scala/com/example/Foo.scala");
+        fs.write_file_new(source, "package com.example\n\nclass Foo\n").unwrap();
+
+        let test_path = MavenResolver::new().resolve_test_path(&fs, source, Language::Scala).unwrap();
+        assert_eq!(test_path, Path::new("/project/src/test/scala/com/example/FooSpec.scala"));
+    }
+
+    #[test]
+    fn test_scala_spec_is_test_path() {
+        let resolver = MavenResolver::new();
+        assert!(resolver.is_test_path(Path::new("src/test/scala/com/example/FooSpec.scala")));
+        assert!(!resolver.is_test_path(Path::new("src/test/scala/com/example/FooTest.scala")));
+        assert!(resolver.is_test_path(Path::new("src/test/java/com/example/FooTest.java")));
+    }
+
     #[test]
     fn test_transform_path_preserves_package() {
         let source = Path::new("src/main/java/com/example/nested/Foo.java");
-        let result = MavenResolver::transform_path(source, Language::Java);
+        let result = MavenResolver::transform_path(source, Language::Java, &NamingConvention::default(), UNIT_TEST_SOURCE_SET);
         assert!(result.is_ok());
         let test_path = result.unwrap();
         let path_str = test_path.to_str().unwrap();
@@ -141,10 +264,31 @@ mod tests {
     #[test]
     fn test_transform_invalid_path_no_src_main() {
         let source = Path::new("src/Foo.java");
-        let result = MavenResolver::transform_path(source, Language::Java);
+        let result = MavenResolver::transform_path(source, Language::Java, &NamingConvention::default(), UNIT_TEST_SOURCE_SET);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_transform_test_file_is_already_a_test() {
+        let source = Path::new("src/test/java/FooTest.java");
+        let result = MavenResolver::transform_path(source, Language::Java, &NamingConvention::default(), UNIT_TEST_SOURCE_SET);
+        assert!(matches!(result, Err(TestsmithError::AlreadyTestFile { ref path }) if path == source));
+
+        let source = Path::new("src\\test\\java\\FooTest.java");
+        let result = MavenResolver::transform_path(source, Language::Java, &NamingConvention::default(), UNIT_TEST_SOURCE_SET);
+        assert!(matches!(result, Err(TestsmithError::AlreadyTestFile { .. })));
+    }
+
+    #[test]
+    fn test_resolve_test_file_is_already_a_test() {
+        let fs = crate::file_ops::FileSystem::new_memory();
+        let source = Path::new("/project/src/test/java/com/example/FooTest.java");
+        fs.write_file_new(source, "package com.example;\n\nclass FooTest {}\n").unwrap();
+
+        let result = MavenResolver::new().resolve_test_path(&fs, source, Language::Java);
+        assert!(matches!(result, Err(TestsmithError::AlreadyTestFile { .. })));
+    }
+
     #[test]
     fn test_is_source_path() {
         let resolver = MavenResolver::new();
@@ -160,9 +304,115 @@ mod tests {
         assert!(!resolver.is_test_path(Path::new("src/test/java/Foo.java")));
     }
 
+    #[test]
+    fn test_is_test_path_kotlin_and_scala() {
+        let resolver = MavenResolver::new();
+        assert!(resolver.is_test_path(Path::new("src/test/kotlin/com/example/FooTest.kt")));
+        assert!(resolver.is_test_path(Path::new("src/test/scala/FooSpec.scala")));
+        assert!(!resolver.is_test_path(Path::new("src/main/kotlin/com/example/Foo.kt")));
+        assert!(!resolver.is_test_path(Path::new("src/test/resources/FooTest.xml")));
+    }
+
+    #[test]
+    fn test_transform_scala_path_keeps_language_dir() {
+        let source = Path::new("src/main/scala/com/example/Foo.scala");
+        let test_path = MavenResolver::transform_path(source, Language::Java, &NamingConvention::default(), UNIT_TEST_SOURCE_SET).unwrap();
+        assert_eq!(test_path, Path::new("src/test/scala/com/example/FooTest.scala"));
+    }
+
     #[test]
     fn test_resolver_name() {
         let resolver = MavenResolver::new();
         assert_eq!(resolver.name(), "Maven");
     }
+
+    #[test]
+    fn test_transform_path_with_tests_suffix() {
+        let source = Path::new("src/main/java/com/example/Foo.java");
+        let naming = NamingConvention::from_overrides(None, Some("Tests".to_string())).unwrap();
+        let test_path = MavenResolver::transform_path(source, Language::Java, &naming, UNIT_TEST_SOURCE_SET).unwrap();
+        assert_eq!(test_path, Path::new("src/test/java/com/example/FooTests.java"));
+    }
+
+    #[test]
+    fn test_transform_path_with_test_prefix() {
+        let source = Path::new("src/main/java/com/example/Foo.java");
+        let naming = NamingConvention::from_overrides(Some("Test".to_string()), None).unwrap();
+        let test_path = MavenResolver::transform_path(source, Language::Java, &naming, UNIT_TEST_SOURCE_SET).unwrap();
+        assert_eq!(test_path, Path::new("src/test/java/com/example/TestFoo.java"));
+    }
+
+    #[test]
+    fn test_is_test_path_with_prefix_naming() {
+        let naming = NamingConvention::from_overrides(Some("Test".to_string()), None).unwrap();
+        let resolver = MavenResolver::with_naming(naming);
+        assert!(resolver.is_test_path(Path::new("src/test/java/TestFoo.java")));
+        assert!(!resolver.is_test_path(Path::new("src/test/java/FooTest.java")));
+    }
+
+    #[test]
+    fn test_transform_path_nested_src_main() {
+        let source = Path::new("/repos/src/main/app/src/main/java/Foo.java");
+        let test_path =
+            MavenResolver::transform_path(source, Language::Java, &NamingConvention::default(), UNIT_TEST_SOURCE_SET).unwrap();
+        assert_eq!(test_path, Path::new("/repos/src/main/app/src/test/java/FooTest.java"));
+    }
+
+    #[test]
+    fn test_replace_last_src_main_backslashes() {
+        assert_eq!(
+            MavenResolver::replace_last_src_main("C:\\repos\\src\\main\\app\\src\\main\\java\\Foo.java", UNIT_TEST_SOURCE_SET),
+            "C:\\repos\\src\\main\\app\\src\\test\\java\\Foo.java"
+        );
+    }
+
+    #[test]
+    fn test_mixed_separators_are_treated_alike() {
+        assert_eq!(
+            MavenResolver::replace_last_src_main("C:\\repo/src\\main/java\\Foo.java", UNIT_TEST_SOURCE_SET),
+            "C:\\repo/src\\test/java\\Foo.java"
+        );
+
+        let resolver = MavenResolver::new();
+        assert!(resolver.is_source_path(Path::new("C:\\repo\\src/main\\java\\Foo.java")));
+        assert!(resolver.is_test_path(Path::new("C:\\repo\\src\\test/java\\FooTest.java")));
+    }
+
+    #[test]
+    fn test_transform_path_single_src_main_unaffected() {
+        let source = Path::new("/project/src/main/java/com/example/Foo.java");
+        let test_path =
+            MavenResolver::transform_path(source, Language::Java, &NamingConvention::default(), UNIT_TEST_SOURCE_SET).unwrap();
+        assert_eq!(test_path, Path::new("/project/src/test/java/com/example/FooTest.java"));
+    }
+
+    #[test]
+    fn test_resolve_android_unit_and_instrumented_source_sets() {
+        let fs = crate::file_ops::FileSystem::new_memory();
+        let source = Path::new("/project/app/src/main/java/com/example/Foo.java");
+        fs.write_file_new(source, "package com.example;\n\npublic class Foo {}\n").unwrap();
+
+        let unit = MavenResolver::new().resolve_test_path(&fs, source, Language::Java).unwrap();
+        assert_eq!(unit, Path::new("/project/app/src/test/java/com/example/FooTest.java"));
+
+        let instrumented = MavenResolver::new()
+            .with_test_source_set(ANDROID_INSTRUMENTED_SOURCE_SET)
+            .resolve_test_path(&fs, source, Language::Java)
+            .unwrap();
+        assert_eq!(instrumented, Path::new("/project/app/src/androidTest/java/com/example/FooTest.java"));
+    }
+
+    #[test]
+    fn test_android_instrumented_test_is_test_path() {
+        let resolver = MavenResolver::new();
+        assert!(resolver.is_test_path(Path::new("app/src/androidTest/java/com/example/FooTest.java")));
+
+        let result = MavenResolver::transform_path(
+            Path::new("app/src/androidTest/java/com/example/FooTest.java"),
+            Language::Java,
+            &NamingConvention::default(),
+            UNIT_TEST_SOURCE_SET,
+        );
+        assert!(matches!(result, Err(TestsmithError::AlreadyTestFile { .. })));
+    }
 }
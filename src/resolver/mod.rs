@@ -1,5 +1,15 @@
+pub mod adjacent;
+pub mod flat;
 pub mod maven;
 pub mod same_file;
+pub mod test_crate;
 pub mod traits;
 
 pub use traits::StructureResolver;
+
+use std::path::Path;
+
+/// A path as a string with `/` separators, so resolver checks match `src\main` and `src/main` alike
+pub(crate) fn slash_path(path: &Path) -> Option<String> {
+    path.to_str().map(|path| path.replace('\\', "/"))
+}
@@ -1,6 +1,9 @@
 use crate::cli::Language;
 use crate::error::TestsmithError;
+use crate::file_ops::FileSystem;
 use crate::resolver::traits::StructureResolver;
+use regex::Regex;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 
 pub struct SameFileResolver;
@@ -9,6 +12,174 @@ impl SameFileResolver {
     pub fn new() -> Self {
         SameFileResolver
     }
+
+    /// Whether Rust source already has a `#[cfg(...)]`-gated test module
+    /// Counts modules whose predicate mentions `test` (e.g. `#[cfg(all(test, feature = "x"))]`)
+    /// as well as cfg-gated modules that contain `#[test]` functions
+    pub fn has_test_module(content: &str) -> bool {
+        Self::test_module_body(content).is_some()
+    }
+
+    /// Whether same-file source already has tests: a test module for Rust, a `test` block for Zig
+    pub fn has_tests(content: &str, language: Language) -> bool {
+        match language {
+            Language::Zig => content.lines().any(|line| Self::is_test_line(line, language)),
+            _ => Self::has_test_module(content),
+        }
+    }
+
+    /// Whether a line starts a test: `#[test]` (or `#[rstest]`) for Rust, `test "name" {` (or `test name {`) for Zig
+    pub fn is_test_line(line: &str, language: Language) -> bool {
+        match language {
+            Language::Zig => Regex::new(r#"^\s*test\b\s*(?:"[^"]*"|[A-Za-z_]\w*)?\s*\{"#)
+                .unwrap()
+                .is_match(line),
+            _ => line.contains("#[test]") || line.contains("#[rstest]"),
+        }
+    }
+
+    /// File holding a test module declared without a body (`#[cfg(test)] mod tests;`),
+    /// found like rustc does: `tests.rs` or `tests/mod.rs` next to `lib.rs`/`main.rs`/`mod.rs`,
+    /// otherwise under the declaring file's own directory (`foo.rs` -> `foo/tests.rs`).
+    /// A `#[path = "..."]` attribute is resolved against the declaring file's directory
+    pub fn external_test_module(fs: &FileSystem, source_path: &Path, content: &str) -> Option<PathBuf> {
+        let declaration_regex = Regex::new(
+            r#"#\[cfg\((?P<predicate>[^\]]*)\)\]\s*(?P<attrs>(?:#\[[^\]]*\]\s*)*)(?:pub(?:\([^)]*\))?\s+)?mod\s+(?P<name>\w+)\s*;"#,
+        )
+        .unwrap();
+        let path_attr_regex = Regex::new(r#"#\[path\s*=\s*"(?P<path>[^"]+)"\]"#).unwrap();
+        let test_token_regex = Regex::new(r"\btest\b").unwrap();
+        let not_test_regex = Regex::new(r"not\s*\(\s*test\s*\)").unwrap();
+
+        let source_dir = source_path.parent()?;
+        let stem = source_path.file_stem()?.to_str()?;
+        let module_dir = match stem {
+            "lib" | "main" | "mod" => source_dir.to_path_buf(),
+            _ => source_dir.join(stem),
+        };
+
+        declaration_regex.captures_iter(content).find_map(|caps| {
+            let predicate = not_test_regex.replace_all(&caps["predicate"], "");
+            if !test_token_regex.is_match(&predicate) {
+                return None;
+            }
+
+            if let Some(path_attr) = path_attr_regex.captures(&caps["attrs"]) {
+                let path = source_dir.join(&path_attr["path"]);
+                return fs.file_exists(&path).then_some(path);
+            }
+
+            let name = &caps["name"];
+            [module_dir.join(format!("{}.rs", name)), module_dir.join(name).join("mod.rs")]
+                .into_iter()
+                .find(|path| fs.file_exists(path))
+        })
+    }
+
+    /// Byte range of the first test module's body, between its braces
+    pub fn test_module_body(content: &str) -> Option<Range<usize>> {
+        let module_regex = Regex::new(
+            r"#\[cfg\((?P<predicate>[^\]]*)\)\]\s*(?:#\[[^\]]*\]\s*)*(?:pub(?:\([^)]*\))?\s+)?mod\s+\w+\s*\{",
+        )
+        .unwrap();
+        let string_regex = Regex::new(r#""[^"]*""#).unwrap();
+        let not_test_regex = Regex::new(r"not\s*\(\s*test\s*\)").unwrap();
+        let test_token_regex = Regex::new(r"\btest\b").unwrap();
+
+        module_regex.captures_iter(content).find_map(|caps| {
+            let predicate = string_regex.replace_all(&caps["predicate"], "");
+            let predicate = not_test_regex.replace_all(&predicate, "");
+            let body_start = caps.get(0).unwrap().end();
+            let body = module_body(&content[body_start..]);
+
+            (test_token_regex.is_match(&predicate) || body.contains("#[test]"))
+                .then(|| body_start..body_start + body.len())
+        })
+    }
+}
+
+/// Text up to the brace closing a module whose opening brace was just consumed
+/// Braces inside string and char literals and comments don't count
+fn module_body(rest: &str) -> &str {
+    let bytes = rest.as_bytes();
+    let mut depth = 1;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &rest[..idx];
+                }
+            }
+            b'"' => idx = quoted_end(bytes, idx + 1),
+            b'r' if idx == 0 || !(bytes[idx - 1].is_ascii_alphanumeric() || bytes[idx - 1] == b'_') => {
+                idx = raw_string_end(bytes, idx)
+            }
+            b'\'' => idx = char_literal_end(rest, idx),
+            b'/' if bytes.get(idx + 1) == Some(&b'/') => {
+                idx = rest[idx..].find('\n').map_or(bytes.len(), |newline| idx + newline)
+            }
+            b'/' if bytes.get(idx + 1) == Some(&b'*') => {
+                idx = rest[idx + 2..].find("*/").map_or(bytes.len(), |end| idx + 2 + end + 1)
+            }
+            _ => {}
+        }
+        idx += 1;
+    }
+    rest
+}
+
+/// Index of the `"` closing a string whose contents start at `start`
+fn quoted_end(bytes: &[u8], start: usize) -> usize {
+    let mut idx = start;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'\\' => idx += 1,
+            b'"' => return idx,
+            _ => {}
+        }
+        idx += 1;
+    }
+    bytes.len()
+}
+
+/// Index of the last byte of a raw string (`r"…"`, `r#"…"#`) starting at `start`,
+/// or `start` itself if the `r` begins something else (an identifier, `r#ident`)
+fn raw_string_end(bytes: &[u8], start: usize) -> usize {
+    let hashes = bytes[start + 1..].iter().take_while(|&&byte| byte == b'#').count();
+    let open = start + 1 + hashes;
+    if bytes.get(open) != Some(&b'"') {
+        return start;
+    }
+
+    let mut idx = open + 1;
+    while idx < bytes.len() {
+        if bytes[idx] == b'"' && bytes[idx + 1..].iter().take(hashes).filter(|&&byte| byte == b'#').count() == hashes {
+            return idx + hashes;
+        }
+        idx += 1;
+    }
+    bytes.len()
+}
+
+/// Index of the `'` closing a char literal starting at `start` (`'{'`, `'\u{7d}'`),
+/// or `start` itself for a lifetime (`'a`)
+fn char_literal_end(rest: &str, start: usize) -> usize {
+    let after = &rest[start + 1..];
+    if let Some(escaped) = after.strip_prefix('\\') {
+        // The escaped character itself may be a quote (`'\''`)
+        return escaped
+            .get(1..)
+            .and_then(|tail| tail.find('\''))
+            .map_or(rest.len(), |close| start + 3 + close);
+    }
+
+    match after.chars().next() {
+        Some(ch) if after[ch.len_utf8()..].starts_with('\'') => start + 1 + ch.len_utf8(),
+        _ => start,
+    }
 }
 
 impl Default for SameFileResolver {
@@ -25,7 +196,7 @@ impl StructureResolver for SameFileResolver {
         _language: Language,
     ) -> Result<PathBuf, TestsmithError> {
         // For same-file structure, the test path is the same as the source path
-        // Tests are appended to the same file using #[cfg(test)] mod tests {}
+        // Tests are appended to the same file (#[cfg(test)] mod tests {} for Rust, test blocks for Zig)
 
         if !fs.file_exists(source_path) {
             return Err(TestsmithError::FileNotFound {
@@ -36,6 +207,17 @@ impl StructureResolver for SameFileResolver {
         Ok(source_path.to_path_buf())
     }
 
+    fn test_file_name(&self, source_path: &Path, _language: Language) -> Result<String, TestsmithError> {
+        source_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.to_string())
+            .ok_or_else(|| TestsmithError::InvalidPath {
+                path: source_path.to_path_buf(),
+                reason: "Filename contains invalid UTF-8".to_string(),
+            })
+    }
+
     fn is_source_path(&self, _path: &Path) -> bool {
         // In same-file structure, we don't distinguish source from test paths
         true
@@ -70,4 +252,117 @@ mod tests {
         let resolver = SameFileResolver::new();
         assert_eq!(resolver.name(), "Same File");
     }
+
+    #[test]
+    fn test_has_test_module_plain_cfg_test() {
+        assert!(SameFileResolver::has_test_module(
+            "pub fn foo() {}\n\n#[cfg(test)]\nmod my_tests {\n    use super::*;\n}\n"
+        ));
+    }
+
+    #[test]
+    fn test_has_test_module_feature_gated() {
+        assert!(SameFileResolver::has_test_module(
+            "pub fn foo() {}\n\n#[cfg(all(test, feature = \"x\"))]\nmod tests {\n    #[test]\n    fn it_works() {}\n}\n"
+        ));
+        assert!(SameFileResolver::has_test_module(
+            "#[cfg(all(\n    test,\n    feature = \"x\",\n))]\n#[allow(unused)]\npub(crate) mod tests {}\n"
+        ));
+    }
+
+    #[test]
+    fn test_has_test_module_cfg_gated_module_with_test_functions() {
+        assert!(SameFileResolver::has_test_module(
+            "#[cfg(feature = \"slow\")]\nmod slow {\n    #[test]\n    fn big() {}\n}\n"
+        ));
+    }
+
+    #[test]
+    fn test_has_test_module_ignores_non_test_modules() {
+        assert!(!SameFileResolver::has_test_module("pub fn foo() {}\n"));
+        assert!(!SameFileResolver::has_test_module(
+            "#[cfg(not(test))]\nmod real {\n    pub fn foo() {}\n}\n"
+        ));
+        assert!(!SameFileResolver::has_test_module(
+            "#[cfg(feature = \"test\")]\nmod helpers {\n    pub fn foo() {}\n}\n"
+        ));
+    }
+
+    #[test]
+    fn test_module_body_ignores_braces_in_literals_and_comments() {
+        let content = concat!(
+            "#[cfg(test)]\nmod tests {\n",
+            "    const OPEN: &str = \"{ \\\" {\";\n",
+            "    const RAW: &str = r#\"}\"#;\n",
+            "    const CLOSE: char = '}';\n",
+            "    const ESCAPED: char = '\\u{7d}';\n",
+            "    const QUOTE: char = '\\'';\n",
+            "    fn borrow<'a>(s: &'a str) -> &'a str { s } // }\n",
+            "    /* } */\n",
+            "}\n",
+            "fn after() {}\n",
+        );
+        let body = SameFileResolver::test_module_body(content).unwrap();
+        assert!(content[body.clone()].ends_with("/* } */\n"));
+        assert_eq!(&content[body.end..], "}\nfn after() {}\n");
+    }
+
+    #[test]
+    fn test_has_tests_zig_test_blocks() {
+        assert!(SameFileResolver::has_tests(
+            "pub fn add(a: i32, b: i32) i32 {\n    return a + b;\n}\n\ntest \"add works\" {\n}\n",
+            Language::Zig
+        ));
+        assert!(SameFileResolver::has_tests("test add {\n}\n", Language::Zig));
+        assert!(SameFileResolver::has_tests("test {\n}\n", Language::Zig));
+        assert!(!SameFileResolver::has_tests(
+            "const testing = @import(\"std\").testing;\npub fn tester() void {}\n",
+            Language::Zig
+        ));
+        // A Rust test module doesn't count as Zig tests, nor the other way round
+        assert!(!SameFileResolver::has_tests("#[cfg(test)]\nmod tests {}\n", Language::Zig));
+        assert!(!SameFileResolver::has_tests("test \"add\" {\n}\n", Language::Rust));
+    }
+
+    #[test]
+    fn test_external_test_module_next_to_lib_rs() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("/crate/src/tests.rs"), "#[test]\nfn it_works() {}\n").unwrap();
+
+        let module = SameFileResolver::external_test_module(
+            &fs,
+            Path::new("/crate/src/lib.rs"),
+            "pub fn add() {}\n\n#[cfg(test)]\nmod tests;\n",
+        );
+        assert_eq!(module, Some(PathBuf::from("/crate/src/tests.rs")));
+    }
+
+    #[test]
+    fn test_external_test_module_under_declaring_file_dir() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("/crate/src/parser/tests/mod.rs"), "#[test]\nfn parses() {}\n").unwrap();
+
+        let module = SameFileResolver::external_test_module(
+            &fs,
+            Path::new("/crate/src/parser.rs"),
+            "#[cfg(test)]\npub(crate) mod tests;\n",
+        );
+        assert_eq!(module, Some(PathBuf::from("/crate/src/parser/tests/mod.rs")));
+    }
+
+    #[test]
+    fn test_external_test_module_requires_test_cfg_and_existing_file() {
+        let fs = FileSystem::new_memory();
+        fs.write_file_new(Path::new("/crate/src/tests.rs"), "").unwrap();
+        fs.write_file_new(Path::new("/crate/src/checks.rs"), "").unwrap();
+        let lib = Path::new("/crate/src/lib.rs");
+
+        assert_eq!(SameFileResolver::external_test_module(&fs, lib, "mod tests;\n"), None);
+        assert_eq!(SameFileResolver::external_test_module(&fs, lib, "#[cfg(not(test))]\nmod tests;\n"), None);
+        assert_eq!(SameFileResolver::external_test_module(&fs, lib, "#[cfg(test)]\nmod missing;\n"), None);
+        assert_eq!(
+            SameFileResolver::external_test_module(&fs, lib, "#[cfg(test)]\n#[path = \"checks.rs\"]\nmod tests;\n"),
+            Some(PathBuf::from("/crate/src/checks.rs"))
+        );
+    }
 }
@@ -0,0 +1,189 @@
+use crate::cli::Language;
+use crate::config::cargo_workspace::{self, WorkspaceMember};
+use crate::error::TestsmithError;
+use crate::resolver::traits::StructureResolver;
+use std::path::{Path, PathBuf};
+
+/// Default name of the workspace member that holds integration tests
+pub const DEFAULT_TEST_CRATE: &str = "tests";
+
+/// Resolves Rust sources to integration tests in a dedicated workspace test crate
+///
+/// `crates/crate-a/src/parser.rs` -> `<test crate>/tests/crate_a_parser.rs`
+pub struct TestCrateResolver {
+    test_crate: String,
+}
+
+impl TestCrateResolver {
+    pub fn new() -> Self {
+        TestCrateResolver {
+            test_crate: DEFAULT_TEST_CRATE.to_string(),
+        }
+    }
+
+    /// Use the workspace member with this package or directory name as the test crate
+    pub fn with_test_crate(test_crate: String) -> Self {
+        TestCrateResolver { test_crate }
+    }
+
+    /// Members of the workspace enclosing the source file
+    fn members_for_source(source_path: &Path) -> Result<Vec<WorkspaceMember>, TestsmithError> {
+        let workspace_root = cargo_workspace::find_workspace_root(source_path).ok_or_else(|| {
+            TestsmithError::InvalidPath {
+                path: source_path.to_path_buf(),
+                reason: "Source file is not inside a Cargo workspace".to_string(),
+            }
+        })?;
+
+        Ok(cargo_workspace::workspace_members(&workspace_root))
+    }
+
+    /// Find the workspace member the source file belongs to
+    pub fn source_member(source_path: &Path) -> Result<WorkspaceMember, TestsmithError> {
+        let members = Self::members_for_source(source_path)?;
+
+        cargo_workspace::member_for_source(&members, source_path).ok_or_else(|| {
+            TestsmithError::InvalidPath {
+                path: source_path.to_path_buf(),
+                reason: "Source file is not inside a workspace member".to_string(),
+            }
+        })
+    }
+
+    fn transform_path(&self, source_path: &Path) -> Result<PathBuf, TestsmithError> {
+        let members = Self::members_for_source(source_path)?;
+
+        let test_crate = cargo_workspace::find_member(&members, &self.test_crate).ok_or_else(|| {
+            TestsmithError::ConfigError {
+                reason: format!("Workspace has no test crate named '{}'", self.test_crate),
+            }
+        })?;
+
+        let source_member = Self::source_member(source_path)?;
+
+        if source_member.path == test_crate.path {
+            return Err(TestsmithError::InvalidPath {
+                path: source_path.to_path_buf(),
+                reason: "Source file is already inside the test crate".to_string(),
+            });
+        }
+
+        Ok(test_crate.path.join("tests").join(Self::file_name(source_path, &source_member)?))
+    }
+
+    /// `<crate ident>_<module>.rs` for a source file in the given member
+    fn file_name(source_path: &Path, source_member: &WorkspaceMember) -> Result<String, TestsmithError> {
+        let module_name = source_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| TestsmithError::InvalidPath {
+                path: source_path.to_path_buf(),
+                reason: "Filename contains invalid UTF-8".to_string(),
+            })?;
+
+        Ok(format!("{}_{}.rs", source_member.crate_ident(), module_name))
+    }
+}
+
+impl Default for TestCrateResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StructureResolver for TestCrateResolver {
+    fn resolve_test_path(
+        &self,
+        fs: &crate::file_ops::FileSystem,
+        source_path: &Path,
+        _language: Language,
+    ) -> Result<PathBuf, TestsmithError> {
+        if !fs.file_exists(source_path) {
+            return Err(TestsmithError::FileNotFound {
+                path: source_path.to_path_buf(),
+            });
+        }
+
+        self.transform_path(source_path)
+    }
+
+    fn test_file_name(&self, source_path: &Path, _language: Language) -> Result<String, TestsmithError> {
+        Self::file_name(source_path, &Self::source_member(source_path)?)
+    }
+
+    fn is_source_path(&self, path: &Path) -> bool {
+        path.components().any(|c| c.as_os_str() == "src")
+    }
+
+    fn is_test_path(&self, path: &Path) -> bool {
+        path.parent()
+            .and_then(|parent| parent.file_name())
+            .is_some_and(|name| name == "tests")
+    }
+
+    fn name(&self) -> &'static str {
+        "Test Crate"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_ops::FileSystem;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_crate(dir: &Path, name: &str) {
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n", name),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_resolves_into_test_crate() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crate-a\", \"crate-b\", \"tests\"]\n",
+        )
+        .unwrap();
+        write_crate(&root.join("crate-a"), "crate-a");
+        write_crate(&root.join("crate-b"), "crate-b");
+        write_crate(&root.join("tests"), "workspace-tests");
+
+        let source = root.join("crate-a/src/parser.rs");
+        fs::write(&source, "pub fn parse() {}\n").unwrap();
+
+        let resolver = TestCrateResolver::new();
+        let test_path = resolver
+            .resolve_test_path(&FileSystem::new_os(), &source, Language::Rust)
+            .unwrap();
+        assert_eq!(test_path, root.join("tests/tests/crate_a_parser.rs"));
+        assert_eq!(TestCrateResolver::source_member(&source).unwrap().crate_ident(), "crate_a");
+    }
+
+    #[test]
+    fn test_missing_test_crate() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"crate-a\"]\n").unwrap();
+        write_crate(&root.join("crate-a"), "crate-a");
+
+        let source = root.join("crate-a/src/lib.rs");
+        fs::write(&source, "").unwrap();
+
+        let resolver = TestCrateResolver::with_test_crate("integration".to_string());
+        let result = resolver.resolve_test_path(&FileSystem::new_os(), &source, Language::Rust);
+        assert!(matches!(result, Err(TestsmithError::ConfigError { .. })));
+    }
+
+    #[test]
+    fn test_resolver_name() {
+        let resolver = TestCrateResolver::new();
+        assert_eq!(resolver.name(), "Test Crate");
+    }
+}
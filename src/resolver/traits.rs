@@ -13,6 +13,24 @@ pub trait StructureResolver: Send + Sync {
         language: Language,
     ) -> Result<PathBuf, TestsmithError>;
 
+    /// Every place this structure would accept the source's test, most preferred first
+    /// (the resolved path alone, unless the structure has several conventions)
+    fn candidate_test_paths(
+        &self,
+        fs: &FileSystem,
+        source_path: &std::path::Path,
+        language: Language,
+    ) -> Vec<PathBuf> {
+        self.resolve_test_path(fs, source_path, language).into_iter().collect()
+    }
+
+    /// File name of the test for a source file, independent of the test directory
+    fn test_file_name(
+        &self,
+        source_path: &std::path::Path,
+        language: Language,
+    ) -> Result<String, TestsmithError>;
+
     /// Check if a path is a valid source file for this structure
     fn is_source_path(&self, path: &std::path::Path) -> bool;
 
@@ -0,0 +1,104 @@
+use crate::cli::{Framework, Language};
+use crate::error::TestsmithError;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+
+/// Vitest + Testing Library template for single-file components (Vue, Svelte)
+pub struct ComponentVitestTemplate {
+    language: Language,
+}
+
+impl ComponentVitestTemplate {
+    pub fn new(language: Language) -> Self {
+        ComponentVitestTemplate { language }
+    }
+
+    /// Testing Library package for the component framework
+    fn testing_library(&self) -> &'static str {
+        match self.language {
+            Language::Svelte => "@testing-library/svelte",
+            _ => "@testing-library/vue",
+        }
+    }
+}
+
+impl TemplateGenerator for ComponentVitestTemplate {
+    fn generate(&self, context: &TemplateContext) -> Result<String, TestsmithError> {
+        let file_name = context
+            .source_file_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| TestsmithError::ClassNameExtractionError {
+                path: context.source_file_path.clone(),
+                reason: "Filename contains invalid UTF-8".to_string(),
+            })?;
+        let component_name = context
+            .source_file_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(file_name);
+
+        let template = format!(
+            "import {{ describe, it, expect }} from 'vitest';\nimport {{ render }} from '{}';\nimport {} from './{}';\n\ndescribe('{}', () => {{\n    it('renders', () => {{\n        render({});\n        // TODO: Implement test\n    }});\n}});\n",
+            self.testing_library(),
+            component_name,
+            file_name,
+            component_name,
+            component_name
+        );
+
+        Ok(template)
+    }
+
+    fn name(&self) -> &'static str {
+        match self.language {
+            Language::Svelte => "Svelte Vitest",
+            _ => "Vue Vitest",
+        }
+    }
+
+    fn language(&self) -> Language {
+        self.language
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::Vitest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_vue_template() {
+        let template = ComponentVitestTemplate::new(Language::Vue);
+        let context = TemplateContext::new(
+            "src/Foo.vue".into(),
+            "src/Foo.test.ts".into(),
+            Language::Vue,
+            Framework::Vitest,
+        );
+
+        let result = template.generate(&context).unwrap();
+        assert!(result.contains("import { render } from '@testing-library/vue';"));
+        assert!(result.contains("import Foo from './Foo.vue';"));
+        assert!(result.contains("describe('Foo', () => {"));
+        assert!(result.contains("render(Foo);"));
+    }
+
+    #[test]
+    fn test_generate_svelte_template() {
+        let template = ComponentVitestTemplate::new(Language::Svelte);
+        let context = TemplateContext::new(
+            "src/Button.svelte".into(),
+            "src/Button.test.ts".into(),
+            Language::Svelte,
+            Framework::Vitest,
+        );
+
+        let result = template.generate(&context).unwrap();
+        assert!(result.contains("import { render } from '@testing-library/svelte';"));
+        assert!(result.contains("import Button from './Button.svelte';"));
+        assert_eq!(template.name(), "Svelte Vitest");
+    }
+}
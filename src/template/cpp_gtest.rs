@@ -0,0 +1,99 @@
+use crate::cli::{Framework, Language};
+use crate::error::TestsmithError;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+
+/// GoogleTest template for C++ sources
+pub struct CppGoogleTestTemplate;
+
+impl CppGoogleTestTemplate {
+    pub fn new() -> Self {
+        CppGoogleTestTemplate
+    }
+
+    /// Test suite name from the source file (string_utils.cpp -> StringUtilsTest)
+    pub fn suite_name(path: &std::path::Path) -> Result<String, TestsmithError> {
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| TestsmithError::ClassNameExtractionError {
+                path: path.to_path_buf(),
+                reason: "Filename contains invalid UTF-8".to_string(),
+            })?;
+
+        let pascal: String = stem
+            .split(['_', '-', '.'])
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let mut chars = part.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            })
+            .collect();
+
+        Ok(format!("{}Test", pascal))
+    }
+}
+
+impl Default for CppGoogleTestTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateGenerator for CppGoogleTestTemplate {
+    fn generate(&self, context: &TemplateContext) -> Result<String, TestsmithError> {
+        let suite_name = Self::suite_name(&context.source_file_path)?;
+
+        let template = format!(
+            "#include <gtest/gtest.h>\n\nTEST({}, Example) {{\n    // TODO: Implement test\n}}\n",
+            suite_name
+        );
+
+        Ok(template)
+    }
+
+    fn name(&self) -> &'static str {
+        "C++ GoogleTest"
+    }
+
+    fn language(&self) -> Language {
+        Language::Cpp
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::GoogleTest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_suite_name() {
+        assert_eq!(CppGoogleTestTemplate::suite_name(Path::new("src/foo.cpp")).unwrap(), "FooTest");
+        assert_eq!(
+            CppGoogleTestTemplate::suite_name(Path::new("src/string_utils.hpp")).unwrap(),
+            "StringUtilsTest"
+        );
+    }
+
+    #[test]
+    fn test_generate_gtest_template() {
+        let template = CppGoogleTestTemplate::new();
+        let context = TemplateContext::new(
+            "src/foo.cpp".into(),
+            "test/foo_test.cpp".into(),
+            Language::Cpp,
+            Framework::GoogleTest,
+        );
+
+        let result = template.generate(&context).unwrap();
+        assert!(result.starts_with("#include <gtest/gtest.h>\n"));
+        assert!(result.contains("TEST(FooTest, Example) {"));
+        assert!(result.contains("// TODO: Implement test"));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::cli::{Framework, Language};
+use crate::error::TestsmithError;
+use crate::template::method_stub;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+
+/// xUnit template with .NET brace style (every brace on its own line)
+pub struct CSharpXUnitTemplate;
+
+impl CSharpXUnitTemplate {
+    pub fn new() -> Self {
+        CSharpXUnitTemplate
+    }
+}
+
+impl Default for CSharpXUnitTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateGenerator for CSharpXUnitTemplate {
+    fn generate(&self, context: &TemplateContext) -> Result<String, TestsmithError> {
+        let test_class_name = context.test_class_name().unwrap_or_else(|| {
+            format!("{}Tests", context.class_name.as_deref().unwrap_or("Example"))
+        });
+
+        let template = format!(
+            "using Xunit;\n\npublic class {}\n{{\n    [Fact]\n    public void {}()\n    {{\n        // TODO: Implement test\n    }}\n}}\n",
+            test_class_name,
+            method_stub::prefixed_name(context.test_prefix(), "example")
+        );
+
+        Ok(template)
+    }
+
+    fn name(&self) -> &'static str {
+        "xUnit"
+    }
+
+    fn language(&self) -> Language {
+        Language::CSharp
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::XUnit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_template() {
+        let template = CSharpXUnitTemplate::new();
+        let context = TemplateContext::new(
+            "src/Foo.cs".into(),
+            "tests/FooTests.cs".into(),
+            Language::CSharp,
+            Framework::XUnit,
+        );
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(
+            result,
+            "using Xunit;\n\npublic class FooTests\n{\n    [Fact]\n    public void TestExample()\n    {\n        // TODO: Implement test\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_template_with_custom_prefix() {
+        let template = CSharpXUnitTemplate::new();
+        let context = TemplateContext::new(
+            "src/Foo.cs".into(),
+            "tests/FooTests.cs".into(),
+            Language::CSharp,
+            Framework::XUnit,
+        )
+        .with_method_prefix("Should".to_string());
+
+        let result = template.generate(&context).unwrap();
+        assert!(result.contains("public void ShouldExample()"));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::cli::{Framework, Language};
+use crate::error::TestsmithError;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+
+/// `package:test` template (two-space indentation, as `dart format` produces)
+pub struct DartTestTemplate;
+
+impl DartTestTemplate {
+    pub fn new() -> Self {
+        DartTestTemplate
+    }
+}
+
+impl Default for DartTestTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateGenerator for DartTestTemplate {
+    fn generate(&self, context: &TemplateContext) -> Result<String, TestsmithError> {
+        // Package imports are sorted together; relative imports get their own section
+        let mut imports = vec!["import 'package:test/test.dart';".to_string()];
+        match context.source_import.as_deref() {
+            Some(import) if import.starts_with("package:") => {
+                imports.push(format!("import '{}';", import));
+                imports.sort();
+            }
+            Some(import) => imports.push(format!("\nimport '{}';", import)),
+            None => {}
+        }
+
+        Ok(format!(
+            "{}\n\nvoid main() {{\n  test('example', () {{\n    // TODO: Implement test\n  }});\n}}\n",
+            imports.join("\n")
+        ))
+    }
+
+    fn name(&self) -> &'static str {
+        "Dart test"
+    }
+
+    fn language(&self) -> Language {
+        Language::Dart
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::DartTest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_template() {
+        let template = DartTestTemplate::new();
+        let context = TemplateContext::new(
+            "lib/foo.dart".into(),
+            "test/foo_test.dart".into(),
+            Language::Dart,
+            Framework::DartTest,
+        );
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(
+            result,
+            "import 'package:test/test.dart';\n\nvoid main() {\n  test('example', () {\n    // TODO: Implement test\n  });\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_package_import_sorted_with_test_import() {
+        let template = DartTestTemplate::new();
+        let context = TemplateContext::new(
+            "lib/cart.dart".into(),
+            "test/cart_test.dart".into(),
+            Language::Dart,
+            Framework::DartTest,
+        )
+        .with_source_import("package:shopping_cart/cart.dart".to_string());
+
+        let result = template.generate(&context).unwrap();
+        assert!(result.starts_with(
+            "import 'package:shopping_cart/cart.dart';\nimport 'package:test/test.dart';\n\nvoid main() {"
+        ));
+    }
+
+    #[test]
+    fn test_relative_import_in_own_section() {
+        let template = DartTestTemplate::new();
+        let context = TemplateContext::new(
+            "lib/cart.dart".into(),
+            "test/cart_test.dart".into(),
+            Language::Dart,
+            Framework::DartTest,
+        )
+        .with_source_import("../lib/cart.dart".to_string());
+
+        let result = template.generate(&context).unwrap();
+        assert!(result.starts_with("import 'package:test/test.dart';\n\nimport '../lib/cart.dart';\n\nvoid main() {"));
+    }
+}
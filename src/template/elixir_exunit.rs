@@ -0,0 +1,129 @@
+use crate::cli::{Framework, Language};
+use crate::error::TestsmithError;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+use regex::Regex;
+use std::path::Path;
+
+/// ExUnit template; the test module is the source module plus `Test`
+pub struct ElixirExUnitTemplate;
+
+impl ElixirExUnitTemplate {
+    pub fn new() -> Self {
+        ElixirExUnitTemplate
+    }
+
+    /// Extract the first module name from Elixir source (defmodule MyApp.Parser do)
+    pub fn extract_module_name(source_content: &str) -> Option<String> {
+        let module_regex = Regex::new(r"^\s*defmodule\s+([A-Z][\w\.]*)\s+do\b").unwrap();
+
+        for line in source_content.lines() {
+            // Skip commented-out definitions such as `# defmodule Old do`
+            let line = line.split('#').next().unwrap_or("");
+            if let Some(caps) = module_regex.captures(line)
+                && let Some(module_name) = caps.get(1)
+            {
+                return Some(module_name.as_str().to_string());
+            }
+        }
+
+        None
+    }
+
+    /// Module name mix would expect for a file (parser_utils.ex -> ParserUtils)
+    fn module_from_file_name(source_path: &Path) -> String {
+        source_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("Example")
+            .split('_')
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let mut chars = part.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                    None => String::new(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for ElixirExUnitTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateGenerator for ElixirExUnitTemplate {
+    fn generate(&self, context: &TemplateContext) -> Result<String, TestsmithError> {
+        let module_name = context
+            .class_name
+            .clone()
+            .unwrap_or_else(|| Self::module_from_file_name(&context.source_file_path));
+
+        // mix format indents with two spaces
+        let template = format!(
+            "defmodule {}Test do\n  use ExUnit.Case\n\n  test \"example\" do\n    # TODO: Implement test\n  end\nend\n",
+            module_name
+        );
+
+        Ok(template)
+    }
+
+    fn name(&self) -> &'static str {
+        "Elixir ExUnit"
+    }
+
+    fn language(&self) -> Language {
+        Language::Elixir
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::ExUnit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_module_name() {
+        let content = "# defmodule MyApp.Old do\ndefmodule MyApp.Parser do\n  def parse(input), do: input\nend\n";
+
+        let module_name = ElixirExUnitTemplate::extract_module_name(content);
+        assert_eq!(module_name, Some("MyApp.Parser".to_string()));
+    }
+
+    #[test]
+    fn test_generate_template() {
+        let template = ElixirExUnitTemplate::new();
+        let context = TemplateContext::new(
+            "lib/my_app/parser.ex".into(),
+            "test/my_app/parser_test.exs".into(),
+            Language::Elixir,
+            Framework::ExUnit,
+        )
+        .with_class_name("MyApp.Parser".to_string());
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(
+            result,
+            "defmodule MyApp.ParserTest do\n  use ExUnit.Case\n\n  test \"example\" do\n    # TODO: Implement test\n  end\nend\n"
+        );
+    }
+
+    #[test]
+    fn test_module_falls_back_to_file_name() {
+        let template = ElixirExUnitTemplate::new();
+        let context = TemplateContext::new(
+            "lib/parser_utils.ex".into(),
+            "test/parser_utils_test.exs".into(),
+            Language::Elixir,
+            Framework::ExUnit,
+        );
+
+        let result = template.generate(&context).unwrap();
+        assert!(result.starts_with("defmodule ParserUtilsTest do\n"));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::cli::{Framework, Language};
+use crate::config::framework_detector::uncommented_lines;
+use crate::error::TestsmithError;
+use crate::template::method_stub;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+use regex::Regex;
+
+/// Go `testing` package template; tests share the source's package
+pub struct GoTestingTemplate;
+
+impl GoTestingTemplate {
+    pub fn new() -> Self {
+        GoTestingTemplate
+    }
+
+    /// Extract package name from Go source file (package foo)
+    pub fn extract_package_name(source_content: &str) -> Option<String> {
+        let package_regex = Regex::new(r"^\s*package\s+(\w+)").unwrap();
+
+        for line in uncommented_lines(source_content) {
+            if let Some(caps) = package_regex.captures(&line)
+                && let Some(package_name) = caps.get(1)
+            {
+                return Some(package_name.as_str().to_string());
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for GoTestingTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateGenerator for GoTestingTemplate {
+    fn generate(&self, context: &TemplateContext) -> Result<String, TestsmithError> {
+        // Without a package clause, fall back to the directory name like `go` does for new packages
+        let package_name = context
+            .package_name
+            .clone()
+            .or_else(|| {
+                context
+                    .source_file_path
+                    .parent()
+                    .and_then(|dir| dir.file_name())
+                    .and_then(|name| name.to_str())
+                    .map(|name| name.to_string())
+            })
+            .unwrap_or_else(|| "main".to_string());
+
+        // gofmt indents with tabs
+        let template = format!(
+            "package {}\n\nimport \"testing\"\n\nfunc {}(t *testing.T) {{\n\t// TODO: Implement test\n}}\n",
+            package_name,
+            method_stub::prefixed_name(context.test_prefix(), "example")
+        );
+
+        Ok(template)
+    }
+
+    fn name(&self) -> &'static str {
+        "Go testing"
+    }
+
+    fn language(&self) -> Language {
+        Language::Go
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::GoTest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_package_name() {
+        let content = "// Package server handles requests.\npackage server\n\nimport \"net/http\"\n";
+
+        let package_name = GoTestingTemplate::extract_package_name(content);
+        assert_eq!(package_name, Some("server".to_string()));
+    }
+
+    #[test]
+    fn test_generate_template() {
+        let template = GoTestingTemplate::new();
+        let context = TemplateContext::new(
+            "pkg/server/handler.go".into(),
+            "pkg/server/handler_test.go".into(),
+            Language::Go,
+            Framework::GoTest,
+        )
+        .with_package_name("server".to_string());
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(
+            result,
+            "package server\n\nimport \"testing\"\n\nfunc TestExample(t *testing.T) {\n\t// TODO: Implement test\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_package_falls_back_to_directory_name() {
+        let template = GoTestingTemplate::new();
+        let context = TemplateContext::new(
+            "pkg/server/handler.go".into(),
+            "pkg/server/handler_test.go".into(),
+            Language::Go,
+            Framework::GoTest,
+        );
+
+        let result = template.generate(&context).unwrap();
+        assert!(result.starts_with("package server\n"));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::cli::{Framework, Language};
+use crate::config::module_path::module_path;
+use crate::error::TestsmithError;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+use regex::Regex;
+use std::path::Path;
+
+/// HSpec template; the spec module is the source module plus `Spec`, as hspec-discover expects
+pub struct HaskellHSpecTemplate;
+
+impl HaskellHSpecTemplate {
+    pub fn new() -> Self {
+        HaskellHSpecTemplate
+    }
+
+    /// Extract the module name from Haskell source (module Data.Parser (parse) where)
+    pub fn extract_module_name(source_content: &str) -> Option<String> {
+        // The export list may follow on later lines, so only the name is matched
+        let module_regex = Regex::new(r"^module\s+([A-Z][\w']*(?:\.[A-Z][\w']*)*)").unwrap();
+
+        source_content
+            .lines()
+            .find_map(|line| module_regex.captures(line))
+            .and_then(|caps| caps.get(1))
+            .map(|module_name| module_name.as_str().to_string())
+    }
+
+    /// Module name Cabal would expect for a file, from its directories below `src/`
+    /// (src/Data/Parser.hs -> Data.Parser)
+    fn module_from_path(source_path: &Path) -> String {
+        source_path
+            .ancestors()
+            .find(|dir| dir.file_name().is_some_and(|name| name == "src"))
+            .and_then(|src| module_path(source_path, src, Language::Haskell))
+            .or_else(|| source_path.file_stem()?.to_str().map(str::to_string))
+            .unwrap_or_else(|| "Main".to_string())
+    }
+}
+
+impl Default for HaskellHSpecTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateGenerator for HaskellHSpecTemplate {
+    fn generate(&self, context: &TemplateContext) -> Result<String, TestsmithError> {
+        let module_name = context
+            .class_name
+            .clone()
+            .unwrap_or_else(|| Self::module_from_path(&context.source_file_path));
+
+        // `pending` keeps the stub compiling until the test is written
+        let template = format!(
+            "module {module}Spec where\n\nimport Test.Hspec\nimport {module}\n\nspec :: Spec\nspec = do\n  describe \"{module}\" $ do\n    it \"does something\" $ do\n      -- TODO: Implement test\n      pending\n",
+            module = module_name
+        );
+
+        Ok(template)
+    }
+
+    fn name(&self) -> &'static str {
+        "Haskell HSpec"
+    }
+
+    fn language(&self) -> Language {
+        Language::Haskell
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::HSpec
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_module_name() {
+        let content = "-- module Old where\nmodule Data.Parser\n  ( parse\n  ) where\n\nparse :: String -> Int\nparse = length\n";
+
+        let module_name = HaskellHSpecTemplate::extract_module_name(content);
+        assert_eq!(module_name, Some("Data.Parser".to_string()));
+    }
+
+    #[test]
+    fn test_generate_template() {
+        let template = HaskellHSpecTemplate::new();
+        let context = TemplateContext::new(
+            "src/Data/Parser.hs".into(),
+            "test/Data/ParserSpec.hs".into(),
+            Language::Haskell,
+            Framework::HSpec,
+        )
+        .with_class_name("Data.Parser".to_string());
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(
+            result,
+            "module Data.ParserSpec where\n\nimport Test.Hspec\nimport Data.Parser\n\nspec :: Spec\nspec = do\n  describe \"Data.Parser\" $ do\n    it \"does something\" $ do\n      -- TODO: Implement test\n      pending\n"
+        );
+    }
+
+    #[test]
+    fn test_module_falls_back_to_path_below_src() {
+        assert_eq!(HaskellHSpecTemplate::module_from_path(Path::new("/pkg/src/Data/Parser.hs")), "Data.Parser");
+        assert_eq!(HaskellHSpecTemplate::module_from_path(Path::new("app/Main.hs")), "Main");
+    }
+}
@@ -1,5 +1,7 @@
 use crate::cli::{Framework, Language};
+use crate::config::framework_detector::{uncommented_lines, PropertyLibrary};
 use crate::error::TestsmithError;
+use crate::template::method_stub;
 use crate::template::traits::{TemplateContext, TemplateGenerator};
 use regex::Regex;
 use std::fs;
@@ -24,18 +26,50 @@ impl JavaJunitTemplate {
         // Look for package declaration: package com.example.foo;
         let package_regex = Regex::new(r"^\s*package\s+([\w\.]+)\s*;").unwrap();
 
-        for line in content.lines() {
-            if let Some(caps) = package_regex.captures(line) {
-                if let Some(package_name) = caps.get(1) {
-                    return Ok(Some(package_name.as_str().to_string()));
-                }
+        // Skip commented-out declarations such as `// package old.name;`
+        for line in uncommented_lines(&content) {
+            if let Some(caps) = package_regex.captures(&line)
+                && let Some(package_name) = caps.get(1)
+            {
+                return Ok(Some(package_name.as_str().to_string()));
             }
         }
 
         Ok(None)
     }
 
-    /// Extract class name from filename (Foo.java -> Foo)
+    /// Name of the first top-level public type declared in Java source
+    /// (a nested `public static class Builder` never wins over its enclosing type)
+    pub fn extract_public_type_name(source: &str) -> Option<String> {
+        let type_regex = Regex::new(
+            r"\bpublic\s+(?:(?:abstract|final|static|sealed|non-sealed|strictfp)\s+)*(?:class|interface|enum|record)\s+([A-Za-z_$][\w$]*)",
+        )
+        .unwrap();
+        let string_regex = Regex::new(r#""(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'"#).unwrap();
+
+        let mut depth = 0usize;
+        for line in uncommented_lines(source) {
+            let code = string_regex.replace_all(&line, "\"\"");
+            if let Some(caps) = type_regex.captures(&code)
+                && let Some(name) = caps.get(1)
+                && depth + code[..name.start()].matches('{').count() == code[..name.start()].matches('}').count()
+            {
+                return Some(name.as_str().to_string());
+            }
+
+            for ch in code.chars() {
+                match ch {
+                    '{' => depth += 1,
+                    '}' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+
+    /// Extract class name from the first public type declared in the file,
+    /// falling back to the filename (Foo.java -> Foo)
     pub fn extract_class_name(path: &Path) -> Result<String, TestsmithError> {
         let file_name = path
             .file_name()
@@ -49,17 +83,22 @@ impl JavaJunitTemplate {
                 reason: "Filename contains invalid UTF-8".to_string(),
             })?;
 
-        // Remove .java extension
-        let class_name = if file_name.ends_with("Test.java") {
-            // Remove both Test and .java
-            file_name.trim_end_matches("Test.java").to_string()
-        } else if file_name.ends_with(".java") {
-            file_name.trim_end_matches(".java").to_string()
-        } else {
-            file_name.to_string()
-        };
+        // Remove .java, and Test for a test file (FooTest.java -> Foo)
+        let stem = file_name.strip_suffix(".java").unwrap_or(file_name);
+        let is_test_file = stem.ends_with("Test") && stem != "Test";
+        let without_test = |name: &str| name.strip_suffix("Test").unwrap_or(name).to_string();
 
-        Ok(class_name)
+        // The declared public type wins over the filename when they differ
+        let declared = fs::read_to_string(path)
+            .ok()
+            .and_then(|source| Self::extract_public_type_name(&source));
+
+        Ok(match declared {
+            Some(name) if is_test_file => without_test(&name),
+            Some(name) => name,
+            None if is_test_file => without_test(stem),
+            None => stem.to_string(),
+        })
     }
 }
 
@@ -83,11 +122,64 @@ impl TemplateGenerator for JavaJunitTemplate {
             .cloned()
             .unwrap_or_else(|| "Example".to_string());
 
-        let test_class_name = format!("{}Test", class_name);
+        let test_class_name = context
+            .test_class_name()
+            .unwrap_or_else(|| format!("{}Test", class_name));
+
+        if context.property_library == Some(PropertyLibrary::Jqwik) {
+            return Ok(format!(
+                "{}import net.jqwik.api.*;\n\nclass {} {{\n    @Property\n    void {}(@ForAll int x) {{\n        // TODO: Implement test\n    }}\n}}\n",
+                package_part,
+                test_class_name,
+                method_stub::prefixed_name(context.method_prefix.as_deref().unwrap_or("prop"), "example")
+            ));
+        }
+
+        // Parameterized tests run once per @ValueSource value, passed in as `value`
+        let (annotations, parameters, test_import) = if context.parameterized {
+            (
+                "@ParameterizedTest\n    @ValueSource(ints = {1, 2, 3})",
+                "int value",
+                "import org.junit.jupiter.params.ParameterizedTest;\nimport org.junit.jupiter.params.provider.ValueSource;",
+            )
+        } else {
+            ("@Test", "", "import org.junit.jupiter.api.Test;")
+        };
+
+        let test_methods = if context.methods.is_empty() {
+            format!(
+                "    {}\n    void {}({}) {{\n        // TODO: Implement test\n    }}\n",
+                annotations,
+                method_stub::prefixed_name(context.test_prefix(), "example"),
+                parameters
+            )
+        } else {
+            let names = method_stub::test_method_names(
+                &context.methods,
+                context.method_naming,
+                context.test_prefix(),
+                Language::Java,
+            );
+            let stubs = context
+                .methods
+                .iter()
+                .zip(names)
+                .map(|(method, name)| {
+                    let body: String = method
+                        .stub_body_comments()
+                        .iter()
+                        .map(|comment| format!("        {}\n", comment))
+                        .collect();
+                    format!("    {}\n    void {}({}) {{\n{}    }}\n", annotations, name, parameters, body)
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            context.section(stubs, "    ")
+        };
 
         let template = format!(
-            "{}import org.junit.jupiter.api.Test;\nimport static org.junit.jupiter.api.Assertions.*;\n\nclass {} {{\n    @Test\n    void testExample() {{\n        // TODO: Implement test\n    }}\n}}\n",
-            package_part, test_class_name
+            "{}{}\nimport static org.junit.jupiter.api.Assertions.*;\n\nclass {} {{\n{}}}\n",
+            package_part, test_import, test_class_name, test_methods
         );
 
         Ok(template)
@@ -145,6 +237,28 @@ mod tests {
         assert_eq!(package_name, None);
     }
 
+    #[test]
+    fn test_extract_package_name_skips_line_comment() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let content = "// package com.fake;\npackage com.real;\n\npublic class Foo {}";
+        temp_file.write_all(content.as_bytes()).unwrap();
+        temp_file.flush().unwrap();
+
+        let package_name = JavaJunitTemplate::extract_package_name(temp_file.path()).unwrap();
+        assert_eq!(package_name, Some("com.real".to_string()));
+    }
+
+    #[test]
+    fn test_extract_package_name_skips_block_comment() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let content = "/*\n * Moved from:\npackage com.fake;\n */\npackage com.real;\n\npublic class Foo {}";
+        temp_file.write_all(content.as_bytes()).unwrap();
+        temp_file.flush().unwrap();
+
+        let package_name = JavaJunitTemplate::extract_package_name(temp_file.path()).unwrap();
+        assert_eq!(package_name, Some("com.real".to_string()));
+    }
+
     #[test]
     fn test_extract_class_name() {
         let path = Path::new("Foo.java");
@@ -159,6 +273,61 @@ mod tests {
         assert_eq!(class_name, "Foo");
     }
 
+    #[test]
+    fn test_extract_class_name_prefers_declared_public_type() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("Helpers.java");
+        std::fs::write(
+            &path,
+            "package com.example;\n\nclass Internal {}\n\npublic final class StringUtils {\n    public static class Builder {}\n}\n",
+        )
+        .unwrap();
+
+        let class_name = JavaJunitTemplate::extract_class_name(&path).unwrap();
+        assert_eq!(class_name, "StringUtils");
+    }
+
+    #[test]
+    fn test_extract_public_type_name_skips_nested_types() {
+        let source = "package com.example;\n\nclass Helpers {\n    public static class Inner {}\n}\n\npublic class Outer {\n    String brace = \"{\";\n    public static class Builder {}\n}\n";
+        assert_eq!(JavaJunitTemplate::extract_public_type_name(source), Some("Outer".to_string()));
+
+        assert_eq!(
+            JavaJunitTemplate::extract_public_type_name("class Helpers {\n    public enum Mode { A }\n}\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extract_class_name_strips_a_single_test_suffix() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("TestTest.java");
+        std::fs::write(&path, "public class TestTest {}\n").unwrap();
+        assert_eq!(JavaJunitTemplate::extract_class_name(&path).unwrap(), "Test");
+
+        assert_eq!(JavaJunitTemplate::extract_class_name(Path::new("ContestTest.java")).unwrap(), "Contest");
+    }
+
+    #[test]
+    fn test_extract_class_name_ignores_commented_out_type() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("Point.java");
+        std::fs::write(&path, "// public class OldPoint {}\npublic record Coordinate(int x, int y) {}\n").unwrap();
+
+        let class_name = JavaJunitTemplate::extract_class_name(&path).unwrap();
+        assert_eq!(class_name, "Coordinate");
+    }
+
+    #[test]
+    fn test_extract_class_name_falls_back_to_filename() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("Foo.java");
+        std::fs::write(&path, "class Foo {}\n").unwrap();
+
+        let class_name = JavaJunitTemplate::extract_class_name(&path).unwrap();
+        assert_eq!(class_name, "Foo");
+    }
+
     #[test]
     fn test_generate_template_with_package() {
         let template = JavaJunitTemplate::new();
@@ -192,4 +361,129 @@ mod tests {
         assert!(!result.contains("package"));
         assert!(result.contains("class FooTest"));
     }
+
+    #[test]
+    fn test_generate_parameterized_template() {
+        let template = JavaJunitTemplate::new();
+        let context = TemplateContext::new(
+            "Foo.java".into(),
+            "FooTest.java".into(),
+            Language::Java,
+            Framework::JUnit,
+        )
+        .with_class_name("Foo".to_string())
+        .with_package_name("com.example".to_string())
+        .with_parameterized(true);
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(
+            result,
+            "package com.example;\n\nimport org.junit.jupiter.params.ParameterizedTest;\nimport org.junit.jupiter.params.provider.ValueSource;\nimport static org.junit.jupiter.api.Assertions.*;\n\nclass FooTest {\n    @ParameterizedTest\n    @ValueSource(ints = {1, 2, 3})\n    void testExample(int value) {\n        // TODO: Implement test\n    }\n}\n"
+        );
+        assert!(!result.contains("import org.junit.jupiter.api.Test;"));
+    }
+
+    #[test]
+    fn test_generate_jqwik_property() {
+        let template = JavaJunitTemplate::new();
+        let context = TemplateContext::new(
+            "Foo.java".into(),
+            "FooTest.java".into(),
+            Language::Java,
+            Framework::JUnit,
+        )
+        .with_class_name("Foo".to_string())
+        .with_package_name("com.example".to_string())
+        .with_property_library(PropertyLibrary::Jqwik);
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(
+            result,
+            "package com.example;\n\nimport net.jqwik.api.*;\n\nclass FooTest {\n    @Property\n    void propExample(@ForAll int x) {\n        // TODO: Implement test\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_template_with_method_stubs() {
+        let template = JavaJunitTemplate::new();
+        let methods = crate::template::method_stub::extract_java_methods(
+            "    public int getCount() {\n    public CompletableFuture<Void> refresh() {\n",
+        );
+        let context = TemplateContext::new(
+            "Foo.java".into(),
+            "FooTest.java".into(),
+            Language::Java,
+            Framework::JUnit,
+        )
+        .with_class_name("Foo".to_string())
+        .with_methods(methods);
+
+        let result = template.generate(&context).unwrap();
+        assert!(!result.contains("testExample"));
+        assert!(result.contains("void testGetCount()"));
+        assert!(result.contains("void testRefresh()"));
+        assert!(result.contains("// assert result"));
+        assert!(result.contains("// refresh() is asynchronous"));
+    }
+
+    #[test]
+    fn test_generate_template_with_section_markers() {
+        let template = JavaJunitTemplate::new();
+        let methods = crate::template::method_stub::extract_java_methods(
+            "    public int getCount() {\n    public void reset() {\n",
+        );
+        let context = TemplateContext::new(
+            "Foo.java".into(),
+            "FooTest.java".into(),
+            Language::Java,
+            Framework::JUnit,
+        )
+        .with_class_name("Foo".to_string())
+        .with_methods(methods.clone());
+
+        let plain = template.generate(&context).unwrap();
+        assert!(!plain.contains("// region"));
+
+        let result = template.generate(&context.with_section_markers(true)).unwrap();
+        let region = result.find("    // region Generated tests\n").unwrap();
+        let end_region = result.find("    // endregion\n}\n").unwrap();
+        let first_test = result.find("void testGetCount()").unwrap();
+        let last_test = result.find("void testReset()").unwrap();
+        assert!(region < first_test && last_test < end_region);
+    }
+
+    #[test]
+    fn test_generate_template_with_numbered_method_names() {
+        let template = JavaJunitTemplate::new();
+        let methods = crate::template::method_stub::extract_java_methods(
+            "    public void add(int a) {\n    public void add(String a) {\n",
+        );
+        let context = TemplateContext::new(
+            "Foo.java".into(),
+            "FooTest.java".into(),
+            Language::Java,
+            Framework::JUnit,
+        )
+        .with_methods(methods)
+        .with_method_naming(crate::cli::MethodNaming::Numbered);
+
+        let result = template.generate(&context).unwrap();
+        assert!(result.contains("void test_add_1()"));
+        assert!(result.contains("void test_add_2()"));
+    }
+
+    #[test]
+    fn test_generate_template_uses_test_file_name() {
+        let template = JavaJunitTemplate::new();
+        let context = TemplateContext::new(
+            "Foo.java".into(),
+            "FooTests.java".into(),
+            Language::Java,
+            Framework::JUnit,
+        )
+        .with_class_name("Foo".to_string());
+
+        let result = template.generate(&context).unwrap();
+        assert!(result.contains("class FooTests {"));
+    }
 }
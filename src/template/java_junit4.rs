@@ -1,5 +1,8 @@
 use crate::cli::{Framework, Language};
+use crate::config::framework_detector::uncommented_lines;
 use crate::error::TestsmithError;
+use crate::template::java_junit::JavaJunitTemplate;
+use crate::template::method_stub;
 use crate::template::traits::{TemplateContext, TemplateGenerator};
 use regex::Regex;
 use std::fs;
@@ -24,42 +27,21 @@ impl JavaJunit4Template {
         // Look for package declaration: package com.example.foo;
         let package_regex = Regex::new(r"^\s*package\s+([\w\.]+)\s*;").unwrap();
 
-        for line in content.lines() {
-            if let Some(caps) = package_regex.captures(line) {
-                if let Some(package_name) = caps.get(1) {
-                    return Ok(Some(package_name.as_str().to_string()));
-                }
+        // Skip commented-out declarations such as `// package old.name;`
+        for line in uncommented_lines(&content) {
+            if let Some(caps) = package_regex.captures(&line)
+                && let Some(package_name) = caps.get(1)
+            {
+                return Ok(Some(package_name.as_str().to_string()));
             }
         }
 
         Ok(None)
     }
 
-    /// Extract class name from filename (Foo.java -> Foo)
+    /// Extract class name as JUnit 5 does: the first top-level public type, else the filename
     pub fn extract_class_name(path: &Path) -> Result<String, TestsmithError> {
-        let file_name = path
-            .file_name()
-            .ok_or_else(|| TestsmithError::ClassNameExtractionError {
-                path: path.to_path_buf(),
-                reason: "No filename found".to_string(),
-            })?
-            .to_str()
-            .ok_or_else(|| TestsmithError::ClassNameExtractionError {
-                path: path.to_path_buf(),
-                reason: "Filename contains invalid UTF-8".to_string(),
-            })?;
-
-        // Remove .java extension
-        let class_name = if file_name.ends_with("Test.java") {
-            // Remove both Test and .java
-            file_name.trim_end_matches("Test.java").to_string()
-        } else if file_name.ends_with(".java") {
-            file_name.trim_end_matches(".java").to_string()
-        } else {
-            file_name.to_string()
-        };
-
-        Ok(class_name)
+        JavaJunitTemplate::extract_class_name(path)
     }
 }
 
@@ -83,11 +65,42 @@ impl TemplateGenerator for JavaJunit4Template {
             .cloned()
             .unwrap_or_else(|| "Example".to_string());
 
-        let test_class_name = format!("{}Test", class_name);
+        let test_class_name = context
+            .test_class_name()
+            .unwrap_or_else(|| format!("{}Test", class_name));
+
+        let test_methods = if context.methods.is_empty() {
+            format!(
+                "    @Test\n    public void {}() {{\n        // TODO: Implement test\n    }}\n",
+                method_stub::prefixed_name(context.test_prefix(), "example")
+            )
+        } else {
+            let names = method_stub::test_method_names(
+                &context.methods,
+                context.method_naming,
+                context.test_prefix(),
+                Language::Java,
+            );
+            let stubs = context
+                .methods
+                .iter()
+                .zip(names)
+                .map(|(method, name)| {
+                    let body: String = method
+                        .stub_body_comments()
+                        .iter()
+                        .map(|comment| format!("        {}\n", comment))
+                        .collect();
+                    format!("    @Test\n    public void {}() {{\n{}    }}\n", name, body)
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            context.section(stubs, "    ")
+        };
 
         let template = format!(
-            "{}import org.junit.Test;\nimport static org.junit.Assert.*;\n\npublic class {} {{\n    @Test\n    public void testExample() {{\n        // TODO: Implement test\n    }}\n}}\n",
-            package_part, test_class_name
+            "{}import org.junit.Test;\nimport static org.junit.Assert.*;\n\npublic class {} {{\n{}}}\n",
+            package_part, test_class_name, test_methods
         );
 
         Ok(template)
@@ -0,0 +1,60 @@
+use crate::cli::Language;
+
+/// File name stem of the index test generated in a `__tests__/` directory
+pub const INDEX_STEM: &str = "index.test";
+
+/// Index test file name for the language (`index.test.js`, `index.test.ts`)
+pub fn index_file_name(language: Language) -> String {
+    match language {
+        Language::TypeScript => format!("{}.ts", INDEX_STEM),
+        _ => format!("{}.js", INDEX_STEM),
+    }
+}
+
+/// Whether a file in a `__tests__/` directory is a test the index should load
+pub fn is_indexed_test(file_name: &str) -> bool {
+    !file_name.starts_with(&format!("{}.", INDEX_STEM))
+        && [".test.", ".spec."].iter().any(|marker| file_name.contains(marker))
+}
+
+/// Index test that loads every other test in its directory, so runners pointed
+/// at one file still see them all
+pub fn generate_index(test_file_names: &[String], language: Language) -> String {
+    let loads: String = test_file_names
+        .iter()
+        .map(|name| match language {
+            Language::TypeScript => {
+                let module = name.strip_suffix(".ts").unwrap_or(name);
+                format!("import './{}';\n", module)
+            }
+            _ => format!("require('./{}');\n", name),
+        })
+        .collect();
+
+    format!("// Generated by testsmith --with-runner: loads every test in this directory\n{}", loads)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_loads_each_test() {
+        let names = vec!["cart.test.js".to_string(), "price.spec.js".to_string()];
+        assert_eq!(
+            generate_index(&names, Language::JavaScript),
+            "// Generated by testsmith --with-runner: loads every test in this directory\nrequire('./cart.test.js');\nrequire('./price.spec.js');\n"
+        );
+
+        let names = vec!["cart.test.ts".to_string()];
+        assert!(generate_index(&names, Language::TypeScript).ends_with("import './cart.test';\n"));
+    }
+
+    #[test]
+    fn test_index_skips_itself_and_non_tests() {
+        assert!(is_indexed_test("cart.test.js"));
+        assert!(!is_indexed_test("index.test.js"));
+        assert!(!is_indexed_test("helpers.js"));
+        assert_eq!(index_file_name(Language::TypeScript), "index.test.ts");
+    }
+}
@@ -0,0 +1,76 @@
+use crate::cli::Language;
+
+/// Java keywords and literals that can't be used as identifiers
+const JAVA_RESERVED: &[&str] = &[
+    "_", "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "const",
+    "continue", "default", "do", "double", "else", "enum", "extends", "false", "final", "finally",
+    "float", "for", "goto", "if", "implements", "import", "instanceof", "int", "interface", "long",
+    "native", "new", "null", "package", "private", "protected", "public", "return", "short",
+    "static", "strictfp", "super", "switch", "synchronized", "this", "throw", "throws", "transient",
+    "true", "try", "void", "volatile", "while",
+];
+
+/// Rust strict and reserved keywords
+const RUST_RESERVED: &[&str] = &[
+    "_", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Kotlin hard keywords
+const KOTLIN_RESERVED: &[&str] = &[
+    "as", "break", "class", "continue", "do", "else", "false", "for", "fun", "if", "in",
+    "interface", "is", "null", "object", "package", "return", "super", "this", "throw", "true",
+    "try", "typealias", "typeof", "val", "var", "when", "while",
+];
+
+/// Python keywords
+const PYTHON_RESERVED: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in",
+    "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with",
+    "yield",
+];
+
+/// Check whether a name is reserved in the language and can't be used as an identifier
+pub fn is_reserved_word(name: &str, language: Language) -> bool {
+    match language {
+        Language::Java => JAVA_RESERVED.contains(&name),
+        Language::Rust => RUST_RESERVED.contains(&name),
+        Language::Python => PYTHON_RESERVED.contains(&name),
+        Language::Kotlin => KOTLIN_RESERVED.contains(&name),
+        _ => false,
+    }
+}
+
+/// Make a generated name legal by suffixing `_` when it is a reserved word (return -> return_)
+pub fn escape_reserved(name: &str, language: Language) -> String {
+    if is_reserved_word(name, language) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserved_words_per_language() {
+        assert!(is_reserved_word("return", Language::Java));
+        assert!(is_reserved_word("match", Language::Rust));
+        assert!(!is_reserved_word("match", Language::Java));
+        assert!(is_reserved_word("lambda", Language::Python));
+        assert!(!is_reserved_word("testReturn", Language::Java));
+    }
+
+    #[test]
+    fn test_escape_reserved() {
+        assert_eq!(escape_reserved("return", Language::Java), "return_");
+        assert_eq!(escape_reserved("ReturnTest", Language::Java), "ReturnTest");
+        assert_eq!(escape_reserved("type", Language::Rust), "type_");
+    }
+}
@@ -0,0 +1,697 @@
+use crate::cli::{Framework, Language, MethodNaming};
+use crate::template::keywords;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+/// Best-effort description of a source method, used to tailor generated test stubs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodSignature {
+    /// Method name as declared in the source
+    pub name: String,
+    /// Whether the method is private (private methods don't get stubs)
+    pub is_private: bool,
+    /// Whether the method is asynchronous (Kotlin `suspend`, Java future return types)
+    pub is_async: bool,
+    /// Whether the method returns a value worth asserting on
+    pub returns_value: bool,
+    /// Simple parameter type names in declaration order (None if the parameter list spans lines)
+    pub parameter_types: Option<Vec<String>>,
+    /// Parameter types as written in the source (`int`, `List<String>`, `&str`)
+    pub declared_types: Option<Vec<String>>,
+}
+
+impl MethodSignature {
+    /// Name of the generated test method (`test` + foo -> testFoo, `test_` + foo -> test_foo)
+    pub fn test_method_name(&self, prefix: &str) -> String {
+        if self.name.is_empty() {
+            return prefixed_name(prefix, "example");
+        }
+        prefixed_name(prefix, &self.name)
+    }
+
+    /// Parameter types joined for use in a method name (int, String -> IntString)
+    /// None when the types are unknown or the method takes no parameters
+    pub fn parameter_summary(&self) -> Option<String> {
+        let types = self.parameter_types.as_ref().filter(|types| !types.is_empty())?;
+        Some(types.iter().map(|ty| capitalize(ty)).collect())
+    }
+
+    /// Comment lines for the body of the generated test stub
+    /// The TODO marker always comes first so cursor positioning still finds it
+    pub fn stub_body_comments(&self) -> Vec<String> {
+        let mut comments = vec![format!("// {}", DEFAULT_TODO_TEXT)];
+
+        if self.is_async {
+            comments.push(format!(
+                "// {}() is asynchronous: await its completion before asserting",
+                self.name
+            ));
+        }
+
+        if self.returns_value {
+            comments.push("// assert result".to_string());
+        }
+
+        comments
+    }
+}
+
+/// Text of the TODO comment every template puts in its test body
+pub const DEFAULT_TODO_TEXT: &str = "TODO: Implement test";
+
+/// Statement that fails a test until it is implemented, using only what the template imports
+/// None for frameworks without one (HSpec stubs are already `pending`)
+pub fn failing_statement(language: Language, framework: Framework, assertion_lib: Option<&str>) -> Option<&'static str> {
+    match (language, framework) {
+        (Language::Java, Framework::JUnit | Framework::JUnit4) => Some("fail(\"not implemented\");"),
+        (Language::Kotlin, Framework::JUnit) => Some("fail<Unit>(\"not implemented\")"),
+        (Language::Rust, Framework::Native | Framework::Rstest | Framework::Proptest) => Some("unimplemented!();"),
+        (Language::Zig, Framework::Native) => Some("return error.NotImplemented;"),
+        (_, Framework::Unittest) => Some("self.fail(\"not implemented\")"),
+        (_, Framework::Jasmine | Framework::Vitest) => Some("expect(false).toBe(true);"),
+        (_, Framework::Mocha) if assertion_lib == Some("chai") => Some("expect.fail('not implemented');"),
+        (_, Framework::Mocha) => Some("assert.fail('not implemented');"),
+        (_, Framework::GoTest) => Some("t.Fatal(\"not implemented\")"),
+        (_, Framework::ExUnit) => Some("flunk(\"not implemented\")"),
+        (_, Framework::PHPUnit) => Some("$this->fail('not implemented');"),
+        (_, Framework::ScalaTest | Framework::MUnit) => Some("fail(\"not implemented\")"),
+        (_, Framework::XUnit) => Some("Assert.Fail(\"not implemented\");"),
+        (_, Framework::DartTest) => Some("fail('not implemented');"),
+        (_, Framework::RSpec) => Some("raise 'not implemented'"),
+        (_, Framework::GoogleTest) => Some("FAIL() << \"not implemented\";"),
+        (_, Framework::XCTest) => Some("XCTFail(\"not implemented\")"),
+        _ => None,
+    }
+}
+
+/// Checklist of methods to cover, one `// TODO: test foo(int)` line each
+/// Parameters that span lines are shown as `...`
+pub fn api_checklist(methods: &[MethodSignature], language: Language) -> String {
+    let comment = match language {
+        Language::Python | Language::Elixir => "#",
+        Language::Haskell => "--",
+        _ => "//",
+    };
+
+    methods
+        .iter()
+        .map(|method| {
+            let parameters = match method.declared_types {
+                Some(ref types) => types.join(", "),
+                None => "...".to_string(),
+            };
+            format!("{} TODO: test {}({})\n", comment, method.name, parameters)
+        })
+        .collect()
+}
+
+/// Idiomatic test function prefix for a framework (JUnit `testFoo`, Rust/pytest `test_foo`)
+pub fn default_test_prefix(framework: Framework) -> &'static str {
+    match framework {
+        Framework::Native | Framework::Rstest | Framework::Proptest | Framework::Pytest | Framework::Unittest => "test_",
+        // `go test` only runs exported TestXxx functions; .NET methods are PascalCase
+        Framework::GoTest | Framework::XUnit => "Test",
+        _ => "test",
+    }
+}
+
+/// A prefix ending in `_` asks for snake_case test names
+fn is_snake_prefix(prefix: &str) -> bool {
+    prefix.ends_with('_')
+}
+
+/// Join a prefix and a name in the prefix's style
+/// `test` + getCount -> testGetCount, `should_` + getCount -> should_get_count
+pub fn prefixed_name(prefix: &str, name: &str) -> String {
+    if is_snake_prefix(prefix) {
+        format!("{}{}", prefix, to_snake_case(name))
+    } else if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}{}", prefix, capitalize(name))
+    }
+}
+
+/// Whether a name is a legal identifier in the language (keywords aside)
+pub fn is_valid_identifier(name: &str, language: Language) -> bool {
+    let allows_dollar = !matches!(language, Language::Rust | Language::Python | Language::Kotlin | Language::Go | Language::Elixir | Language::Php | Language::Scala | Language::CSharp | Language::Zig | Language::Dart | Language::Ruby | Language::Haskell | Language::Swift);
+    let is_start = |c: char| c.is_ascii_alphabetic() || c == '_' || (allows_dollar && c == '$');
+
+    let mut chars = name.chars();
+    chars.next().is_some_and(is_start) && chars.all(|c| is_start(c) || c.is_ascii_digit())
+}
+
+/// Names for the generated test methods, in source order and free of collisions
+/// Overloads are told apart by parameter types when every overload's types are known,
+/// otherwise the whole set falls back to numeric suffixes;
+/// names that would be reserved words in the language get a trailing `_`
+pub fn test_method_names(
+    methods: &[MethodSignature],
+    naming: MethodNaming,
+    prefix: &str,
+    language: Language,
+) -> Vec<String> {
+    let mut name_counts: HashMap<&str, usize> = HashMap::new();
+    let mut untyped: HashSet<&str> = HashSet::new();
+    for method in methods {
+        *name_counts.entry(method.name.as_str()).or_insert(0) += 1;
+        if method.parameter_summary().is_none() {
+            untyped.insert(method.name.as_str());
+        }
+    }
+
+    let mut occurrences: HashMap<&str, usize> = HashMap::new();
+    let mut used: HashSet<String> = HashSet::new();
+
+    methods
+        .iter()
+        .map(|method| {
+            let occurrence = occurrences.entry(method.name.as_str()).or_insert(0);
+            *occurrence += 1;
+            let by_types = name_counts[method.name.as_str()] > 1 && !untyped.contains(method.name.as_str());
+
+            let mut n = match naming {
+                MethodNaming::Camel => 1,
+                MethodNaming::Numbered => *occurrence,
+            };
+            let candidate_for = |n| keywords::escape_reserved(&naming_candidate(method, naming, prefix, by_types, n), language);
+            let mut candidate = candidate_for(n);
+            while used.contains(&candidate) {
+                n += 1;
+                candidate = candidate_for(n);
+            }
+
+            used.insert(candidate.clone());
+            candidate
+        })
+        .collect()
+}
+
+/// Name for the `n`th (1-based) candidate of a method's test under the given scheme
+/// `by_types` appends the parameter types (only set when they are known)
+fn naming_candidate(
+    method: &MethodSignature,
+    naming: MethodNaming,
+    prefix: &str,
+    by_types: bool,
+    n: usize,
+) -> String {
+    let snake = is_snake_prefix(prefix);
+
+    match naming {
+        MethodNaming::Camel => {
+            let base = method.test_method_name(prefix);
+            let base = match (by_types, &method.parameter_types, method.parameter_summary()) {
+                (true, Some(types), _) if snake => format!("{}_{}", base, types.join("_").to_lowercase()),
+                (true, _, Some(summary)) => format!("{}{}", base, summary),
+                _ => base,
+            };
+            match n {
+                1 => base,
+                _ if snake => format!("{}_{}", base, n),
+                _ => format!("{}{}", base, n),
+            }
+        }
+        MethodNaming::Numbered => match prefix.trim_end_matches('_') {
+            "" => format!("{}_{}", method.name, n),
+            stem => format!("{}_{}_{}", stem, method.name, n),
+        },
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => format!("{}{}", first.to_uppercase(), chars.as_str()),
+        None => String::new(),
+    }
+}
+
+/// Convert a camelCase name to snake_case (getCount -> get_count)
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut previous_lower = false;
+
+    for c in name.chars() {
+        if c.is_uppercase() && previous_lower {
+            snake.push('_');
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        snake.extend(c.to_lowercase());
+    }
+
+    snake
+}
+
+/// Split a parameter list on top-level commas (ignoring commas inside generics)
+fn split_parameters(params: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, c) in params.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(params[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(params[start..].trim());
+
+    parts.into_iter().filter(|part| !part.is_empty()).collect()
+}
+
+/// Reduce a declared type to a simple name (java.util.List<String> -> List, int[] -> IntArray)
+fn simple_type_name(declared: &str) -> Option<String> {
+    let declared = declared.trim().trim_end_matches('?');
+    let is_array = declared.ends_with("[]") || declared.ends_with("...");
+    let base = declared.split('<').next()?.trim_end_matches("[]").trim_end_matches("...").trim();
+    let base = base.rsplit('.').next()?.trim();
+
+    if base.is_empty() || !base.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$') {
+        return None;
+    }
+
+    let name = capitalize(base);
+    Some(if is_array { format!("{}Array", name) } else { name })
+}
+
+/// Simple names for a list of declared types (None if any type can't be reduced)
+fn simple_type_names(declared: &[String], simplify: fn(&str) -> Option<String>) -> Option<Vec<String>> {
+    declared.iter().map(|ty| simplify(ty)).collect()
+}
+
+/// Declared types of a Java parameter list (`final int a, List<String> b` -> [int, List<String>])
+fn java_declared_types(params: &str) -> Option<Vec<String>> {
+    split_parameters(params)
+        .into_iter()
+        .map(|param| {
+            let tokens: Vec<&str> = param
+                .split_whitespace()
+                .filter(|token| !token.starts_with('@') && *token != "final")
+                .collect();
+            let (_, type_tokens) = tokens.split_last()?;
+            (!type_tokens.is_empty()).then(|| type_tokens.join(" "))
+        })
+        .collect()
+}
+
+/// Declared types of a Kotlin parameter list (`id: Long, name: String = ""` -> [Long, String])
+fn kotlin_declared_types(params: &str) -> Option<Vec<String>> {
+    split_parameters(params)
+        .into_iter()
+        .map(|param| {
+            let (_, declared) = param.split_once(':')?;
+            Some(declared.split('=').next()?.trim().to_string())
+        })
+        .collect()
+}
+
+/// Java return types that represent an asynchronous computation
+const JAVA_ASYNC_TYPES: &[&str] = &[
+    "CompletableFuture",
+    "CompletionStage",
+    "Future",
+    "ListenableFuture",
+    "Mono",
+    "Flux",
+];
+
+/// Words that can precede `(` in Java without being a method declaration
+const JAVA_NON_METHOD_WORDS: &[&str] = &[
+    "if", "for", "while", "switch", "catch", "synchronized", "return", "new", "else", "throw",
+    "case", "public", "protected", "private", "static", "final", "abstract",
+];
+
+/// Extract method signatures from Java source
+pub fn extract_java_methods(content: &str) -> Vec<MethodSignature> {
+    let method_regex = Regex::new(
+        r"^\s*(?P<mods>(?:(?:public|protected|private|static|final|abstract|synchronized|default|native)\s+)*)(?P<ret>[\w.$]+(?:<.*>)?(?:\[\])*)\s+(?P<name>\w+)\s*\((?P<params>[^)]*\))?",
+    )
+    .unwrap();
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let caps = method_regex.captures(line)?;
+            let modifiers = &caps["mods"];
+            let return_type = &caps["ret"];
+            let name = &caps["name"];
+
+            if JAVA_NON_METHOD_WORDS.contains(&return_type) || JAVA_NON_METHOD_WORDS.contains(&name) {
+                return None;
+            }
+
+            let base_type = return_type.split('<').next().unwrap_or(return_type);
+            let base_type = base_type.rsplit('.').next().unwrap_or(base_type);
+
+            let declared_types = caps
+                .name("params")
+                .and_then(|params| java_declared_types(params.as_str().trim_end_matches(')')));
+
+            Some(MethodSignature {
+                name: name.to_string(),
+                is_private: modifiers.split_whitespace().any(|m| m == "private"),
+                is_async: JAVA_ASYNC_TYPES.contains(&base_type),
+                returns_value: return_type != "void",
+                parameter_types: declared_types.as_deref().and_then(|types| simple_type_names(types, simple_type_name)),
+                declared_types,
+            })
+        })
+        .collect()
+}
+
+/// Extract function signatures from Kotlin source
+pub fn extract_kotlin_methods(content: &str) -> Vec<MethodSignature> {
+    let fun_regex = Regex::new(
+        r"^\s*(?P<mods>(?:(?:public|internal|protected|private|override|open|suspend|inline|operator)\s+)*)fun\s+(?:<[^>]*>\s*)?(?P<name>\w+)\s*\((?P<params>[^)]*)\)\s*(?::\s*(?P<ret>[\w.<>?, ]+?))?\s*(?:[={]|$)",
+    )
+    .unwrap();
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let caps = fun_regex.captures(line)?;
+            let modifiers: Vec<&str> = caps["mods"].split_whitespace().collect();
+            let return_type = caps.name("ret").map(|m| m.as_str().trim());
+            let declared_types = kotlin_declared_types(&caps["params"]);
+
+            Some(MethodSignature {
+                name: caps["name"].to_string(),
+                is_private: modifiers.contains(&"private"),
+                is_async: modifiers.contains(&"suspend"),
+                returns_value: return_type.is_some_and(|ret| ret != "Unit"),
+                parameter_types: declared_types.as_deref().and_then(|types| simple_type_names(types, simple_type_name)),
+                declared_types,
+            })
+        })
+        .collect()
+}
+
+/// Extract function signatures from Rust source, stopping at the `#[cfg(test)]` module
+pub fn extract_rust_functions(content: &str) -> Vec<MethodSignature> {
+    let fn_regex = Regex::new(
+        r#"^\s*(?P<vis>pub(?:\([^)]*\))?\s+)?(?P<mods>(?:(?:const|async|unsafe|extern\s+"[^"]*")\s+)*)fn\s+(?:r#)?(?P<name>\w+)\s*(?:<.*?>)?\s*\((?:(?P<params>[^)]*)\)(?P<rest>.*))?$"#,
+    )
+    .unwrap();
+
+    content
+        .lines()
+        .take_while(|line| !line.contains("#[cfg(test)]"))
+        .filter_map(|line| {
+            let caps = fn_regex.captures(line)?;
+            let return_type = caps
+                .name("rest")
+                .and_then(|rest| rest.as_str().split_once("->"))
+                .and_then(|(_, ret)| ret.split(['{', ';']).next())
+                .map(|ret| ret.split(" where").next().unwrap_or(ret).trim());
+
+            let declared_types = caps.name("params").and_then(|params| rust_declared_types(params.as_str()));
+
+            Some(MethodSignature {
+                name: caps["name"].to_string(),
+                is_private: caps.name("vis").is_none(),
+                is_async: caps["mods"].split_whitespace().any(|m| m == "async"),
+                returns_value: return_type.is_some_and(|ret| !ret.is_empty() && ret != "()"),
+                parameter_types: declared_types.as_deref().and_then(|types| simple_type_names(types, rust_simple_type_name)),
+                declared_types,
+            })
+        })
+        .collect()
+}
+
+/// Declared types of a Rust parameter list, skipping `self` receivers (`&self, id: u32` -> [u32])
+fn rust_declared_types(params: &str) -> Option<Vec<String>> {
+    split_parameters(params)
+        .into_iter()
+        .filter(|param| !param.split(':').next().unwrap_or(param).trim().ends_with("self"))
+        .map(|param| {
+            let (_, declared) = param.split_once(':')?;
+            Some(declared.trim().to_string())
+        })
+        .collect()
+}
+
+/// Simple name of a Rust type, ignoring references and lifetimes (&'a mut Vec<u8> -> Vec)
+fn rust_simple_type_name(declared: &str) -> Option<String> {
+    let declared = declared.trim_start_matches('&');
+    let declared = match declared.strip_prefix('\'') {
+        Some(after_lifetime) => after_lifetime.split_once(' ')?.1,
+        None => declared,
+    };
+    simple_type_name(declared.trim_start_matches("mut "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_java_methods() {
+        let content = r#"
+public class Foo {
+    private int count;
+
+    public Foo(int count) {
+        this.count = count;
+    }
+
+    public int getCount() {
+        return count;
+    }
+
+    public void reset() {
+        if (count > 0) {
+            count = 0;
+        }
+    }
+
+    private void helper() {}
+}
+"#;
+        let methods = extract_java_methods(content);
+        let names: Vec<&str> = methods.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["getCount", "reset", "helper"]);
+        assert!(methods[0].returns_value);
+        assert!(!methods[1].returns_value);
+        assert!(methods[2].is_private);
+    }
+
+    #[test]
+    fn test_async_java_method_gets_async_comment() {
+        let content = "    public CompletableFuture<String> fetch(String id) {";
+        let methods = extract_java_methods(content);
+        assert_eq!(methods.len(), 1);
+        assert!(methods[0].is_async);
+
+        let comments = methods[0].stub_body_comments();
+        assert_eq!(comments[0], "// TODO: Implement test");
+        assert!(comments.iter().any(|c| c.contains("fetch() is asynchronous")));
+    }
+
+    #[test]
+    fn test_async_kotlin_function_gets_async_comment() {
+        let content = "    suspend fun loadUser(id: Long): User {";
+        let methods = extract_kotlin_methods(content);
+        assert_eq!(methods.len(), 1);
+        assert!(methods[0].is_async);
+        assert!(methods[0].returns_value);
+
+        let comments = methods[0].stub_body_comments();
+        assert!(comments.iter().any(|c| c.contains("loadUser() is asynchronous")));
+    }
+
+    #[test]
+    fn test_value_returning_method_gets_assert_hint() {
+        let methods = extract_java_methods("    public int add(int a, int b) {");
+        assert_eq!(
+            methods[0].stub_body_comments(),
+            vec!["// TODO: Implement test", "// assert result"]
+        );
+
+        let methods = extract_java_methods("    public void clear() {");
+        assert_eq!(methods[0].stub_body_comments(), vec!["// TODO: Implement test"]);
+    }
+
+    #[test]
+    fn test_kotlin_unit_function_has_no_assert_hint() {
+        let methods = extract_kotlin_methods("fun log(message: String): Unit {");
+        assert!(!methods[0].returns_value);
+
+        let methods = extract_kotlin_methods("fun log(message: String) {");
+        assert!(!methods[0].returns_value);
+    }
+
+    #[test]
+    fn test_test_method_name() {
+        let methods = extract_java_methods("    public int getCount() {");
+        assert_eq!(methods[0].test_method_name("test"), "testGetCount");
+    }
+
+    const OVERLOADED: &str = r#"
+public class Calculator {
+    public int add(int a, int b) {
+        return a + b;
+    }
+
+    public double add(double a, double b) {
+        return a + b;
+    }
+
+    public void clear() {
+    }
+
+    public long add(long a, long b) {
+        return a + b;
+    }
+}
+"#;
+
+    #[test]
+    fn test_camel_names_for_overloads_are_unique_and_in_source_order() {
+        let methods = extract_java_methods(OVERLOADED);
+        let names = test_method_names(&methods, MethodNaming::Camel, "test", Language::Java);
+        assert_eq!(names, vec!["testAddIntInt", "testAddDoubleDouble", "testClear", "testAddLongLong"]);
+        assert_eq!(test_method_names(&methods, MethodNaming::Camel, "test", Language::Java), names);
+    }
+
+    #[test]
+    fn test_numbered_names_for_overloads() {
+        let methods = extract_java_methods(OVERLOADED);
+        let names = test_method_names(&methods, MethodNaming::Numbered, "test", Language::Java);
+        assert_eq!(names, vec!["test_add_1", "test_add_2", "test_clear_1", "test_add_3"]);
+    }
+
+    #[test]
+    fn test_suffixed_name_does_not_collide_with_existing_method() {
+        let methods = extract_java_methods("    public void add() {\n    public void add2() {\n    public void add(int a) {\n");
+        let names = test_method_names(&methods, MethodNaming::Camel, "test", Language::Java);
+        assert_eq!(names, vec!["testAdd", "testAdd2", "testAdd3"]);
+    }
+
+    #[test]
+    fn test_typed_overload_does_not_collide_with_existing_method() {
+        let methods = extract_java_methods("    public void addInt() {\n    public void add(int a) {\n    public void add(long a) {\n");
+        let names = test_method_names(&methods, MethodNaming::Camel, "test", Language::Java);
+        assert_eq!(names, vec!["testAddInt", "testAddInt2", "testAddLong"]);
+    }
+
+    #[test]
+    fn test_overloads_named_by_parameter_types() {
+        let content = "    public void foo(int value) {\n    public void foo(String value) {\n    public void foo(List<Map<String, Integer>> rows, byte[] data) {\n";
+        let methods = extract_java_methods(content);
+        assert_eq!(methods[2].parameter_types, Some(vec!["List".to_string(), "ByteArray".to_string()]));
+
+        let names = test_method_names(&methods, MethodNaming::Camel, "test", Language::Java);
+        assert_eq!(names, vec!["testFooInt", "testFooString", "testFooListByteArray"]);
+    }
+
+    #[test]
+    fn test_kotlin_overloads_named_by_parameter_types() {
+        let content = "fun find(id: Long): User? {\nfun find(name: String, limit: Int = 10): User? {\n";
+        let methods = extract_kotlin_methods(content);
+        let names = test_method_names(&methods, MethodNaming::Camel, "test", Language::Java);
+        assert_eq!(names, vec!["testFindLong", "testFindStringInt"]);
+    }
+
+    #[test]
+    fn test_overloads_without_summarizable_types_fall_back_to_numbers() {
+        let content = "    public void run(Runnable task) {\n    public void run(\n    public void run(Callable<T> task) {\n";
+        let methods = extract_java_methods(content);
+        assert_eq!(methods[1].parameter_types, None);
+
+        let names = test_method_names(&methods, MethodNaming::Camel, "test", Language::Java);
+        assert_eq!(names, vec!["testRun", "testRun2", "testRun3"]);
+    }
+
+    #[test]
+    fn test_api_checklist_lists_public_methods() {
+        let methods: Vec<MethodSignature> = extract_java_methods(
+            "    public int foo(int count) {\n    public void bar() {\n    private void hidden() {\n",
+        )
+        .into_iter()
+        .filter(|method| !method.is_private)
+        .collect();
+
+        assert_eq!(
+            api_checklist(&methods, Language::Java),
+            "// TODO: test foo(int)\n// TODO: test bar()\n"
+        );
+    }
+
+    #[test]
+    fn test_should_prefix_for_java() {
+        let methods = extract_java_methods("    public void doThing() {\n    public int getCount() {\n");
+        assert_eq!(
+            test_method_names(&methods, MethodNaming::Camel, "should", Language::Java),
+            vec!["shouldDoThing", "shouldGetCount"]
+        );
+        assert_eq!(
+            test_method_names(&methods, MethodNaming::Camel, "should_", Language::Java),
+            vec!["should_do_thing", "should_get_count"]
+        );
+    }
+
+    #[test]
+    fn test_extract_rust_functions_with_test_prefix() {
+        let content = r#"
+pub fn parse(input: &str) -> Result<Ast, Error> {
+}
+
+pub async fn fetch(&self, id: u32) {
+}
+
+fn helper() {}
+
+#[cfg(test)]
+mod tests {
+    fn not_extracted() {}
+}
+"#;
+        let functions = extract_rust_functions(content);
+        let names: Vec<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["parse", "fetch", "helper"]);
+        assert!(functions[0].returns_value);
+        assert!(functions[1].is_async);
+        assert!(!functions[1].returns_value);
+        assert_eq!(functions[1].parameter_types, Some(vec!["U32".to_string()]));
+        assert!(functions[2].is_private);
+
+        assert_eq!(
+            test_method_names(&functions, MethodNaming::Camel, default_test_prefix(Framework::Native), Language::Rust),
+            vec!["test_parse", "test_fetch", "test_helper"]
+        );
+    }
+
+    #[test]
+    fn test_prefix_must_form_identifier() {
+        assert!(is_valid_identifier(&prefixed_name("should", "example"), Language::Java));
+        assert!(is_valid_identifier(&prefixed_name("$", "example"), Language::Java));
+        assert!(!is_valid_identifier(&prefixed_name("$", "example"), Language::Rust));
+        assert!(!is_valid_identifier(&prefixed_name("1", "example"), Language::Rust));
+        assert!(!is_valid_identifier(&prefixed_name("it-", "example"), Language::Java));
+    }
+
+    #[test]
+    fn test_keyword_method_name_gets_legal_test_name() {
+        let functions = extract_rust_functions("pub fn r#match(pattern: &str) -> bool {\n");
+        assert_eq!(functions[0].name, "match");
+        assert_eq!(test_method_names(&functions, MethodNaming::Camel, "", Language::Rust), vec!["match_"]);
+        assert_eq!(test_method_names(&functions, MethodNaming::Camel, "test_", Language::Rust), vec!["test_match"]);
+
+        let generated = MethodSignature {
+            name: "return".to_string(),
+            is_private: false,
+            is_async: false,
+            returns_value: false,
+            parameter_types: Some(Vec::new()),
+            declared_types: Some(Vec::new()),
+        };
+        assert_eq!(test_method_names(std::slice::from_ref(&generated), MethodNaming::Camel, "", Language::Java), vec!["return_"]);
+        assert_eq!(test_method_names(&[generated], MethodNaming::Camel, "test", Language::Java), vec!["testReturn"]);
+    }
+}
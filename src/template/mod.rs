@@ -1,8 +1,30 @@
+pub mod component_vitest;
+pub mod cpp_gtest;
+pub mod csharp_xunit;
+pub mod dart_test;
+pub mod elixir_exunit;
+pub mod go_testing;
+pub mod haskell_hspec;
 pub mod java_junit;
 pub mod java_junit4;
+pub mod js_index;
+pub mod js_jasmine;
+pub mod js_mocha;
+pub mod keywords;
+pub mod kotlin_junit;
+pub mod method_stub;
+pub mod php_phpunit;
+pub mod python_unittest;
 pub mod registry;
+pub mod ruby_rspec;
 pub mod rust_native;
+pub mod rust_proptest;
+pub mod rust_rstest;
+pub mod scala_munit;
+pub mod scala_scalatest;
+pub mod swift_xctest;
 pub mod traits;
+pub mod zig_native;
 
 pub use registry::TemplateRegistry;
 pub use traits::{TemplateContext, TemplateGenerator};
@@ -0,0 +1,149 @@
+use crate::cli::{Framework, Language};
+use crate::error::TestsmithError;
+use crate::template::method_stub;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+use regex::Regex;
+
+/// Namespace prefix for tests when `.testsmithrc` doesn't set `php_test_namespace`
+pub const DEFAULT_TEST_NAMESPACE_PREFIX: &str = "Tests";
+
+/// PHPUnit template laid out per PSR-12 (braces on their own line for classes and methods)
+pub struct PhpUnitTemplate;
+
+impl PhpUnitTemplate {
+    pub fn new() -> Self {
+        PhpUnitTemplate
+    }
+
+    /// Extract the namespace declared in PHP source (namespace App\Foo;)
+    pub fn extract_namespace(source_content: &str) -> Option<String> {
+        let namespace_regex = Regex::new(r"^\s*namespace\s+([\w\\]+)\s*;").unwrap();
+
+        source_content.lines().find_map(|line| {
+            namespace_regex
+                .captures(line)
+                .and_then(|caps| caps.get(1))
+                .map(|namespace| namespace.as_str().trim_matches('\\').to_string())
+        })
+    }
+
+    /// Namespace for the test of a class in `source_namespace`
+    /// (`App\Foo` with prefix `Tests` -> `Tests\App\Foo`; an empty prefix keeps it unchanged)
+    pub fn test_namespace(source_namespace: &str, prefix: &str) -> String {
+        let prefix = prefix.trim_matches('\\');
+        if prefix.is_empty() {
+            source_namespace.to_string()
+        } else {
+            format!("{}\\{}", prefix, source_namespace)
+        }
+    }
+}
+
+impl Default for PhpUnitTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateGenerator for PhpUnitTemplate {
+    fn generate(&self, context: &TemplateContext) -> Result<String, TestsmithError> {
+        let test_class_name = context.test_class_name().unwrap_or_else(|| {
+            format!("{}Test", context.class_name.as_deref().unwrap_or("Example"))
+        });
+
+        let namespace_part = match context.package_name {
+            Some(ref namespace) => format!("namespace {};\n\n", namespace),
+            None => String::new(),
+        };
+
+        let template = format!(
+            "<?php\n\n{}use PHPUnit\\Framework\\TestCase;\n\nclass {} extends TestCase\n{{\n    public function {}(): void\n    {{\n        // TODO: Implement test\n    }}\n}}\n",
+            namespace_part,
+            test_class_name,
+            method_stub::prefixed_name(context.test_prefix(), "example")
+        );
+
+        Ok(template)
+    }
+
+    fn name(&self) -> &'static str {
+        "PHPUnit"
+    }
+
+    fn language(&self) -> Language {
+        Language::Php
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::PHPUnit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_template() {
+        let template = PhpUnitTemplate::new();
+        let context = TemplateContext::new(
+            "src/Foo.php".into(),
+            "tests/FooTest.php".into(),
+            Language::Php,
+            Framework::PHPUnit,
+        );
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(
+            result,
+            "<?php\n\nuse PHPUnit\\Framework\\TestCase;\n\nclass FooTest extends TestCase\n{\n    public function testExample(): void\n    {\n        // TODO: Implement test\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_extract_namespace() {
+        let source = "<?php\n\ndeclare(strict_types=1);\n\nnamespace App\\Http\\Controllers;\n\nclass UserController {}\n";
+        assert_eq!(
+            PhpUnitTemplate::extract_namespace(source),
+            Some("App\\Http\\Controllers".to_string())
+        );
+        assert_eq!(PhpUnitTemplate::extract_namespace("<?php\n\nclass Foo {}\n"), None);
+    }
+
+    #[test]
+    fn test_test_namespace() {
+        assert_eq!(PhpUnitTemplate::test_namespace("App\\Foo", "Tests"), "Tests\\App\\Foo");
+        assert_eq!(PhpUnitTemplate::test_namespace("App\\Foo", "Tests\\Unit\\"), "Tests\\Unit\\App\\Foo");
+        assert_eq!(PhpUnitTemplate::test_namespace("App\\Foo", ""), "App\\Foo");
+    }
+
+    #[test]
+    fn test_generate_template_with_namespace() {
+        let template = PhpUnitTemplate::new();
+        let context = TemplateContext::new(
+            "src/Foo.php".into(),
+            "tests/FooTest.php".into(),
+            Language::Php,
+            Framework::PHPUnit,
+        )
+        .with_package_name("Tests\\App".to_string());
+
+        let result = template.generate(&context).unwrap();
+        assert!(result.starts_with("<?php\n\nnamespace Tests\\App;\n\nuse PHPUnit\\Framework\\TestCase;\n"));
+    }
+
+    #[test]
+    fn test_generate_template_with_custom_prefix() {
+        let template = PhpUnitTemplate::new();
+        let context = TemplateContext::new(
+            "src/Foo.php".into(),
+            "tests/FooTest.php".into(),
+            Language::Php,
+            Framework::PHPUnit,
+        )
+        .with_method_prefix("it".to_string());
+
+        let result = template.generate(&context).unwrap();
+        assert!(result.contains("public function itExample(): void"));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::cli::{Framework, Language};
+use crate::error::TestsmithError;
+use crate::template::method_stub;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+use regex::Regex;
+use std::path::Path;
+
+/// Entry point that lets the test file run on its own (`python tests/test_foo.py`)
+pub const MAIN_GUARD: &str = "if __name__ == \"__main__\":\n    unittest.main()\n";
+
+/// Standard library `unittest` template; the test case is `Test` plus the module in PascalCase
+pub struct PythonUnittestTemplate;
+
+impl PythonUnittestTemplate {
+    pub fn new() -> Self {
+        PythonUnittestTemplate
+    }
+
+    /// Test case class name for a source file (user_service.py -> TestUserService)
+    pub fn test_class_name(source_path: &Path) -> String {
+        let pascal: String = source_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("example")
+            .split('_')
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let mut chars = part.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                    None => String::new(),
+                }
+            })
+            .collect();
+
+        format!("Test{}", pascal)
+    }
+
+    /// Text to append so a test file ends with the `__main__` guard, or None if it has one
+    pub fn missing_main_guard(content: &str) -> Option<String> {
+        let guard = Regex::new(r#"(?m)^if\s+__name__\s*==\s*["']__main__["']\s*:"#).unwrap();
+        if guard.is_match(content) {
+            return None;
+        }
+
+        let separator = match content {
+            "" => "",
+            _ if content.ends_with('\n') => "\n\n",
+            _ => "\n\n\n",
+        };
+        Some(format!("{}{}", separator, MAIN_GUARD))
+    }
+}
+
+impl Default for PythonUnittestTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateGenerator for PythonUnittestTemplate {
+    fn generate(&self, context: &TemplateContext) -> Result<String, TestsmithError> {
+        let template = format!(
+            "import unittest\n\n\nclass {}(unittest.TestCase):\n    def {}(self):\n        # TODO: Implement test\n        pass\n\n\n{}",
+            Self::test_class_name(&context.source_file_path),
+            method_stub::prefixed_name(context.test_prefix(), "example"),
+            MAIN_GUARD
+        );
+
+        Ok(template)
+    }
+
+    fn name(&self) -> &'static str {
+        "Python unittest"
+    }
+
+    fn language(&self) -> Language {
+        Language::Python
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::Unittest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_template() {
+        let template = PythonUnittestTemplate::new();
+        let context = TemplateContext::new(
+            "src/user_service.py".into(),
+            "tests/user_service_test.py".into(),
+            Language::Python,
+            Framework::Unittest,
+        );
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(
+            result,
+            "import unittest\n\n\nclass TestUserService(unittest.TestCase):\n    def test_example(self):\n        # TODO: Implement test\n        pass\n\n\nif __name__ == \"__main__\":\n    unittest.main()\n"
+        );
+    }
+
+    #[test]
+    fn test_missing_main_guard() {
+        let generated = PythonUnittestTemplate::new()
+            .generate(&TemplateContext::new(
+                "src/parser.py".into(),
+                "tests/test_parser.py".into(),
+                Language::Python,
+                Framework::Unittest,
+            ))
+            .unwrap();
+        assert_eq!(PythonUnittestTemplate::missing_main_guard(&generated), None);
+        assert_eq!(PythonUnittestTemplate::missing_main_guard("if __name__ == '__main__':\n    main()\n"), None);
+
+        let without_guard = "import unittest\n\n\nclass TestParser(unittest.TestCase):\n    pass\n";
+        assert_eq!(
+            PythonUnittestTemplate::missing_main_guard(without_guard),
+            Some(format!("\n\n{}", MAIN_GUARD))
+        );
+    }
+
+    #[test]
+    fn test_class_name_is_test_plus_pascal_case() {
+        assert_eq!(PythonUnittestTemplate::test_class_name(Path::new("parser.py")), "TestParser");
+        assert_eq!(
+            PythonUnittestTemplate::test_class_name(Path::new("pkg/http_client_utils.py")),
+            "TestHttpClientUtils"
+        );
+    }
+}
@@ -1,9 +1,28 @@
 use crate::cli::{Framework, Language};
 use crate::error::TestsmithError;
+use crate::template::component_vitest::ComponentVitestTemplate;
+use crate::template::cpp_gtest::CppGoogleTestTemplate;
+use crate::template::csharp_xunit::CSharpXUnitTemplate;
+use crate::template::dart_test::DartTestTemplate;
+use crate::template::elixir_exunit::ElixirExUnitTemplate;
+use crate::template::go_testing::GoTestingTemplate;
 use crate::template::java_junit::JavaJunitTemplate;
 use crate::template::java_junit4::JavaJunit4Template;
+use crate::template::js_jasmine::JasmineTemplate;
+use crate::template::js_mocha::MochaTemplate;
+use crate::template::haskell_hspec::HaskellHSpecTemplate;
+use crate::template::kotlin_junit::KotlinJunitTemplate;
+use crate::template::php_phpunit::PhpUnitTemplate;
+use crate::template::python_unittest::PythonUnittestTemplate;
+use crate::template::ruby_rspec::RubyRSpecTemplate;
 use crate::template::rust_native::RustNativeTemplate;
+use crate::template::rust_proptest::RustProptestTemplate;
+use crate::template::rust_rstest::RustRstestTemplate;
+use crate::template::scala_munit::ScalaMUnitTemplate;
+use crate::template::scala_scalatest::ScalaTestTemplate;
+use crate::template::swift_xctest::SwiftXCTestTemplate;
 use crate::template::traits::TemplateGenerator;
+use crate::template::zig_native::ZigNativeTemplate;
 use std::collections::HashMap;
 
 pub struct TemplateRegistry {
@@ -27,12 +46,132 @@ impl TemplateRegistry {
             Box::new(JavaJunit4Template::new()) as Box<dyn TemplateGenerator>,
         );
 
+        // Register Kotlin/JUnit template
+        generators.insert(
+            (Language::Kotlin, Framework::JUnit),
+            Box::new(KotlinJunitTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
         // Register Rust/Native template
         generators.insert(
             (Language::Rust, Framework::Native),
             Box::new(RustNativeTemplate::new()) as Box<dyn TemplateGenerator>,
         );
 
+        // Register Rust/rstest and Rust/proptest templates
+        generators.insert(
+            (Language::Rust, Framework::Rstest),
+            Box::new(RustRstestTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+        generators.insert(
+            (Language::Rust, Framework::Proptest),
+            Box::new(RustProptestTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register Zig/Native template
+        generators.insert(
+            (Language::Zig, Framework::Native),
+            Box::new(ZigNativeTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register JavaScript/TypeScript Jasmine templates
+        generators.insert(
+            (Language::JavaScript, Framework::Jasmine),
+            Box::new(JasmineTemplate::new(Language::JavaScript)) as Box<dyn TemplateGenerator>,
+        );
+        generators.insert(
+            (Language::TypeScript, Framework::Jasmine),
+            Box::new(JasmineTemplate::new(Language::TypeScript)) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register Ruby/RSpec template
+        generators.insert(
+            (Language::Ruby, Framework::RSpec),
+            Box::new(RubyRSpecTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register Haskell/HSpec template
+        generators.insert(
+            (Language::Haskell, Framework::HSpec),
+            Box::new(HaskellHSpecTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register Swift/XCTest template
+        generators.insert(
+            (Language::Swift, Framework::XCTest),
+            Box::new(SwiftXCTestTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register JavaScript/Mocha template
+        generators.insert(
+            (Language::JavaScript, Framework::Mocha),
+            Box::new(MochaTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register Vue/Svelte component templates
+        generators.insert(
+            (Language::Vue, Framework::Vitest),
+            Box::new(ComponentVitestTemplate::new(Language::Vue)) as Box<dyn TemplateGenerator>,
+        );
+        generators.insert(
+            (Language::Svelte, Framework::Vitest),
+            Box::new(ComponentVitestTemplate::new(Language::Svelte)) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register Go/testing template
+        generators.insert(
+            (Language::Go, Framework::GoTest),
+            Box::new(GoTestingTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register Elixir/ExUnit template
+        generators.insert(
+            (Language::Elixir, Framework::ExUnit),
+            Box::new(ElixirExUnitTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register Scala/ScalaTest template
+        generators.insert(
+            (Language::Scala, Framework::ScalaTest),
+            Box::new(ScalaTestTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register Scala/MUnit template
+        generators.insert(
+            (Language::Scala, Framework::MUnit),
+            Box::new(ScalaMUnitTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register Python/unittest template
+        generators.insert(
+            (Language::Python, Framework::Unittest),
+            Box::new(PythonUnittestTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register PHP/PHPUnit template
+        generators.insert(
+            (Language::Php, Framework::PHPUnit),
+            Box::new(PhpUnitTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register C#/xUnit template
+        generators.insert(
+            (Language::CSharp, Framework::XUnit),
+            Box::new(CSharpXUnitTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register Dart/test template
+        generators.insert(
+            (Language::Dart, Framework::DartTest),
+            Box::new(DartTestTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
+        // Register C++/GoogleTest template
+        generators.insert(
+            (Language::Cpp, Framework::GoogleTest),
+            Box::new(CppGoogleTestTemplate::new()) as Box<dyn TemplateGenerator>,
+        );
+
         TemplateRegistry { generators }
     }
 
@@ -89,6 +228,13 @@ mod tests {
         assert!(registry.is_supported(Language::Rust, Framework::Native));
     }
 
+    #[test]
+    fn test_registry_contains_rust_rstest_and_proptest() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Rust, Framework::Rstest));
+        assert!(registry.is_supported(Language::Rust, Framework::Proptest));
+    }
+
     #[test]
     fn test_registry_contains_java_junit4() {
         let registry = TemplateRegistry::new();
@@ -123,4 +269,106 @@ mod tests {
         let registry = TemplateRegistry::new();
         assert!(!registry.is_supported(Language::Python, Framework::Pytest));
     }
+
+    #[test]
+    fn test_registry_contains_component_templates() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Vue, Framework::Vitest));
+        assert!(registry.is_supported(Language::Svelte, Framework::Vitest));
+    }
+
+    #[test]
+    fn test_registry_contains_kotlin_junit() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Kotlin, Framework::JUnit));
+        assert!(!registry.is_supported(Language::Kotlin, Framework::JUnit4));
+    }
+
+    #[test]
+    fn test_registry_contains_python_unittest() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Python, Framework::Unittest));
+    }
+
+    #[test]
+    fn test_registry_contains_go_testing() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Go, Framework::GoTest));
+    }
+
+    #[test]
+    fn test_registry_contains_elixir_exunit() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Elixir, Framework::ExUnit));
+    }
+
+    #[test]
+    fn test_registry_contains_scala_scalatest() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Scala, Framework::ScalaTest));
+        assert!(registry.is_supported(Language::Scala, Framework::MUnit));
+    }
+
+    #[test]
+    fn test_registry_contains_php_phpunit() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Php, Framework::PHPUnit));
+    }
+
+    #[test]
+    fn test_registry_contains_zig_native() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Zig, Framework::Native));
+    }
+
+    #[test]
+    fn test_registry_contains_js_jasmine() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::JavaScript, Framework::Jasmine));
+        assert!(registry.is_supported(Language::TypeScript, Framework::Jasmine));
+    }
+
+    #[test]
+    fn test_registry_contains_js_mocha() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::JavaScript, Framework::Mocha));
+        assert!(!registry.is_supported(Language::TypeScript, Framework::Mocha));
+    }
+
+    #[test]
+    fn test_registry_contains_ruby_rspec() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Ruby, Framework::RSpec));
+    }
+
+    #[test]
+    fn test_registry_contains_haskell_hspec() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Haskell, Framework::HSpec));
+    }
+
+    #[test]
+    fn test_registry_contains_swift_xctest() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Swift, Framework::XCTest));
+    }
+
+    #[test]
+    fn test_registry_contains_dart_test() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Dart, Framework::DartTest));
+    }
+
+    #[test]
+    fn test_registry_contains_csharp_xunit() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::CSharp, Framework::XUnit));
+    }
+
+    #[test]
+    fn test_registry_contains_cpp_googletest() {
+        let registry = TemplateRegistry::new();
+        assert!(registry.is_supported(Language::Cpp, Framework::GoogleTest));
+        assert!(!registry.is_supported(Language::Cpp, Framework::Native));
+    }
 }
@@ -0,0 +1,105 @@
+use crate::cli::{Framework, Language};
+use crate::error::TestsmithError;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+use std::path::{Component, Path};
+
+/// RSpec template; the described constant follows the Zeitwerk layout under `lib/`
+pub struct RubyRSpecTemplate;
+
+impl RubyRSpecTemplate {
+    pub fn new() -> Self {
+        RubyRSpecTemplate
+    }
+
+    /// Constant for a file, namespaced by its directories below `lib/`
+    /// (lib/my_gem/price_list.rb -> MyGem::PriceList)
+    fn constant_name(source_path: &Path) -> String {
+        let without_extension = source_path.with_extension("");
+        let components: Vec<&str> = without_extension
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => part.to_str(),
+                _ => None,
+            })
+            .collect();
+        let start = components
+            .iter()
+            .rposition(|part| *part == "lib")
+            .map_or(components.len().saturating_sub(1), |idx| idx + 1);
+
+        components[start..]
+            .iter()
+            .map(|part| {
+                part.split('_')
+                    .filter(|word| !word.is_empty())
+                    .map(|word| {
+                        let mut chars = word.chars();
+                        match chars.next() {
+                            Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                            None => String::new(),
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+}
+
+impl Default for RubyRSpecTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateGenerator for RubyRSpecTemplate {
+    fn generate(&self, context: &TemplateContext) -> Result<String, TestsmithError> {
+        // RuboCop indents with two spaces
+        let template = format!(
+            "require 'rspec'\n\nRSpec.describe {} do\n  it 'does something' do\n    # TODO: Implement test\n  end\nend\n",
+            Self::constant_name(&context.source_file_path)
+        );
+
+        Ok(template)
+    }
+
+    fn name(&self) -> &'static str {
+        "Ruby RSpec"
+    }
+
+    fn language(&self) -> Language {
+        Language::Ruby
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::RSpec
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_template() {
+        let template = RubyRSpecTemplate::new();
+        let context = TemplateContext::new(
+            "lib/foo.rb".into(),
+            "spec/foo_spec.rb".into(),
+            Language::Ruby,
+            Framework::RSpec,
+        );
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(
+            result,
+            "require 'rspec'\n\nRSpec.describe Foo do\n  it 'does something' do\n    # TODO: Implement test\n  end\nend\n"
+        );
+    }
+
+    #[test]
+    fn test_constant_is_namespaced_by_lib_subdirectories() {
+        assert_eq!(RubyRSpecTemplate::constant_name(Path::new("/gem/lib/my_gem/price_list.rb")), "MyGem::PriceList");
+        assert_eq!(RubyRSpecTemplate::constant_name(Path::new("app/models/user.rb")), "User");
+    }
+}
@@ -0,0 +1,74 @@
+use crate::cli::{Framework, Language};
+use crate::error::TestsmithError;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+
+/// MUnit `FunSuite` template, indented two spaces as scalafmt does
+pub struct ScalaMUnitTemplate;
+
+impl ScalaMUnitTemplate {
+    pub fn new() -> Self {
+        ScalaMUnitTemplate
+    }
+}
+
+impl Default for ScalaMUnitTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateGenerator for ScalaMUnitTemplate {
+    fn generate(&self, context: &TemplateContext) -> Result<String, TestsmithError> {
+        let package_part = if let Some(ref package_name) = context.package_name {
+            format!("package {}\n\n", package_name)
+        } else {
+            String::new()
+        };
+
+        let suite_class_name = context.test_class_name().unwrap_or_else(|| {
+            format!("{}Suite", context.class_name.as_deref().unwrap_or("Example"))
+        });
+
+        let template = format!(
+            "{}class {} extends munit.FunSuite {{\n  test(\"example\") {{\n    // TODO: Implement test\n  }}\n}}\n",
+            package_part, suite_class_name
+        );
+
+        Ok(template)
+    }
+
+    fn name(&self) -> &'static str {
+        "Scala MUnit"
+    }
+
+    fn language(&self) -> Language {
+        Language::Scala
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::MUnit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_template() {
+        let template = ScalaMUnitTemplate::new();
+        let context = TemplateContext::new(
+            "src/main/scala/com/example/Foo.scala".into(),
+            "src/test/scala/com/example/FooSpec.scala".into(),
+            Language::Scala,
+            Framework::MUnit,
+        )
+        .with_package_name("com.example".to_string());
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(
+            result,
+            "package com.example\n\nclass FooSpec extends munit.FunSuite {\n  test(\"example\") {\n    // TODO: Implement test\n  }\n}\n"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use crate::cli::{Framework, Language};
+use crate::config::framework_detector::uncommented_lines;
+use crate::error::TestsmithError;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+use regex::Regex;
+
+/// ScalaTest `AnyFunSuite` template, indented two spaces as scalafmt does
+pub struct ScalaTestTemplate;
+
+impl ScalaTestTemplate {
+    pub fn new() -> Self {
+        ScalaTestTemplate
+    }
+
+    /// Extract package name from Scala source file (package com.example)
+    pub fn extract_package_name(source_content: &str) -> Option<String> {
+        let package_regex = Regex::new(r"^\s*package\s+([\w\.]+)\s*;?\s*$").unwrap();
+
+        for line in uncommented_lines(source_content) {
+            if let Some(caps) = package_regex.captures(&line)
+                && let Some(package_name) = caps.get(1)
+            {
+                return Some(package_name.as_str().to_string());
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for ScalaTestTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateGenerator for ScalaTestTemplate {
+    fn generate(&self, context: &TemplateContext) -> Result<String, TestsmithError> {
+        let package_part = if let Some(ref package_name) = context.package_name {
+            format!("package {}\n\n", package_name)
+        } else {
+            String::new()
+        };
+
+        let spec_class_name = context.test_class_name().unwrap_or_else(|| {
+            format!("{}Spec", context.class_name.as_deref().unwrap_or("Example"))
+        });
+
+        let template = format!(
+            "{}import org.scalatest.funsuite.AnyFunSuite\n\nclass {} extends AnyFunSuite {{\n  test(\"example\") {{\n    // TODO: Implement test\n  }}\n}}\n",
+            package_part, spec_class_name
+        );
+
+        Ok(template)
+    }
+
+    fn name(&self) -> &'static str {
+        "ScalaTest"
+    }
+
+    fn language(&self) -> Language {
+        Language::Scala
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::ScalaTest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_package_name() {
+        let content = "/* package com.fake */\npackage com.example.foo\n\nclass Foo";
+
+        let package_name = ScalaTestTemplate::extract_package_name(content);
+        assert_eq!(package_name, Some("com.example.foo".to_string()));
+    }
+
+    #[test]
+    fn test_generate_template() {
+        let template = ScalaTestTemplate::new();
+        let context = TemplateContext::new(
+            "src/main/scala/com/example/Foo.scala".into(),
+            "src/test/scala/com/example/FooSpec.scala".into(),
+            Language::Scala,
+            Framework::ScalaTest,
+        )
+        .with_package_name("com.example".to_string());
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(
+            result,
+            "package com.example\n\nimport org.scalatest.funsuite.AnyFunSuite\n\nclass FooSpec extends AnyFunSuite {\n  test(\"example\") {\n    // TODO: Implement test\n  }\n}\n"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use crate::cli::{Framework, Language};
+use crate::error::TestsmithError;
+use crate::template::method_stub;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+use std::path::Path;
+
+/// XCTest template; the test case imports the source's SwiftPM target with `@testable`
+/// so internal declarations are visible
+pub struct SwiftXCTestTemplate;
+
+impl SwiftXCTestTemplate {
+    pub fn new() -> Self {
+        SwiftXCTestTemplate
+    }
+
+    /// SwiftPM target holding a source: the directory below `Sources/`
+    /// (Sources/Parser/Lexer.swift -> Parser)
+    pub fn target_name(source_path: &Path) -> Option<String> {
+        let components: Vec<_> = source_path.components().map(|c| c.as_os_str()).collect();
+        let sources_idx = components.iter().rposition(|component| *component == "Sources")?;
+
+        // The file itself sits below the target directory
+        if sources_idx + 2 >= components.len() {
+            return None;
+        }
+        components[sources_idx + 1].to_str().map(str::to_string)
+    }
+}
+
+impl Default for SwiftXCTestTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateGenerator for SwiftXCTestTemplate {
+    fn generate(&self, context: &TemplateContext) -> Result<String, TestsmithError> {
+        let test_class_name = context.test_class_name().unwrap_or_else(|| {
+            format!("{}Tests", context.class_name.as_deref().unwrap_or("Example"))
+        });
+        let testable_import = Self::target_name(&context.source_file_path)
+            .map(|target| format!("@testable import {}\n", target))
+            .unwrap_or_default();
+
+        let template = format!(
+            "import XCTest\n{}\nfinal class {}: XCTestCase {{\n    func {}() {{\n        // TODO: Implement test\n    }}\n}}\n",
+            testable_import,
+            test_class_name,
+            method_stub::prefixed_name(context.test_prefix(), "example")
+        );
+
+        Ok(template)
+    }
+
+    fn name(&self) -> &'static str {
+        "Swift XCTest"
+    }
+
+    fn language(&self) -> Language {
+        Language::Swift
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::XCTest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_template() {
+        let template = SwiftXCTestTemplate::new();
+        let context = TemplateContext::new(
+            "Sources/Parser/Lexer.swift".into(),
+            "Tests/ParserTests/LexerTests.swift".into(),
+            Language::Swift,
+            Framework::XCTest,
+        );
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(
+            result,
+            "import XCTest\n@testable import Parser\n\nfinal class LexerTests: XCTestCase {\n    func testExample() {\n        // TODO: Implement test\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_target_name() {
+        assert_eq!(SwiftXCTestTemplate::target_name(Path::new("pkg/Sources/Parser/Lexer.swift")), Some("Parser".to_string()));
+        assert_eq!(SwiftXCTestTemplate::target_name(Path::new("pkg/Sources/Parser/Tokens/Token.swift")), Some("Parser".to_string()));
+        assert_eq!(SwiftXCTestTemplate::target_name(Path::new("pkg/Sources/main.swift")), None);
+        assert_eq!(SwiftXCTestTemplate::target_name(Path::new("App/Lexer.swift")), None);
+    }
+}
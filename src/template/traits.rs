@@ -1,5 +1,7 @@
-use crate::cli::{Framework, Language};
+use crate::cli::{Framework, Language, MethodNaming};
+use crate::config::framework_detector::PropertyLibrary;
 use crate::error::TestsmithError;
+use crate::template::method_stub::{self, MethodSignature};
 use std::path::PathBuf;
 
 /// Context information needed to generate a test file
@@ -19,6 +21,23 @@ pub struct TemplateContext {
     pub package_name: Option<String>,
     /// Module path (for Rust)
     pub module_path: Option<String>,
+    /// Source methods to generate individual test stubs for (empty = single example test)
+    pub methods: Vec<MethodSignature>,
+    /// Naming scheme for the per-method test stubs
+    pub method_naming: MethodNaming,
+    /// Prefix for generated test function names (None = idiomatic default for the framework)
+    pub method_prefix: Option<String>,
+    /// Property-based testing library to write a property stub for (None = example test)
+    pub property_library: Option<PropertyLibrary>,
+    /// Import specifier for the source file (for Dart, `package:<pkg>/foo.dart` or a relative path)
+    pub source_import: Option<String>,
+    /// Assertion library detected for the project (for Mocha, "chai"; for Rust, "pretty_assertions";
+    /// None = the built-in assertions)
+    pub assertion_lib: Option<String>,
+    /// Whether to write parameterized tests (JUnit 5 `@ParameterizedTest` with `@ValueSource`)
+    pub parameterized: bool,
+    /// Whether to wrap the per-method test stubs in `// region` / `// endregion` fold markers
+    pub section_markers: bool,
 }
 
 impl TemplateContext {
@@ -36,6 +55,14 @@ impl TemplateContext {
             class_name: None,
             package_name: None,
             module_path: None,
+            methods: Vec::new(),
+            method_naming: MethodNaming::default(),
+            method_prefix: None,
+            property_library: None,
+            source_import: None,
+            assertion_lib: None,
+            parameterized: false,
+            section_markers: false,
         }
     }
 
@@ -53,6 +80,71 @@ impl TemplateContext {
         self.module_path = Some(module_path);
         self
     }
+
+    pub fn with_methods(mut self, methods: Vec<MethodSignature>) -> Self {
+        self.methods = methods;
+        self
+    }
+
+    pub fn with_method_naming(mut self, method_naming: MethodNaming) -> Self {
+        self.method_naming = method_naming;
+        self
+    }
+
+    pub fn with_method_prefix(mut self, method_prefix: String) -> Self {
+        self.method_prefix = Some(method_prefix);
+        self
+    }
+
+    pub fn with_property_library(mut self, property_library: PropertyLibrary) -> Self {
+        self.property_library = Some(property_library);
+        self
+    }
+
+    pub fn with_source_import(mut self, source_import: String) -> Self {
+        self.source_import = Some(source_import);
+        self
+    }
+
+    pub fn with_assertion_lib(mut self, assertion_lib: String) -> Self {
+        self.assertion_lib = Some(assertion_lib);
+        self
+    }
+
+    pub fn with_parameterized(mut self, parameterized: bool) -> Self {
+        self.parameterized = parameterized;
+        self
+    }
+
+    pub fn with_section_markers(mut self, section_markers: bool) -> Self {
+        self.section_markers = section_markers;
+        self
+    }
+
+    /// The per-method test stubs, inside fold markers at `indent` when section markers are on
+    pub fn section(&self, stubs: String, indent: &str) -> String {
+        if self.section_markers {
+            format!("{}// region Generated tests\n{}{}// endregion\n", indent, stubs, indent)
+        } else {
+            stubs
+        }
+    }
+
+    /// Prefix for generated test function names (`test` for JUnit, `test_` for Rust)
+    pub fn test_prefix(&self) -> &str {
+        self.method_prefix
+            .as_deref()
+            .unwrap_or_else(|| method_stub::default_test_prefix(self.framework))
+    }
+
+    /// Test class name taken from the test file name (FooTests.java -> FooTests)
+    pub fn test_class_name(&self) -> Option<String> {
+        self.test_file_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .filter(|stem| !stem.is_empty())
+            .map(|stem| stem.to_string())
+    }
 }
 
 /// Trait for generating test file content
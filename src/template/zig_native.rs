@@ -0,0 +1,55 @@
+use crate::cli::{Framework, Language};
+use crate::error::TestsmithError;
+use crate::template::traits::{TemplateContext, TemplateGenerator};
+
+/// Zig `test` block appended to the source file, run by `zig test` / `zig build test`
+pub struct ZigNativeTemplate;
+
+impl ZigNativeTemplate {
+    pub fn new() -> Self {
+        ZigNativeTemplate
+    }
+}
+
+impl Default for ZigNativeTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateGenerator for ZigNativeTemplate {
+    fn generate(&self, _context: &TemplateContext) -> Result<String, TestsmithError> {
+        Ok("test \"example\" {\n    // TODO: Implement test\n}\n".to_string())
+    }
+
+    fn name(&self) -> &'static str {
+        "Zig Native"
+    }
+
+    fn language(&self) -> Language {
+        Language::Zig
+    }
+
+    fn framework(&self) -> Framework {
+        Framework::Native
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_template() {
+        let template = ZigNativeTemplate::new();
+        let context = TemplateContext::new(
+            "src/parser.zig".into(),
+            "src/parser.zig".into(),
+            Language::Zig,
+            Framework::Native,
+        );
+
+        let result = template.generate(&context).unwrap();
+        assert_eq!(result, "test \"example\" {\n    // TODO: Implement test\n}\n");
+    }
+}
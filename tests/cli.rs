@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// Run the CLI with an isolated cache directory
+fn run_cli(data_home: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_testsmith-nvim"))
+        .args(args)
+        .env("XDG_DATA_HOME", data_home)
+        .output()
+        .expect("Failed to run testsmith-nvim")
+}
+
+#[test]
+fn test_json_output_for_created_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let source = temp_dir.path().join("src/main/java/com/example/Foo.java");
+    fs::create_dir_all(source.parent().unwrap()).unwrap();
+    fs::write(&source, "package com.example;\n\npublic class Foo {}\n").unwrap();
+
+    let output = run_cli(
+        &temp_dir.path().join("data"),
+        &[source.to_str().unwrap(), "-l", "java", "-f", "junit", "--format", "json"],
+    );
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["test_file_path"].as_str().unwrap().ends_with("src/test/java/com/example/FooTest.java"));
+    assert_eq!(json["created"], true);
+    assert_eq!(json["dry_run"], false);
+    assert_eq!(json["line_number"], 9);
+}
+
+#[test]
+fn test_json_output_for_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let source = temp_dir.path().join("Missing.java");
+
+    let output = run_cli(
+        &temp_dir.path().join("data"),
+        &[source.to_str().unwrap(), "--format", "json"],
+    );
+    assert!(!output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["error"].as_str().unwrap().contains("Missing.java"));
+}
+
+#[test]
+fn test_clear_removes_cache_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let data_home = temp_dir.path().join("data");
+    let cache_file = data_home.join("nvim/testsmith/testsmith.projects.json");
+    fs::create_dir_all(cache_file.parent().unwrap()).unwrap();
+    fs::write(&cache_file, "{}").unwrap();
+
+    let output = run_cli(&data_home, &["clear"]);
+    assert!(output.status.success());
+    assert!(!cache_file.exists());
+}
+
+#[test]
+fn test_dry_run_prints_preview() {
+    let temp_dir = TempDir::new().unwrap();
+    let source = temp_dir.path().join("src/main/java/com/example/Foo.java");
+    fs::create_dir_all(source.parent().unwrap()).unwrap();
+    fs::write(&source, "package com.example;\n\npublic class Foo {}\n").unwrap();
+
+    let output = run_cli(
+        &temp_dir.path().join("data"),
+        &[source.to_str().unwrap(), "-l", "java", "-f", "junit", "--dry-run"],
+    );
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("Would create test file: "));
+    assert!(stdout.contains("package com.example;"));
+    assert!(stdout.contains("class FooTest {"));
+    assert!(!temp_dir.path().join("src/test/java/com/example/FooTest.java").exists());
+}
+
+#[test]
+fn test_list_candidates_prints_each_location() {
+    let temp_dir = TempDir::new().unwrap();
+    let source = temp_dir.path().join("src/cart.js");
+    fs::create_dir_all(source.parent().unwrap()).unwrap();
+    fs::write(&source, "module.exports = {};\n").unwrap();
+
+    let output = run_cli(
+        &temp_dir.path().join("data"),
+        &[source.to_str().unwrap(), "-s", "adjacent", "-f", "jasmine", "--list-candidates"],
+    );
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let candidates: Vec<&str> = stdout.lines().collect();
+    assert_eq!(candidates.len(), 2);
+    assert!(candidates[0].ends_with("src/cart.test.js"));
+    assert!(candidates[1].ends_with("src/__tests__/cart.test.js"));
+    assert!(!temp_dir.path().join("src/cart.test.js").exists());
+}